@@ -0,0 +1,207 @@
+//! Derive macros for il2cpp-rs
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote, quote_spanned};
+use std::ffi::CString;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitCStr, LitStr, Meta, Token};
+
+/// Derives `Il2CppLayout` for a `#[repr(C)]` struct mirroring a managed class
+///
+/// # Attributes
+///
+/// * `#[il2cpp(class = "Namespace.Class")]` - Full name of the mirrored class, required
+/// * `#[il2cpp(assembly = "Assembly-CSharp")]` - Assembly declaring the class, optional
+/// * `#[il2cpp(name = "m_Field")]` - Managed name of a field, the Rust name by default
+/// * `#[il2cpp(skip)]` - Field that is not a managed field, such as the object header
+///
+/// Every other field gets a getter named after it, and an unsafe setter prefixed by `set_`
+/// writing through the runtime. Getters return the field by value, so mirrored fields must be
+/// `Copy`, such as primitives and object handles.
+#[proc_macro_derive(Il2CppLayout, attributes(il2cpp))]
+pub fn derive_il2cpp_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    layout(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Managed field mirrored by a Rust field
+struct Field<'a> {
+    field: &'a syn::Field,
+    ident: &'a syn::Ident,
+    managed_name: String,
+}
+
+fn layout(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "Il2CppLayout cannot be derived for generic structs",
+        ));
+    }
+    if !is_repr_c(&input.attrs)? {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Il2CppLayout requires `#[repr(C)]`",
+        ));
+    }
+
+    let mut class = None;
+    let mut assembly = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("il2cpp"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("class") {
+                class = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("assembly") {
+                assembly = Some(meta.value()?.parse::<LitStr>()?);
+            } else {
+                return Err(meta.error("expected `class` or `assembly`"));
+            }
+            Ok(())
+        })?;
+    }
+    let class = class.ok_or_else(|| {
+        syn::Error::new_spanned(ident, "missing `#[il2cpp(class = \"Namespace.Class\")]`")
+    })?;
+    let assembly = match assembly {
+        Some(assembly) => {
+            let assembly = c_str(&assembly.value(), assembly.span())?;
+            quote!(::core::option::Option::Some(#assembly))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Il2CppLayout can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &data.fields,
+            "Il2CppLayout requires named fields",
+        ));
+    };
+
+    let mut fields = Vec::new();
+    for field in &named.named {
+        let field_ident = field.ident.as_ref().unwrap();
+        let mut skip = false;
+        let mut managed_name = field_ident.to_string();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("il2cpp"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                } else if meta.path.is_ident("name") {
+                    managed_name = meta.value()?.parse::<LitStr>()?.value();
+                } else {
+                    return Err(meta.error("expected `skip` or `name`"));
+                }
+                Ok(())
+            })?;
+        }
+        if !skip {
+            fields.push(Field {
+                field,
+                ident: field_ident,
+                managed_name,
+            });
+        }
+    }
+
+    let mut descriptors = Vec::new();
+    let mut accessors = Vec::new();
+    for Field {
+        field,
+        ident: field_ident,
+        managed_name,
+    } in &fields
+    {
+        let ty = &field.ty;
+        let vis = &field.vis;
+        let name = field_ident.to_string();
+        let managed = c_str(managed_name, Span::call_site())?;
+        let setter = format_ident!("set_{}", field_ident);
+
+        descriptors.push(quote! {
+            ::il2cpp_rs::LayoutField {
+                name: #name,
+                managed_name: #managed,
+                offset: ::core::mem::offset_of!(#ident, #field_ident),
+                size: ::core::mem::size_of::<#ty>(),
+            }
+        });
+
+        // Reports a field type that is not `Copy` on the field rather than in the getter body
+        let value = quote_spanned! {ty.span()=>
+            ::il2cpp_rs::layout_value(&self.#field_ident)
+        };
+        let getter_doc = format!("Returns the `{managed_name}` field");
+        let setter_doc = format!("Writes the `{managed_name}` field through the runtime");
+        accessors.push(quote! {
+            #[doc = #getter_doc]
+            #[inline]
+            #vis fn #field_ident(&self) -> #ty {
+                #value
+            }
+
+            #[doc = #setter_doc]
+            ///
+            /// # Safety
+            ///
+            /// `self` must be a managed instance of the class, unboxed for value types
+            ///
+            /// # Panics
+            ///
+            /// Panics if the class or the field is not found
+            #[track_caller]
+            #vis unsafe fn #setter(&mut self, value: #ty) {
+                static FIELD: ::std::sync::OnceLock<usize> = ::std::sync::OnceLock::new();
+                unsafe { ::il2cpp_rs::set_layout_field(self, &FIELD, #managed, value) }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::il2cpp_rs::Il2CppLayout for #ident {
+            const CLASS: &'static str = #class;
+            const ASSEMBLY: ::core::option::Option<&'static ::core::ffi::CStr> = #assembly;
+            const FIELDS: &'static [::il2cpp_rs::LayoutField] = &[#(#descriptors),*];
+        }
+
+        impl #ident {
+            #(#accessors)*
+        }
+    })
+}
+
+/// Returns `true` if the struct has a `#[repr(C)]` attribute
+fn is_repr_c(attrs: &[Attribute]) -> syn::Result<bool> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let reprs = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+        if reprs.iter().any(|repr| repr.path().is_ident("C")) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns a C string literal of `value`
+fn c_str(value: &str, span: Span) -> syn::Result<LitCStr> {
+    let value = CString::new(value)
+        .map_err(|_| syn::Error::new(span, "names cannot contain a nul byte"))?;
+    Ok(LitCStr::new(&value, span))
+}
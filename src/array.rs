@@ -1,11 +1,14 @@
 //! Il2CppArray
 
 use crate::class::Il2CppClass;
-use crate::{NonNullRef, Ref};
-use il2cpp_sys_rs::{
+use crate::runtime::{
     il2cpp_array_class_get, il2cpp_array_get_byte_length, il2cpp_array_new,
-    il2cpp_array_new_full, il2cpp_array_new_specific, il2cpp_array_size_t, il2cpp_bounded_array_class_get,
-    il2cpp_gc_wbarrier_set_field, Il2CppArrayBounds, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY,
+    il2cpp_array_new_full, il2cpp_array_new_specific, il2cpp_bounded_array_class_get,
+    il2cpp_gc_wbarrier_set_field,
+};
+use crate::{Il2CppError, NonNullRef, Ref};
+use il2cpp_sys_rs::{
+    il2cpp_array_size_t, Il2CppArrayBounds, Il2CppTypeEnum, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY,
     Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY,
 };
 use std::any::type_name;
@@ -138,10 +141,15 @@ impl<T> Il2CppSzArray<T> {
     ///
     /// * `element_class` - Inflated element class
     /// * `len` - Array length
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the array
     #[inline]
-    pub fn new(element_class: Il2CppClass, len: il2cpp_array_size_t) -> Option<Self> {
+    pub fn new(element_class: Il2CppClass, len: il2cpp_array_size_t) -> Result<Self, Il2CppError> {
         // Note: `il2cpp_array_new` use `il2cpp_array_class_get` then `il2cpp_array_new_specific`
         unsafe { Self::from_ptr(il2cpp_array_new(element_class.as_ptr(), len)) }
+            .ok_or(Il2CppError::AllocationFailed("array"))
     }
 
     /// Allocate a new SZ array from an existing array class
@@ -151,26 +159,25 @@ impl<T> Il2CppSzArray<T> {
     /// Create an array class using [`Self::array_class_get`]
     /// and reuse it for multiple allocations
     ///
-    /// # Panics
-    ///
-    /// Panics if `array_class` is not a `IL2CPP_TYPE_SZARRAY`\
-    /// Panics when `array_class::rank` != 1
-    ///
     /// # Arguments
     ///
     /// * `array_class` - Inflated SZ array class
     /// * `len` - Array length
-    #[track_caller]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::TypeMismatch`] if `array_class` is not a `IL2CPP_TYPE_SZARRAY`,
+    /// or [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the array
     #[inline]
-    pub fn new_specific(array_class: Il2CppClass, len: il2cpp_array_size_t) -> Option<Self> {
-        assert_eq!(
-            array_class.as_ref().byval_arg.type_(),
-            Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY
-        );
-        assert_eq!(array_class.as_ref().rank, 1);
+    pub fn new_specific(
+        array_class: Il2CppClass,
+        len: il2cpp_array_size_t,
+    ) -> Result<Self, Il2CppError> {
+        check_array_class(array_class, Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY, 1)?;
 
         // Note: `il2cpp_array_new_specific` create only SZ array
         unsafe { Self::from_ptr(il2cpp_array_new_specific(array_class.as_ptr(), len)) }
+            .ok_or(Il2CppError::AllocationFailed("array"))
     }
 
     /// Get the SZ array class for an element class
@@ -180,9 +187,14 @@ impl<T> Il2CppSzArray<T> {
     /// # Arguments
     ///
     /// * `element_class` - Inflated element class
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot create the array class
     #[inline]
-    pub fn array_class_get(element_class: Il2CppClass) -> Option<Il2CppClass> {
+    pub fn array_class_get(element_class: Il2CppClass) -> Result<Il2CppClass, Il2CppError> {
         unsafe { Il2CppClass::from_ptr(il2cpp_array_class_get(element_class.as_ptr(), 1)) }
+            .ok_or(Il2CppError::AllocationFailed("array class"))
     }
 
     /// View as slice
@@ -291,30 +303,30 @@ impl<T, const R: usize> Il2CppMdArray<T, R> {
     ///
     /// Use [`Self::bounded_array_class_get`] to create the specific `array_class`
     ///
-    /// # Panics
-    ///
-    /// Panics if `array_class` is not a `IL2CPP_TYPE_ARRAY`\
-    /// Panics when `array_class::rank` != `R`\
-    /// Panics when `lower_bounds == [0]` for rank 1, as IL2CPP will create an SZ array
-    ///
     /// # Arguments
     ///
     /// * `array_class` - Inflated MD array class of rank `R`
     /// * `lengths` - Per-dimension lengths
     /// * `lower_bounds` - Per-dimension lower bounds
-    #[track_caller]
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::TypeMismatch`] if `array_class` is not a `IL2CPP_TYPE_ARRAY`
+    /// of rank `R` or if `lower_bounds == [0]` for rank 1, as IL2CPP would create an SZ array,
+    /// or [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the array
     #[inline]
     pub fn new(
         array_class: Il2CppClass,
         lengths: &mut [il2cpp_array_size_t; R],
         lower_bounds: &mut [il2cpp_array_size_t; R],
-    ) -> Option<Self> {
-        assert_eq!(
-            array_class.as_ref().byval_arg.type_(),
-            Il2CppTypeEnum_IL2CPP_TYPE_ARRAY
-        );
-        assert_eq!(array_class.as_ref().rank as usize, R);
-        assert!(R > 1 || lower_bounds[0] != 0);
+    ) -> Result<Self, Il2CppError> {
+        check_array_class(array_class, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY, R)?;
+        if R == 1 && lower_bounds[0] == 0 {
+            return Err(Il2CppError::TypeMismatch {
+                expected: "rank 1 array with a non-zero lower bound".into(),
+                found: "SZ array".into(),
+            });
+        }
 
         unsafe {
             Self::from_ptr(il2cpp_array_new_full(
@@ -323,6 +335,7 @@ impl<T, const R: usize> Il2CppMdArray<T, R> {
                 lower_bounds.as_mut_ptr(),
             ))
         }
+        .ok_or(Il2CppError::AllocationFailed("array"))
     }
 
     /// Get the MD array class for an element class
@@ -334,9 +347,15 @@ impl<T, const R: usize> Il2CppMdArray<T, R> {
     /// # Arguments
     ///
     /// * `element_class` - Inflated element class
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot create the array class
     #[track_caller]
     #[inline]
-    pub fn bounded_array_class_get(element_class: Il2CppClass) -> Option<Il2CppClass> {
+    pub fn bounded_array_class_get(
+        element_class: Il2CppClass,
+    ) -> Result<Il2CppClass, Il2CppError> {
         assert_ne!(R, 0);
 
         unsafe {
@@ -346,6 +365,7 @@ impl<T, const R: usize> Il2CppMdArray<T, R> {
                 true,
             ))
         }
+        .ok_or(Il2CppError::AllocationFailed("array class"))
     }
 
     /// Returns the bounds of each dimension
@@ -366,6 +386,29 @@ impl<T, const R: usize> Il2CppMdArray<T, R> {
     }
 }
 
+/// Checks the kind and rank of an array class
+fn check_array_class(
+    array_class: Il2CppClass,
+    type_enum: Il2CppTypeEnum,
+    rank: usize,
+) -> Result<(), Il2CppError> {
+    if array_class.as_ref().byval_arg.type_() == type_enum
+        && array_class.as_ref().rank as usize == rank
+    {
+        Ok(())
+    } else {
+        let expected = if type_enum == Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY {
+            "SZ array class".into()
+        } else {
+            format!("array class of rank {rank}").into()
+        };
+        Err(Il2CppError::TypeMismatch {
+            expected,
+            found: array_class.full_name(),
+        })
+    }
+}
+
 impl<T> Deref for Il2CppSzArray<T> {
     type Target = [T];
 
@@ -385,3 +428,52 @@ impl<T: fmt::Debug, const R: usize> fmt::Debug for Il2CppMdArray<T, R> {
         }
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock;
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Il2CppMdArray, Il2CppSzArray};
+
+    fn int32() -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap()
+    }
+
+    #[test]
+    fn sz_array() {
+        mock::install();
+        let array = Il2CppSzArray::<i32>::new(int32(), 4).unwrap();
+        array.set_value(2, 7);
+
+        assert_eq!(&*array, &[0, 0, 7, 0]);
+        assert_eq!(array.len(), 4);
+        assert_eq!(array.byte_len(), 16);
+        assert_eq!(array.element_size(), 4);
+        assert_eq!(array.class().name(), c"Int32[]");
+
+        let class = Il2CppSzArray::<i32>::array_class_get(int32()).unwrap();
+        let array = Il2CppSzArray::<i32>::new_specific(class, 2).unwrap();
+        assert_eq!(&*array, &[0, 0]);
+    }
+
+    #[test]
+    fn md_array() {
+        mock::install();
+        let class = Il2CppMdArray::<i32, 2>::bounded_array_class_get(int32()).unwrap();
+        let array = Il2CppMdArray::<i32, 2>::new(class, &mut [2, 3], &mut [0, 0]).unwrap();
+        assert_eq!(array.len(), 6);
+        assert_eq!(array.rank(), 2);
+
+        // A rank 2 array class is not a single-dimension array class
+        let err = Il2CppSzArray::<i32>::new_specific(class, 1).err().unwrap();
+        assert!(matches!(err, Il2CppError::TypeMismatch { .. }));
+        assert_eq!(err.to_string(), "expected `SZ array class`, found `System.Int32[,]`");
+
+        // IL2CPP would create an SZ array
+        let class = Il2CppMdArray::<i32, 1>::bounded_array_class_get(int32()).unwrap();
+        let err = Il2CppMdArray::<i32, 1>::new(class, &mut [2], &mut [0]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "expected `rank 1 array with a non-zero lower bound`, found `SZ array`"
+        );
+    }
+}
@@ -1,10 +1,8 @@
 //! Il2CppAssembly
 
-use il2cpp_sys_rs::{il2cpp_domain_get, il2cpp_domain_get_assemblies, Il2CppDomain};
-
-use crate::{Il2CppImage, NonNullRef, Ref};
+use crate::{Il2CppDomain, Il2CppImage, NonNullRef, Ref};
 use std::ffi::CStr;
-use std::{fmt, slice};
+use std::fmt;
 
 /// Assembly handle
 pub type Il2CppAssembly = NonNullRef<il2cpp_sys_rs::Il2CppAssembly, ()>;
@@ -43,22 +41,11 @@ impl Il2CppAssembly {
     /// Assembly handle if found, otherwise `None`
     #[inline]
     pub fn from_name(name: &CStr) -> Option<Self> {
-        unsafe {
-            let domain: NonNullRef<Il2CppDomain, ()> =
-                Ref::new(il2cpp_domain_get()).unwrap_non_null();
-
-            let mut size = 0;
-            let assemblies = il2cpp_domain_get_assemblies(domain.as_ptr(), &mut size);
-            // Safety: null check is bypassed as assemblies should never be null
-            let assemblies_slice = slice::from_raw_parts(assemblies as *mut Self, size);
-            for &assembly in assemblies_slice {
-                if assembly.name() == name {
-                    return Some(assembly);
-                }
-            }
-
-            None
-        }
+        Il2CppDomain::get()
+            .assemblies()
+            .iter()
+            .copied()
+            .find(|assembly| assembly.name() == name)
     }
 }
 
@@ -0,0 +1,80 @@
+//! Generates Rust bindings from a JSON snapshot of the loaded assemblies
+
+use il2cpp_rs::bindgen::Generator;
+use il2cpp_rs::DomainDescriptor;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::{env, fs, process};
+
+const USAGE: &str = "\
+Usage: il2cpp-bindgen <domain.json> [options]
+
+Options:
+    -o, --output <file>       Write the bindings to <file> instead of stdout
+    -a, --assembly <name>     Generate the classes of an assembly, can be repeated
+    -n, --namespace <name>    Generate the classes of a namespace, can be repeated
+    -h, --help                Print this help";
+
+/// Command line arguments
+#[derive(Default)]
+struct Arguments {
+    input: Option<String>,
+    output: Option<String>,
+    assemblies: Vec<String>,
+    namespaces: Vec<String>,
+}
+
+impl Arguments {
+    /// Parses the command line, printing the usage on `--help`
+    fn parse() -> Result<Self, String> {
+        let mut arguments = Self::default();
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("missing value for `{arg}`"));
+            match arg.as_str() {
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    process::exit(0);
+                }
+                "-o" | "--output" => arguments.output = Some(value()?),
+                "-a" | "--assembly" => arguments.assemblies.push(value()?),
+                "-n" | "--namespace" => arguments.namespaces.push(value()?),
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+                _ if arguments.input.is_none() => arguments.input = Some(arg),
+                _ => return Err(format!("unexpected argument `{arg}`")),
+            }
+        }
+        Ok(arguments)
+    }
+}
+
+fn run(arguments: Arguments) -> Result<(), Box<dyn Error>> {
+    let input = arguments.input.ok_or("missing snapshot path")?;
+    let domain = DomainDescriptor::from_json(&fs::read_to_string(&input)?)?;
+
+    let generator = arguments
+        .assemblies
+        .into_iter()
+        .fold(Generator::new(&domain), Generator::assembly);
+    let generator = arguments
+        .namespaces
+        .into_iter()
+        .fold(generator, Generator::namespace);
+
+    match arguments.output {
+        Some(output) => generator.write(BufWriter::new(File::create(output)?))?,
+        None => generator.write(io::stdout().lock())?,
+    }
+    Ok(())
+}
+
+fn main() {
+    let result = Arguments::parse()
+        .map_err(|err| format!("{err}\n\n{USAGE}").into())
+        .and_then(run);
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,740 @@
+//! Rust bindings generated from a snapshot of the loaded assemblies
+//!
+//! Every class becomes a typed handle with lazily cached class, field and method lookups,
+//! and accessors for the fields, properties and methods whose types map to Rust types.
+//! Namespaces become nested modules, such as `unity_engine::ui` for `UnityEngine.UI`.
+//!
+//! Structs, enums and interfaces are not generated, neither are members using them,
+//! nor members using generic or by-reference types.
+//!
+//! The `il2cpp-bindgen` binary generates the bindings from the command line.
+//!
+//! Only snapshots of a running game are supported, `global-metadata.dat` files are not.
+//!
+//! # Example
+//!
+//! Generating the bindings from a `build.rs` script, from a snapshot exported with
+//! [`Il2CppDomain::snapshot`](crate::Il2CppDomain::snapshot):
+//!
+//! ```no_run
+//! use il2cpp_rs::bindgen::Generator;
+//! use il2cpp_rs::DomainDescriptor;
+//! use std::path::Path;
+//!
+//! let domain = DomainDescriptor::from_json(&std::fs::read_to_string("domain.json")?)?;
+//! let out = Path::new(&std::env::var("OUT_DIR")?).join("game.rs");
+//!
+//! Generator::new(&domain)
+//!     .assembly("Assembly-CSharp")
+//!     .write(std::fs::File::create(out)?)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The bindings are then included in the crate:
+//!
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/game.rs"));
+//!
+//! let player = game::Player::from_object(object).unwrap();
+//! player.set_health(100);
+//! player.Jump()?;
+//! ```
+
+use crate::flags::{
+    FIELD_ATTRIBUTE_INIT_ONLY, FIELD_ATTRIBUTE_LITERAL, FIELD_ATTRIBUTE_STATIC,
+    METHOD_ATTRIBUTE_STATIC,
+};
+use crate::{AssemblyDescriptor, ClassDescriptor, DomainDescriptor, MethodDescriptor};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io;
+
+/// Rust keywords that can be raw identifiers
+const KEYWORDS: [&str; 47] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+    "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized",
+    "use", "virtual", "where", "while",
+];
+
+/// Rust keywords that cannot be raw identifiers
+const RESERVED: [&str; 5] = ["_", "crate", "self", "Self", "super"];
+
+/// Generates Rust bindings from a [`DomainDescriptor`]
+///
+/// Every class is generated unless filtered out by assembly or namespace.
+#[derive(Debug)]
+pub struct Generator<'a> {
+    domain: &'a DomainDescriptor,
+    /// Assemblies to generate, every assembly if empty
+    assemblies: Vec<String>,
+    /// Namespaces to generate, every namespace if empty
+    namespaces: Vec<String>,
+    /// Classes of the domain by full name, nested classes are separated by `+`
+    classes: HashMap<&'a str, &'a ClassDescriptor>,
+}
+
+/// Where a type is used, which decides its Rust representation
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    /// Field value, read and written in place
+    Field,
+    /// Method argument
+    Argument,
+    /// Method return value
+    Return,
+}
+
+/// Module of the generated bindings
+#[derive(Default)]
+struct Module {
+    modules: BTreeMap<String, Module>,
+    items: Vec<String>,
+    /// Item names, to deduplicate them
+    names: HashSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    /// Creates a generator of every class of `domain`
+    ///
+    /// # Arguments
+    ///
+    /// * `domain` - Snapshot of the loaded assemblies
+    pub fn new(domain: &'a DomainDescriptor) -> Self {
+        let classes = domain
+            .assemblies
+            .iter()
+            .flat_map(|assembly| &assembly.image.classes)
+            .map(|class| (class.full_name.as_str(), class))
+            .collect();
+        Self {
+            domain,
+            assemblies: Vec::new(),
+            namespaces: Vec::new(),
+            classes,
+        }
+    }
+
+    /// Generates the classes of `assembly`, can be called several times
+    ///
+    /// # Arguments
+    ///
+    /// * `assembly` - Assembly name, such as `Assembly-CSharp`
+    pub fn assembly(mut self, assembly: impl Into<String>) -> Self {
+        self.assemblies.push(assembly.into());
+        self
+    }
+
+    /// Generates the classes of `namespace`, can be called several times
+    ///
+    /// Nested classes belong to the namespace of their outermost declaring class.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Exact namespace, empty for global
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespaces.push(namespace.into());
+        self
+    }
+
+    /// Returns the generated bindings
+    pub fn generate(&self) -> String {
+        let mut root = Module::default();
+        let assemblies = self.domain.assemblies.iter().filter(|assembly| {
+            self.assemblies.is_empty() || self.assemblies.contains(&assembly.name)
+        });
+
+        for assembly in assemblies {
+            for class in &assembly.image.classes {
+                let generated = !class.is_value_type && !class.is_interface;
+                let path = self.declaring_types(class);
+                let namespace = &path[0].namespace;
+                if !generated
+                    || path.iter().any(|class| class.name.contains('<'))
+                    || !(self.namespaces.is_empty() || self.namespaces.contains(namespace))
+                {
+                    continue;
+                }
+
+                let module = namespace
+                    .split('.')
+                    .filter(|segment| !segment.is_empty())
+                    .fold(&mut root, |module, segment| {
+                        module.modules.entry(snake_case(segment)).or_default()
+                    });
+                let name = path
+                    .iter()
+                    .map(|class| class.name.replace('`', "_"))
+                    .collect::<Vec<_>>()
+                    .join("_");
+                let name = unique(&mut module.names, identifier(&name));
+                let lazy = format!("{}_CLASS", name.trim_start_matches("r#").to_uppercase());
+                let lazy = unique(&mut module.names, lazy);
+                module.items.push(self.class_item(assembly, &path, &name, &lazy));
+            }
+        }
+
+        let mut out = String::from("// Generated by il2cpp-rs, do not edit\n\n");
+        root.write(&mut out, 0);
+        out
+    }
+
+    /// Writes the generated bindings into `out`
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Output, such as a file included by the crate
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `out`
+    pub fn write(&self, mut out: impl io::Write) -> io::Result<()> {
+        out.write_all(self.generate().as_bytes())
+    }
+
+    /// Returns the outermost declaring class of `class` down to `class`
+    fn declaring_types(&self, class: &'a ClassDescriptor) -> Vec<&'a ClassDescriptor> {
+        let mut path = vec![class];
+        while let Some(declaring_type) = path[0]
+            .declaring_type
+            .as_deref()
+            .and_then(|name| self.classes.get(name))
+        {
+            path.insert(0, declaring_type);
+        }
+        path
+    }
+
+    /// Returns the Rust type of a managed type, if it has one
+    ///
+    /// # Arguments
+    ///
+    /// * `type_name` - Fully qualified type name
+    /// * `position` - Where the type is used
+    fn rust_type(&self, type_name: &str, position: Position) -> Option<&'static str> {
+        let rust_type = match type_name {
+            "System.Void" if position == Position::Return => "()",
+            "System.Boolean" => "bool",
+            "System.Char" if position == Position::Field => "u16",
+            "System.Char" => "char",
+            "System.SByte" => "i8",
+            "System.Byte" => "u8",
+            "System.Int16" => "i16",
+            "System.UInt16" => "u16",
+            "System.Int32" => "i32",
+            "System.UInt32" => "u32",
+            "System.Int64" => "i64",
+            "System.UInt64" => "u64",
+            "System.IntPtr" => "isize",
+            "System.UIntPtr" => "usize",
+            "System.Single" => "f32",
+            "System.Double" => "f64",
+            "System.String" => "::il2cpp_rs::Il2CppStringRef",
+            "System.Object" => "::il2cpp_rs::Il2CppObjectRef",
+            _ if type_name.ends_with("[]") => "::il2cpp_rs::Il2CppObjectRef",
+            _ => {
+                let class = self.classes.get(type_name.replace('/', "+").as_str())?;
+                if class.is_value_type || class.is_generic {
+                    return None;
+                }
+                "::il2cpp_rs::Il2CppObjectRef"
+            }
+        };
+        Some(rust_type)
+    }
+
+    /// Returns the handle of a class and its accessors
+    ///
+    /// # Arguments
+    ///
+    /// * `assembly` - Assembly of the class
+    /// * `path` - Outermost declaring class down to the class
+    /// * `name` - Name of the handle
+    /// * `lazy` - Name of the lazy class lookup
+    fn class_item(
+        &self,
+        assembly: &AssemblyDescriptor,
+        path: &[&ClassDescriptor],
+        name: &str,
+        lazy: &str,
+    ) -> String {
+        let class = path[path.len() - 1];
+        let namespace = &path[0].namespace;
+        let nested_name = path
+            .iter()
+            .map(|class| class.name.as_str())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "static {lazy}: ::il2cpp_rs::LazyClass =\n    \
+             ::il2cpp_rs::LazyClass::new(c{:?}, c{namespace:?}, c{nested_name:?});\n",
+            assembly.name
+        );
+        let _ = writeln!(out, "/// `{}`, from `{}`", class.full_name, assembly.name);
+        out.push_str("#[allow(non_camel_case_types)]\n");
+        out.push_str("#[derive(Clone, Copy, Debug)]\n");
+        out.push_str("#[repr(transparent)]\n");
+        let _ = writeln!(out, "pub struct {name}(::il2cpp_rs::Il2CppObject);\n");
+        out.push_str("#[allow(non_snake_case, clippy::too_many_arguments)]\n");
+        let _ = writeln!(out, "impl {name} {{");
+
+        let mut names: HashSet<String> =
+            ["class", "from_object", "object"].map(String::from).into();
+        let _ = write!(
+            out,
+            "    /// Returns the `{full_name}` class
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class is not loaded
+    pub fn class() -> ::il2cpp_rs::Il2CppClass {{
+        {lazy}.get()
+    }}
+
+    /// Wraps `object` if it is an instance of the class, or of a class derived from it
+    pub fn from_object(object: ::il2cpp_rs::Il2CppObject) -> Option<Self> {{
+        let class = Self::class();
+        let object_class = object.class();
+        (object_class.as_ptr() == class.as_ptr() || object_class.is_subclass_of(class, false))
+            .then_some(Self(object))
+    }}
+
+    /// Returns the wrapped object
+    pub fn object(self) -> ::il2cpp_rs::Il2CppObject {{
+        self.0
+    }}
+",
+            full_name = class.full_name,
+        );
+
+        for field in &class.fields {
+            let Some(rust_type) = self.rust_type(&field.type_name, Position::Field) else {
+                continue;
+            };
+            if field.name.contains('<') {
+                continue;
+            }
+
+            let static_ = field.flags & FIELD_ATTRIBUTE_STATIC != 0;
+            let readonly = field.flags & (FIELD_ATTRIBUTE_INIT_ONLY | FIELD_ATTRIBUTE_LITERAL) != 0;
+            let getter = unique(&mut names, identifier(&field.name));
+            let setter = unique(&mut names, identifier(&format!("set_{}", field.name)));
+            let handle = unique(&mut names, identifier(&format!("{}_field", field.name)));
+            let declaration = format!("{} {}", field.type_name, field.name);
+
+            let _ = write!(
+                out,
+                "
+    /// Returns the `{declaration}` field
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class has no such field
+    pub fn {handle}() -> ::il2cpp_rs::FieldInfo {{
+        static FIELD: ::il2cpp_rs::LazyField = ::il2cpp_rs::LazyField::new(&{lazy}, c{name:?});
+        FIELD.get()
+    }}
+",
+                name = field.name,
+            );
+
+            let (this, get, set) = if static_ {
+                ("", "static_value()", "set_static_value(value)")
+            } else {
+                (
+                    "self",
+                    "value(self.0.as_ptr() as _)",
+                    "set_value(self.0.as_ptr() as _, value)",
+                )
+            };
+            let _ = write!(
+                out,
+                "
+    /// Reads the `{declaration}` field
+    pub fn {getter}({this}) -> {rust_type} {{
+        unsafe {{ Self::{handle}().{get} }}
+    }}
+",
+            );
+            if !readonly {
+                let separator = if static_ { "" } else { ", " };
+                let _ = write!(
+                    out,
+                    "
+    /// Writes the `{declaration}` field
+    pub fn {setter}({this}{separator}value: {rust_type}) {{
+        unsafe {{ Self::{handle}().{set} }}
+    }}
+",
+                );
+            }
+        }
+
+        let mut accessors = HashSet::new();
+        for property in &class.properties {
+            let accessor_methods = [(&property.getter, 0), (&property.setter, 1)];
+            for (accessor, parameters) in accessor_methods {
+                let method = accessor.as_deref().and_then(|accessor| {
+                    class.methods.iter().find(|method| {
+                        method.name == accessor && method.parameters.len() == parameters
+                    })
+                });
+                if let Some(method) = method {
+                    accessors.insert(&method.name);
+                    let doc = format!(
+                        "Calls the `{} {}` property {}",
+                        property.type_name,
+                        property.name,
+                        if parameters == 0 { "getter" } else { "setter" }
+                    );
+                    self.method_item(&mut out, &mut names, lazy, method, &doc);
+                }
+            }
+        }
+
+        for method in &class.methods {
+            if !method.name.starts_with('.') && !accessors.contains(&method.name) {
+                let doc = format!("Calls `{}`", signature(method));
+                self.method_item(&mut out, &mut names, lazy, method, &doc);
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Writes the accessor of a method and its cached lookup, if its types map to Rust types
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - Body of the class implementation
+    /// * `names` - Names already used in the implementation
+    /// * `lazy` - Name of the lazy lookup of the class declaring the method
+    /// * `method` - Method to call
+    /// * `doc` - Documentation of the accessor
+    fn method_item(
+        &self,
+        out: &mut String,
+        names: &mut HashSet<String>,
+        lazy: &str,
+        method: &MethodDescriptor,
+        doc: &str,
+    ) {
+        let Some(return_type) = self.rust_type(&method.return_type, Position::Return) else {
+            return;
+        };
+        let Some(types) = method
+            .parameters
+            .iter()
+            .map(|parameter| self.rust_type(&parameter.type_name, Position::Argument))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+        if method.name.contains('<') {
+            return;
+        }
+
+        let name = unique(names, identifier(&method.name));
+        let handle = format!("{}_method", name.trim_start_matches("r#"));
+        let handle = unique(names, identifier(&handle));
+        let static_ = method.flags & METHOD_ATTRIBUTE_STATIC != 0;
+
+        let mut parameter_names = HashSet::from(["self".to_string()]);
+        let mut parameters = Vec::new();
+        let mut arguments = Vec::new();
+        if !static_ {
+            parameters.push("self".to_string());
+        }
+        for (i, (parameter, rust_type)) in method.parameters.iter().zip(types).enumerate() {
+            let name = if parameter.name.is_empty() {
+                format!("arg{i}")
+            } else {
+                identifier(&parameter.name)
+            };
+            let name = unique(&mut parameter_names, name);
+            parameters.push(format!("{name}: {rust_type}"));
+            arguments.push(name);
+        }
+        let arguments = match arguments.len() {
+            1 => format!("({},)", arguments[0]),
+            _ => format!("({})", arguments.join(", ")),
+        };
+        let parameter_types = method
+            .parameters
+            .iter()
+            .map(|parameter| format!("{:?}", parameter.type_name))
+            .collect::<Vec<_>>();
+        let parameter_types = parameter_types.join(", ");
+        let target = if static_ {
+            "::il2cpp_rs::Ref::null()"
+        } else {
+            "self.0.into()"
+        };
+
+        let _ = write!(
+            out,
+            "
+    /// Returns the `{signature}` method
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class has no such method
+    pub fn {handle}() -> ::il2cpp_rs::MethodInfo {{
+        static METHOD: ::il2cpp_rs::LazyMethod =
+            ::il2cpp_rs::LazyMethod::exact(&{lazy}, c{method_name:?}, &[{parameter_types}]);
+        METHOD.get()
+    }}
+
+    /// {doc}
+    pub fn {name}({parameters}) -> Result<{return_type}, ::il2cpp_rs::InvokeError> {{
+        Self::{handle}().call({target}, {arguments})
+    }}
+",
+            signature = signature(method),
+            method_name = method.name,
+            parameters = parameters.join(", "),
+        );
+    }
+}
+
+impl Module {
+    /// Writes the items and modules, indented by `depth` levels
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = "    ".repeat(depth);
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            for line in item.lines() {
+                if !line.is_empty() {
+                    out.push_str(&indent);
+                }
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        for (i, (name, module)) in self.modules.iter().enumerate() {
+            if i > 0 || !self.items.is_empty() {
+                out.push('\n');
+            }
+            let _ = writeln!(out, "{indent}pub mod {name} {{");
+            module.write(out, depth + 1);
+            let _ = writeln!(out, "{indent}}}");
+        }
+    }
+}
+
+/// Returns the C# signature of a method, such as `System.Void Jump(System.Single height)`
+fn signature(method: &MethodDescriptor) -> String {
+    let parameters = method
+        .parameters
+        .iter()
+        .map(|parameter| format!("{} {}", parameter.type_name, parameter.name))
+        .collect::<Vec<_>>();
+    format!(
+        "{} {}({})",
+        method.return_type,
+        method.name,
+        parameters.join(", ")
+    )
+}
+
+/// Converts a namespace segment to snake case, such as `unity_engine` for `UnityEngine`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    identifier(&snake)
+}
+
+/// Converts a managed name to a Rust identifier
+fn identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if RESERVED.contains(&identifier.as_str()) {
+        identifier.push('_');
+    } else if KEYWORDS.contains(&identifier.as_str()) {
+        identifier.insert_str(0, "r#");
+    }
+    identifier
+}
+
+/// Returns `name`, suffixed by a number if it is already used
+fn unique(names: &mut HashSet<String>, name: String) -> String {
+    let name = (0..)
+        .map(|i| match i {
+            0 => name.clone(),
+            _ => format!("{name}_{i}"),
+        })
+        .find(|name| !names.contains(name))
+        .unwrap();
+    names.insert(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    use crate::flags::{FIELD_ATTRIBUTE_STATIC, METHOD_ATTRIBUTE_STATIC};
+    use crate::{
+        AssemblyDescriptor, ClassDescriptor, DomainDescriptor, FieldDescriptor, ImageDescriptor,
+        MethodDescriptor, ParameterDescriptor, PropertyDescriptor,
+    };
+
+    fn class(namespace: &str, name: &str, declaring_type: Option<&str>) -> ClassDescriptor {
+        let full_name = match declaring_type {
+            Some(declaring_type) => format!("{declaring_type}+{name}"),
+            None => format!("{namespace}.{name}"),
+        };
+        ClassDescriptor {
+            name: name.to_owned(),
+            namespace: namespace.to_owned(),
+            full_name,
+            token: 0x0200_0001,
+            flags: 0,
+            parent: Some("System.Object".to_owned()),
+            declaring_type: declaring_type.map(str::to_owned),
+            interfaces: Vec::new(),
+            is_value_type: false,
+            is_enum: false,
+            is_interface: false,
+            is_generic: false,
+            fields: Vec::new(),
+            properties: Vec::new(),
+            methods: Vec::new(),
+        }
+    }
+
+    fn field(name: &str, type_name: &str, flags: u32) -> FieldDescriptor {
+        FieldDescriptor {
+            name: name.to_owned(),
+            type_name: type_name.to_owned(),
+            token: 0x0400_0001,
+            flags,
+            offset: 0x10,
+        }
+    }
+
+    fn method(name: &str, parameters: &[(&str, &str)], return_type: &str) -> MethodDescriptor {
+        MethodDescriptor {
+            name: name.to_owned(),
+            token: 0x0600_0001,
+            flags: 0,
+            impl_flags: 0,
+            return_type: return_type.to_owned(),
+            parameters: parameters
+                .iter()
+                .map(|&(name, type_name)| ParameterDescriptor {
+                    name: name.to_owned(),
+                    type_name: type_name.to_owned(),
+                })
+                .collect(),
+            address: None,
+        }
+    }
+
+    /// `Game.Player` with its members, nested classes and a struct
+    fn domain() -> DomainDescriptor {
+        let mut player = class("Game.Units", "Player", None);
+        player.fields = vec![
+            field("health", "System.Int32", 0),
+            field("type", "System.Single", 0),
+            field("count", "System.Int32", FIELD_ATTRIBUTE_STATIC),
+            field("name", "System.String", 0),
+            field("position", "UnityEngine.Vector3", 0),
+        ];
+        player.methods = vec![
+            method("get_Health", &[], "System.Int32"),
+            method("Move", &[("x", "System.Int32")], "System.Void"),
+            method("Move", &[("x", "System.Int32"), ("self", "System.Single")], "System.Boolean"),
+            method("TryGet", &[("value", "System.Int32&")], "System.Boolean"),
+        ];
+        let mut create = method("Create", &[("name", "System.String")], "Game.Units.Player");
+        create.flags = METHOD_ATTRIBUTE_STATIC;
+        player.methods.push(create);
+        player.properties = vec![PropertyDescriptor {
+            name: "Health".to_owned(),
+            type_name: "System.Int32".to_owned(),
+            token: 0x1700_0001,
+            flags: 0,
+            getter: Some("get_Health".to_owned()),
+            setter: None,
+        }];
+
+        let inner = class("", "Inventory`1", Some("Game.Units.Player"));
+        let compiler = class("", "<>c", Some("Game.Units.Player"));
+        let mut vector = class("UnityEngine", "Vector3", None);
+        vector.is_value_type = true;
+
+        DomainDescriptor {
+            assemblies: vec![AssemblyDescriptor {
+                name: "Assembly-CSharp".to_owned(),
+                version: "0.0.0.0".to_owned(),
+                image: ImageDescriptor {
+                    name: "Assembly-CSharp.dll".to_owned(),
+                    token: 1,
+                    classes: vec![player, inner, compiler, vector],
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn generated_code_parses() {
+        let domain = domain();
+        let generated = Generator::new(&domain).generate();
+        let file = syn::parse_file(&generated).unwrap_or_else(|err| {
+            panic!("invalid bindings: {err}\n{generated}");
+        });
+        assert!(!file.items.is_empty());
+
+        for item in [
+            "pub mod game",
+            "pub mod units",
+            "pub struct Player(",
+            "pub struct Player_Inventory_1(",
+            "pub fn r#type(",
+            "pub fn set_count(",
+            "pub fn TryGet",
+        ] {
+            assert_eq!(generated.contains(item), item != "pub fn TryGet", "{item}\n{generated}");
+        }
+        assert!(!generated.contains("Vector3"), "{generated}");
+        assert!(!generated.contains("<>c"), "{generated}");
+    }
+
+    #[test]
+    fn filters() {
+        let domain = domain();
+        let generated = Generator::new(&domain).namespace("UnityEngine").generate();
+        syn::parse_file(&generated).unwrap();
+        assert!(!generated.contains("Player"));
+
+        let generated = Generator::new(&domain).assembly("Assembly-CSharp").generate();
+        syn::parse_file(&generated).unwrap();
+        assert!(generated.contains("Player"));
+        assert!(!Generator::new(&domain).assembly("mscorlib").generate().contains("Player"));
+    }
+}
@@ -1,16 +1,22 @@
 //! Il2CppClass
 
-use crate::{FieldInfo, Il2CppImage, MethodInfo, NonNullRef, PropertyInfo, Ref};
-use il2cpp_sys_rs::{
-    il2cpp_class_array_element_size, il2cpp_class_from_name, il2cpp_class_get_type, il2cpp_class_is_abstract,
-    il2cpp_class_is_blittable, il2cpp_class_is_enum, il2cpp_class_is_generic,
-    il2cpp_class_is_inflated, il2cpp_class_is_inited, il2cpp_class_is_interface,
-    il2cpp_class_is_subclass_of, il2cpp_class_is_valuetype, il2cpp_class_num_fields,
-    Il2CppType,
+use crate::flags::{TYPE_ATTRIBUTE_SEALED, TYPE_ATTRIBUTE_VISIBILITY_MASK};
+use crate::runtime::{
+    il2cpp_class_array_element_size, il2cpp_class_from_name, il2cpp_class_from_system_type,
+    il2cpp_class_get_interfaces, il2cpp_class_get_nested_types, il2cpp_class_get_type,
+    il2cpp_class_is_abstract, il2cpp_class_is_blittable, il2cpp_class_is_enum,
+    il2cpp_class_is_generic, il2cpp_class_is_inflated, il2cpp_class_is_inited,
+    il2cpp_class_is_interface, il2cpp_class_is_subclass_of, il2cpp_class_is_valuetype,
+    il2cpp_class_num_fields,
+};
+use crate::method_info::same_signature;
+use crate::{
+    FieldInfo, Il2CppError, Il2CppImage, Il2CppObject, Il2CppTypeHandle, MethodInfo, NonNullRef,
+    ParameterType, PropertyInfo, Ref,
 };
 use std::borrow::Cow;
-use std::ffi::CStr;
-use std::{fmt, slice};
+use std::ffi::{c_void, CStr};
+use std::{fmt, iter, ptr, slice};
 
 /// Class handle
 pub type Il2CppClass = NonNullRef<il2cpp_sys_rs::Il2CppClass, ()>;
@@ -31,7 +37,7 @@ impl Il2CppClass {
     /// Panics if the type pointer is null
     #[track_caller]
     #[inline]
-    pub fn type_(self) -> NonNullRef<Il2CppType, ()> {
+    pub fn type_(self) -> Il2CppTypeHandle {
         Ref::new(unsafe { il2cpp_class_get_type(self.as_ptr()) as _ }).unwrap_non_null()
     }
 
@@ -54,10 +60,10 @@ impl Il2CppClass {
     /// Returns a human-readable fully qualified name.
     ///
     /// Combines namespace and class name with a dot.
+    /// Nested classes are not prefixed by their declaring class,
+    /// see [`Il2CppClass::qualified_name`].
     #[inline]
     pub fn full_name(self) -> Cow<'static, str> {
-        // todo: include the declaring type ?
-
         let ns = self.namespace().to_string_lossy();
         let name = self.name().to_string_lossy();
         if ns.is_empty() {
@@ -67,6 +73,43 @@ impl Il2CppClass {
         }
     }
 
+    /// Returns the name of the class within its namespace, such as ``Dictionary`2/Enumerator``
+    ///
+    /// Nested classes are separated from their declaring class by `/`, as accepted by
+    /// [`Il2CppImage::find_class`].
+    pub fn nested_name(self) -> Cow<'static, str> {
+        let name = self.name().to_string_lossy();
+        match self.declaring_type() {
+            Some(declaring_type) => format!("{}/{name}", declaring_type.nested_name()).into(),
+            None => name,
+        }
+    }
+
+    /// Returns the namespace and nested name, nested classes separated by `separator`
+    ///
+    /// `qualified_name('+')` matches `System.Type.FullName`, such as
+    /// `System.Environment+SpecialFolder`.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - Separator between a nested class and its declaring class
+    pub fn qualified_name(self, separator: char) -> Cow<'static, str> {
+        let outermost = iter::successors(Some(self), |class| class.declaring_type())
+            .last()
+            .unwrap_or(self);
+        let ns = outermost.namespace().to_string_lossy();
+        let mut name = self.nested_name();
+        if separator != '/' {
+            name = name.replace('/', separator.encode_utf8(&mut [0; 4])).into();
+        }
+
+        if ns.is_empty() {
+            name
+        } else {
+            format!("{ns}.{name}").into()
+        }
+    }
+
     /// Returns the base class of this type, if it inherits one
     ///
     /// # Panics
@@ -152,6 +195,33 @@ impl Il2CppClass {
         unsafe { il2cpp_class_is_subclass_of(self.as_ptr(), other.as_ptr(), check_interfaces) }
     }
 
+    /// Returns the raw class flags, `TYPE_ATTRIBUTE_*`
+    #[inline]
+    pub const fn flags(self) -> u32 {
+        self.as_ref().flags
+    }
+
+    /// Class visibility
+    ///
+    /// - 0x0000 - `internal`
+    /// - 0x0001 - `public`
+    /// - 0x0002 - nested `public`
+    /// - 0x0003 - nested `private`
+    /// - 0x0004 - nested `protected`
+    /// - 0x0005 - nested `internal`
+    /// - 0x0006 - nested `private protected`
+    /// - 0x0007 - nested `protected internal`
+    #[inline]
+    pub const fn accessibility(self) -> u32 {
+        self.flags() & TYPE_ATTRIBUTE_VISIBILITY_MASK
+    }
+
+    /// Returns `true` if the class cannot be inherited
+    #[inline]
+    pub const fn is_sealed(self) -> bool {
+        self.flags() & TYPE_ATTRIBUTE_SEALED != 0
+    }
+
     /// Returns the class token
     #[inline]
     pub const fn token(self) -> u32 {
@@ -183,34 +253,26 @@ impl Il2CppClass {
     ///
     /// Each [`FieldInfo`] describes a declared field, including static and instance ones.
     #[inline]
-    pub const fn fields(self) -> &'static [FieldInfo] {
-        if self.as_ref().fields.is_null() {
-            &[]
-        } else {
-            unsafe {
-                slice::from_raw_parts(
-                    self.as_ref().fields as _,
-                    self.as_ref().field_count as usize,
-                )
-            }
-        }
+    pub fn fields(self) -> impl ExactSizeIterator<Item = FieldInfo> + Clone {
+        let fields = self.as_ref().fields;
+        let count = if fields.is_null() { 0 } else { self.as_ref().field_count as usize };
+        // The class holds an array of `FieldInfo` structures, not of pointers
+        (0..count).map(move |i| Ref::new(unsafe { fields.add(i) }).unwrap_non_null())
     }
 
     /// Returns all property definitions of the class.
     ///
     /// Each [`PropertyInfo`] describes a declared property, including static and instance ones.
     #[inline]
-    pub const fn properties(self) -> &'static [PropertyInfo] {
-        if self.as_ref().properties.is_null() {
-            &[]
+    pub fn properties(self) -> impl ExactSizeIterator<Item = PropertyInfo> + Clone {
+        let properties = self.as_ref().properties;
+        let count = if properties.is_null() {
+            0
         } else {
-            unsafe {
-                slice::from_raw_parts(
-                    self.as_ref().properties as _,
-                    self.as_ref().property_count as usize,
-                )
-            }
-        }
+            self.as_ref().property_count as usize
+        };
+        // The class holds an array of `PropertyInfo` structures, not of pointers
+        (0..count).map(move |i| Ref::new(unsafe { properties.add(i) } as _).unwrap_non_null())
     }
 
     /// Returns all method definitions of the class.
@@ -230,18 +292,39 @@ impl Il2CppClass {
         }
     }
 
+    /// Returns the classes nested in the class
+    pub fn nested_types(self) -> impl Iterator<Item = Self> {
+        let mut iter: *mut c_void = ptr::null_mut();
+        iter::from_fn(move || {
+            Self::from_ptr(unsafe { il2cpp_class_get_nested_types(self.as_ptr(), &mut iter) })
+        })
+    }
+
+    /// Returns the interfaces implemented by the class
+    ///
+    /// Interfaces implemented by base classes are not included.
+    pub fn interfaces(self) -> impl Iterator<Item = Self> {
+        let mut iter: *mut c_void = ptr::null_mut();
+        iter::from_fn(move || {
+            Self::from_ptr(unsafe { il2cpp_class_get_interfaces(self.as_ptr(), &mut iter) })
+        })
+    }
+
     /// Finds a field by name
     ///
     /// # Arguments
     ///
     /// * `name` - Field name
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// FieldInfo handle if found, otherwise `None`
+    /// Returns [`Il2CppError::FieldNotFound`] if the class has no such field
     #[inline]
-    pub fn find_field(self, name: &CStr) -> Option<FieldInfo> {
-        FieldInfo::from_name(self, name)
+    pub fn find_field(self, name: &CStr) -> Result<FieldInfo, Il2CppError> {
+        FieldInfo::from_name(self, name).ok_or_else(|| Il2CppError::FieldNotFound {
+            class: self,
+            name: name.to_owned(),
+        })
     }
 
     /// Finds a property by name
@@ -250,12 +333,15 @@ impl Il2CppClass {
     ///
     /// * `name` - Property name
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// PropertyInfo handle if found, otherwise `None`
+    /// Returns [`Il2CppError::PropertyNotFound`] if the class has no such property
     #[inline]
-    pub fn find_property(self, name: &CStr) -> Option<PropertyInfo> {
-        PropertyInfo::from_name(self, name)
+    pub fn find_property(self, name: &CStr) -> Result<PropertyInfo, Il2CppError> {
+        PropertyInfo::from_name(self, name).ok_or_else(|| Il2CppError::PropertyNotFound {
+            class: self,
+            name: name.to_owned(),
+        })
     }
 
     /// Finds a method by name and arity
@@ -265,12 +351,90 @@ impl Il2CppClass {
     /// * `name` - Simple method name. For generic **definitions**, **do not** include the arity suffix.
     /// * `arity` - Number of parameters
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Method handle if found, otherwise `None`
+    /// Returns [`Il2CppError::MethodNotFound`] if the class has no such method
     #[inline]
-    pub fn find_method(self, name: &CStr, arity: i32) -> Option<MethodInfo> {
-        MethodInfo::from_name(self, name, arity)
+    pub fn find_method(self, name: &CStr, arity: i32) -> Result<MethodInfo, Il2CppError> {
+        MethodInfo::from_name(self, name, arity).ok_or_else(|| Il2CppError::MethodNotFound {
+            class: self,
+            name: name.to_owned(),
+            arity,
+        })
+    }
+
+    /// Finds every method overload matching a name and parameter types
+    ///
+    /// Walks the class and its parents. Parent methods hidden or overridden by a method with the
+    /// same signature are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Simple method name
+    /// * `parameters` - Parameter types, as fully qualified names such as `System.Single`
+    ///   or as type handles
+    pub fn find_methods<P: ParameterType>(self, name: &CStr, parameters: &[P]) -> Vec<MethodInfo> {
+        let mut candidates: Vec<MethodInfo> = Vec::new();
+
+        for class in iter::successors(Some(self), |class| class.parent()) {
+            let first_inherited = candidates.len();
+            for &method in class.methods() {
+                let matches = method.name() == name
+                    && method.parameters().len() == parameters.len()
+                    && iter::zip(method.parameters(), parameters)
+                        .all(|(&type_, parameter)| parameter.matches(type_));
+                let hidden = candidates[..first_inherited]
+                    .iter()
+                    .any(|&candidate| same_signature(candidate, method));
+
+                if matches && !hidden {
+                    candidates.push(method);
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// Finds the method overload matching a name and parameter types
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Simple method name
+    /// * `parameters` - Parameter types, as fully qualified names such as `System.Single`
+    ///   or as type handles
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::MethodNotFound`] if no overload matches,
+    /// or [`Il2CppError::AmbiguousMethod`] with every candidate if several overloads match
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::Il2CppImage;
+    /// let math = Il2CppImage::corlib().find_class(c"System", c"Math").unwrap();
+    /// let abs = math.find_method_exact(c"Abs", &["System.Single"]).unwrap();
+    /// ```
+    pub fn find_method_exact<P: ParameterType>(
+        self,
+        name: &CStr,
+        parameters: &[P],
+    ) -> Result<MethodInfo, Il2CppError> {
+        let mut candidates = self.find_methods(name, parameters);
+        match candidates.len() {
+            0 => Err(Il2CppError::MethodNotFound {
+                class: self,
+                name: name.to_owned(),
+                arity: parameters.len() as i32,
+            }),
+            1 => Ok(candidates.remove(0)),
+            _ => Err(Il2CppError::AmbiguousMethod {
+                class: self,
+                name: name.to_owned(),
+                candidates,
+            }),
+        }
     }
 }
 
@@ -299,6 +463,34 @@ impl Il2CppClass {
             .non_null()
         }
     }
+
+    /// Returns the class of a `System.Type` object
+    ///
+    /// The runtime does not check the object, so its class is checked to derive from
+    /// `System.Type` first.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - `System.Type` instance
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::TypeMismatch`] if `object` is not a type object,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export
+    /// `il2cpp_class_from_system_type`
+    pub fn from_system_type(object: Il2CppObject) -> Result<Self, Il2CppError> {
+        let mismatch = || Il2CppError::TypeMismatch {
+            expected: "System.Type".into(),
+            found: object.class().full_name(),
+        };
+        let system_type = Il2CppImage::corlib().find_class(c"System", c"Type")?;
+        if !object.class().is_subclass_of(system_type, false) {
+            return Err(mismatch());
+        }
+
+        let class = unsafe { il2cpp_class_from_system_type(object.as_ptr() as _) }?;
+        Self::from_ptr(class).ok_or_else(mismatch)
+    }
 }
 
 impl fmt::Display for Il2CppClass {
@@ -322,3 +514,110 @@ impl fmt::Debug for Il2CppClass {
             .finish()
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Ref};
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn names() {
+        let image = mock::image(c"ClassTestsNames.dll");
+        let player = ClassBuilder::new(image, c"Game", c"Player").build();
+        let inventory = ClassBuilder::new(image, c"", c"Inventory")
+            .nested_in(player)
+            .build();
+        let slot = ClassBuilder::new(image, c"", c"Slot`1").nested_in(inventory).build();
+
+        assert_eq!(player.full_name(), "Game.Player");
+        assert_eq!(player.qualified_name('+'), "Game.Player");
+        assert_eq!(inventory.full_name(), "Inventory");
+        assert_eq!(slot.nested_name(), "Player/Inventory/Slot`1");
+        assert_eq!(slot.qualified_name('+'), "Game.Player+Inventory+Slot`1");
+        assert_eq!(slot.qualified_name('/'), "Game.Player/Inventory/Slot`1");
+    }
+
+    #[test]
+    fn find_members() {
+        let image = mock::image(c"ClassTests.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .property(c"Health", Some(c"get_Health"), None)
+            .method(c"get_Health", &[], corlib(c"Int32"), |_| Ok(Ref::null()))
+            .method(c"Move", &[(c"x", corlib(c"Single"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .build();
+
+        let method = class.find_method(c"Move", 1).unwrap();
+        assert_eq!(method.signature(), "System.Void Move(System.Single x)");
+        assert_eq!(class.find_method(c"Move", -1).unwrap().as_ptr(), method.as_ptr());
+        assert_eq!(class.find_field(c"health").unwrap().name(), c"health");
+        assert_eq!(class.find_property(c"Health").unwrap().name(), c"Health");
+    }
+
+    #[test]
+    fn find_members_not_found() {
+        let image = mock::image(c"ClassTestsMissing.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .method(c"Move", &[(c"x", corlib(c"Single"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .build();
+
+        let err = class.find_method(c"Move", 2).unwrap_err();
+        assert!(matches!(err, Il2CppError::MethodNotFound { arity: 2, .. }));
+        assert_eq!(err.to_string(), "method Game.Player.Move with 2 parameters not found");
+        assert_eq!(
+            class.find_method(c"Jump", -1).unwrap_err().to_string(),
+            "method Game.Player.Jump not found"
+        );
+        assert_eq!(
+            class.find_field(c"health").unwrap_err().to_string(),
+            "field Game.Player.health not found"
+        );
+        assert!(matches!(
+            class.find_property(c"Health"),
+            Err(Il2CppError::PropertyNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn find_method_exact() {
+        let image = mock::image(c"ClassTestsOverloads.dll");
+        let (int32, single) = (corlib(c"Int32"), corlib(c"Single"));
+        let base = ClassBuilder::new(image, c"Game", c"Base")
+            .static_method(c"Abs", &[(c"value", int32)], int32, |_| Ok(Ref::null()))
+            .static_method(c"Abs", &[(c"value", single)], single, |_| Ok(Ref::null()))
+            .build();
+        let derived = ClassBuilder::new(image, c"Game", c"Derived")
+            .parent(base)
+            .static_method(c"Abs", &[(c"value", int32)], int32, |_| Ok(Ref::null()))
+            .static_method(c"Both", &[(c"value", single)], int32, |_| Ok(Ref::null()))
+            .static_method(c"Both", &[(c"other", single)], single, |_| Ok(Ref::null()))
+            .build();
+
+        // Overloads of the class hide the base class overloads of the same signature
+        let abs = derived.find_method_exact(c"Abs", &["System.Int32"]).unwrap();
+        assert_eq!(abs.declaring_type().as_ptr(), derived.as_ptr());
+        let abs = derived.find_method_exact(c"Abs", &["System.Single"]).unwrap();
+        assert_eq!(abs.declaring_type().as_ptr(), base.as_ptr());
+        let abs = derived.find_method_exact(c"Abs", &[single.type_()]).unwrap();
+        assert_eq!(abs.declaring_type().as_ptr(), base.as_ptr());
+
+        assert!(matches!(
+            derived.find_method_exact(c"Abs", &["System.Double"]),
+            Err(Il2CppError::MethodNotFound { arity: 1, .. })
+        ));
+        let err = derived.find_method_exact(c"Both", &["System.Single"]).unwrap_err();
+        assert!(matches!(
+            &err,
+            Il2CppError::AmbiguousMethod { candidates, .. } if candidates.len() == 2
+        ));
+        assert!(err.to_string().starts_with("method Game.Derived.Both is ambiguous, 2 overloads"));
+    }
+}
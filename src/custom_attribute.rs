@@ -0,0 +1,419 @@
+//! Custom attributes
+
+use crate::runtime::{
+    il2cpp_custom_attrs_construct, il2cpp_custom_attrs_free, il2cpp_custom_attrs_from_class,
+    il2cpp_custom_attrs_from_method, il2cpp_custom_attrs_get_attr, il2cpp_custom_attrs_has_attr,
+};
+use crate::{
+    FieldInfo, GcHandle, Il2CppClass, Il2CppError, Il2CppObject, Il2CppString, Il2CppSzArrayRef,
+    MethodInfo, PropertyInfo,
+};
+use il2cpp_sys_rs::Il2CppCustomAttrInfo;
+use std::ffi::{c_void, CStr};
+use std::{fmt, ptr};
+
+/// `BindingFlags.DeclaredOnly | Instance | Static | Public | NonPublic`
+const DECLARED_MEMBERS: i32 = 0x3e;
+
+/// Custom attributes of a class, method, field or property
+///
+/// Listing the attributes constructs them, which runs the attribute constructors.
+pub struct CustomAttributes {
+    source: Source,
+}
+
+/// Where the attributes are read from
+enum Source {
+    /// Attributes of a class or method, read by the runtime, null if there are none
+    Info(*mut Il2CppCustomAttrInfo),
+    /// Reflection object of a field or property
+    Member(Option<GcHandle<il2cpp_sys_rs::Il2CppObject>>),
+}
+
+impl CustomAttributes {
+    /// Returns the classes of the attributes
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    pub fn classes(&self) -> Result<Vec<Il2CppClass>, Il2CppError> {
+        Ok(self.objects()?.into_iter().map(Il2CppObject::class).collect())
+    }
+
+    /// Returns the attribute objects
+    ///
+    /// The objects are constructed on every call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    pub fn objects(&self) -> Result<Vec<Il2CppObject>, Il2CppError> {
+        let array: Il2CppSzArrayRef<*mut il2cpp_sys_rs::Il2CppObject> = match &self.source {
+            Source::Info(info) if info.is_null() => return Ok(Vec::new()),
+            Source::Info(info) => {
+                Il2CppSzArrayRef::new(unsafe { il2cpp_custom_attrs_construct(*info) }? as _)
+            }
+            Source::Member(None) => return Ok(Vec::new()),
+            Source::Member(Some(member)) => {
+                let member = member.target();
+                member
+                    .class()
+                    .find_method(c"GetCustomAttributes", 1)?
+                    .call(member.into(), (false,))?
+            }
+        };
+
+        Ok(array.non_null().map_or_else(Vec::new, |array| {
+            array
+                .iter()
+                .filter_map(|&object| Il2CppObject::from_ptr(object))
+                .collect()
+        }))
+    }
+
+    /// Returns `true` if an attribute is an instance of `attribute`, or of a class derived from it
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class, such as `System.ObsoleteAttribute`
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    pub fn has(&self, attribute: Il2CppClass) -> Result<bool, Il2CppError> {
+        match &self.source {
+            Source::Info(info) if info.is_null() => Ok(false),
+            Source::Info(info) => unsafe {
+                il2cpp_custom_attrs_has_attr(*info, attribute.as_ptr())
+            },
+            Source::Member(_) => Ok(self
+                .classes()?
+                .into_iter()
+                .any(|class| class.is_subclass_of(attribute, false))),
+        }
+    }
+
+    /// Returns the first attribute that is an instance of `attribute`, or of a class derived
+    /// from it
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class, such as `System.ObsoleteAttribute`
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    pub fn get(&self, attribute: Il2CppClass) -> Result<Option<Il2CppObject>, Il2CppError> {
+        match &self.source {
+            Source::Info(info) if info.is_null() => Ok(None),
+            Source::Info(info) => Ok(Il2CppObject::from_ptr(unsafe {
+                il2cpp_custom_attrs_get_attr(*info, attribute.as_ptr())
+            }?)),
+            Source::Member(_) => Ok(self
+                .objects()?
+                .into_iter()
+                .find(|object| object.class().is_subclass_of(attribute, false))),
+        }
+    }
+
+    /// Reads the attributes of a field or property from its reflection object
+    ///
+    /// # Arguments
+    ///
+    /// * `parent` - Class declaring the member
+    /// * `getter` - `System.Type` method returning the member, such as `GetField`
+    /// * `name` - Member name
+    fn from_member(parent: Il2CppClass, getter: &CStr, name: &CStr) -> Result<Self, Il2CppError> {
+        let type_object = parent.type_().system_type()?;
+        let getter = type_object
+            .class()
+            .find_method_exact(getter, &["System.String", "System.Reflection.BindingFlags"])?;
+
+        let name = Il2CppString::new(name)?;
+        let mut flags = DECLARED_MEMBERS;
+        let mut args = [name.as_ptr() as *mut c_void, &mut flags as *mut i32 as _];
+        let member = getter.invoke(type_object.into(), &mut args)?.non_null();
+        Ok(Self {
+            source: Source::Member(member.map(GcHandle::new)),
+        })
+    }
+}
+
+impl Drop for CustomAttributes {
+    fn drop(&mut self) {
+        if let Source::Info(info) = self.source
+            && !info.is_null()
+        {
+            // Leaked if the runtime exports no way to free it
+            let _ = unsafe { il2cpp_custom_attrs_free(info) };
+        }
+    }
+}
+
+/// Prints where the attributes are read from, without constructing them
+impl fmt::Debug for CustomAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (source, ptr) = match &self.source {
+            Source::Info(info) => ("info", *info as *const c_void),
+            Source::Member(member) => (
+                "member",
+                member
+                    .as_ref()
+                    .map_or(ptr::null(), |member| member.target().as_ptr() as _),
+            ),
+        };
+        f.debug_struct("CustomAttributes")
+            .field("source", &source)
+            .field("ptr", &ptr)
+            .finish()
+    }
+}
+
+impl Il2CppClass {
+    /// Returns the custom attributes declared on the class
+    ///
+    /// Attributes inherited from base classes are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::MissingExport`] if the runtime does not export
+    /// `il2cpp_custom_attrs_from_class`
+    #[inline]
+    pub fn custom_attributes(self) -> Result<CustomAttributes, Il2CppError> {
+        Ok(CustomAttributes {
+            source: Source::Info(unsafe { il2cpp_custom_attrs_from_class(self.as_ptr()) }?),
+        })
+    }
+
+    /// Returns `true` if the class declares an attribute of class `attribute`
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    #[inline]
+    pub fn has_attribute(self, attribute: Il2CppClass) -> Result<bool, Il2CppError> {
+        self.custom_attributes()?.has(attribute)
+    }
+}
+
+impl MethodInfo {
+    /// Returns the custom attributes declared on the method
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::MissingExport`] if the runtime does not export
+    /// `il2cpp_custom_attrs_from_method`
+    #[inline]
+    pub fn custom_attributes(self) -> Result<CustomAttributes, Il2CppError> {
+        Ok(CustomAttributes {
+            source: Source::Info(unsafe { il2cpp_custom_attrs_from_method(self.as_ptr()) }?),
+        })
+    }
+
+    /// Returns `true` if the method declares an attribute of class `attribute`
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError::ManagedException`] if an attribute constructor throws,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export the
+    /// `il2cpp_custom_attrs_*` functions
+    #[inline]
+    pub fn has_attribute(self, attribute: Il2CppClass) -> Result<bool, Il2CppError> {
+        self.custom_attributes()?.has(attribute)
+    }
+}
+
+impl FieldInfo {
+    /// Returns the custom attributes declared on the field, such as `[SerializeField]`
+    ///
+    /// Read through reflection, the runtime exports no accessor for field attributes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError`] if the reflection object of the field cannot be created
+    #[inline]
+    pub fn custom_attributes(self) -> Result<CustomAttributes, Il2CppError> {
+        CustomAttributes::from_member(self.parent(), c"GetField", self.name())
+    }
+
+    /// Returns `true` if the field declares an attribute of class `attribute`
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError`] if the reflection lookup fails or an attribute constructor
+    /// throws
+    #[inline]
+    pub fn has_attribute(self, attribute: Il2CppClass) -> Result<bool, Il2CppError> {
+        self.custom_attributes()?.has(attribute)
+    }
+}
+
+impl PropertyInfo {
+    /// Returns the custom attributes declared on the property
+    ///
+    /// Read through reflection, the runtime exports no accessor for property attributes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError`] if the reflection object of the property cannot be created
+    #[inline]
+    pub fn custom_attributes(self) -> Result<CustomAttributes, Il2CppError> {
+        CustomAttributes::from_member(self.parent(), c"GetProperty", self.name())
+    }
+
+    /// Returns `true` if the property declares an attribute of class `attribute`
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - Attribute class
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Il2CppError`] if the reflection lookup fails or an attribute constructor
+    /// throws
+    #[inline]
+    pub fn has_attribute(self, attribute: Il2CppClass) -> Result<bool, Il2CppError> {
+        self.custom_attributes()?.has(attribute)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Ref};
+    use std::ffi::CStr;
+
+    fn corlib(name: &CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn class_and_method_attributes() {
+        let image = mock::image(c"AttributeTests.dll");
+        let obsolete = ClassBuilder::new(image, c"System", c"ObsoleteAttribute")
+            .parent(corlib(c"Attribute"))
+            .build();
+        let serialize = ClassBuilder::new(image, c"UnityEngine", c"SerializeField")
+            .parent(corlib(c"Attribute"))
+            .build();
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .method(c"Old", &[], corlib(c"Void"), |_| Ok(Ref::null()))
+            .method(c"New", &[], corlib(c"Void"), |_| Ok(Ref::null()))
+            .attribute(obsolete)
+            .member_attribute(c"Old", obsolete)
+            .member_attribute(c"Old", serialize)
+            .build();
+
+        let attributes = class.custom_attributes().unwrap();
+        let classes = attributes.classes().unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].as_ptr(), obsolete.as_ptr());
+        assert!(attributes.get(obsolete).unwrap().is_some());
+        assert!(attributes.get(serialize).unwrap().is_none());
+        assert!(class.has_attribute(obsolete).unwrap());
+        assert!(!class.has_attribute(serialize).unwrap());
+
+        let old = class.find_method(c"Old", 0).unwrap();
+        assert_eq!(old.custom_attributes().unwrap().objects().unwrap().len(), 2);
+        assert!(old.has_attribute(serialize).unwrap());
+        let new = class.find_method(c"New", 0).unwrap();
+        assert!(new.custom_attributes().unwrap().objects().unwrap().is_empty());
+        assert!(!new.has_attribute(obsolete).unwrap());
+    }
+
+    #[test]
+    fn member_attributes() {
+        let image = mock::image(c"AttributeTestsMembers.dll");
+        let obsolete = ClassBuilder::new(image, c"System", c"ObsoleteAttribute")
+            .parent(corlib(c"Attribute"))
+            .build();
+        let serialize = ClassBuilder::new(image, c"UnityEngine", c"SerializeField")
+            .parent(corlib(c"Attribute"))
+            .build();
+        let special = ClassBuilder::new(image, c"Game", c"SpecialSerialize")
+            .parent(serialize)
+            .build();
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .field(c"plain", corlib(c"Int32"))
+            .method(c"get_Health", &[], corlib(c"Int32"), |_| Ok(Ref::null()))
+            .property(c"Health", Some(c"get_Health"), None)
+            .member_attribute(c"health", special)
+            .member_attribute(c"Health", obsolete)
+            .build();
+
+        let health = class.find_field(c"health").unwrap();
+        assert!(health.has_attribute(serialize).unwrap());
+        assert!(health.has_attribute(special).unwrap());
+        assert!(!health.has_attribute(obsolete).unwrap());
+        let attribute = health.custom_attributes().unwrap().get(serialize).unwrap().unwrap();
+        assert_eq!(attribute.class().as_ptr(), special.as_ptr());
+        let plain = class.find_field(c"plain").unwrap().custom_attributes().unwrap();
+        assert!(plain.classes().unwrap().is_empty());
+
+        let property = class.find_property(c"Health").unwrap();
+        assert!(property.has_attribute(obsolete).unwrap());
+        assert_eq!(property.custom_attributes().unwrap().classes().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn constructor_exceptions() {
+        let image = mock::image(c"AttributeTestsThrow.dll");
+        let throwing = ClassBuilder::new(image, c"Game", c"ThrowingAttribute")
+            .parent(corlib(c"Attribute"))
+            .abstract_type()
+            .build();
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .member_attribute(c"health", throwing)
+            .build();
+
+        let health = class.find_field(c"health").unwrap();
+        let err = health.has_attribute(throwing).unwrap_err();
+        assert!(matches!(err, Il2CppError::ManagedException(_)));
+        assert_eq!(
+            err.to_string(),
+            "managed exception: System.Exception: cannot create an abstract attribute"
+        );
+        let attributes = health.custom_attributes().unwrap();
+        assert!(attributes.objects().is_err());
+        assert!(attributes.get(throwing).is_err());
+    }
+
+    #[test]
+    fn debug_does_not_construct() {
+        let image = mock::image(c"AttributeTestsDebug.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .attribute(corlib(c"Attribute"))
+            .build();
+
+        let debug = format!("{:?}", class.custom_attributes().unwrap());
+        assert!(debug.starts_with("CustomAttributes { source: \"info\", ptr: 0x"), "{debug}");
+        let field = class.find_field(c"health").unwrap().custom_attributes().unwrap();
+        let debug = format!("{field:?}");
+        assert!(debug.starts_with("CustomAttributes { source: \"member\", ptr: 0x"), "{debug}");
+    }
+}
@@ -0,0 +1,610 @@
+//! Il2CppDelegate
+
+use crate::flags::METHOD_ATTRIBUTE_PUBLIC;
+use crate::invoke::{is_array, is_by_value, is_reference, is_string};
+use crate::runtime::il2cpp_gc_wbarrier_set_field;
+use crate::{
+    catch_panic, Il2CppArrayRef, Il2CppClass, Il2CppImage, Il2CppObject, Il2CppObjectRef,
+    Il2CppStringRef, Il2CppTypeHandle, MethodInfo, NonNullRef, Ref, WeakGcHandle,
+};
+use il2cpp_sys_rs::{
+    Il2CppMethodPointer, Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN, Il2CppTypeEnum_IL2CPP_TYPE_I,
+    Il2CppTypeEnum_IL2CPP_TYPE_I1, Il2CppTypeEnum_IL2CPP_TYPE_I2, Il2CppTypeEnum_IL2CPP_TYPE_I4,
+    Il2CppTypeEnum_IL2CPP_TYPE_I8, Il2CppTypeEnum_IL2CPP_TYPE_R4, Il2CppTypeEnum_IL2CPP_TYPE_R8,
+    Il2CppTypeEnum_IL2CPP_TYPE_U, Il2CppTypeEnum_IL2CPP_TYPE_U1, Il2CppTypeEnum_IL2CPP_TYPE_U2,
+    Il2CppTypeEnum_IL2CPP_TYPE_U4, Il2CppTypeEnum_IL2CPP_TYPE_U8,
+    Il2CppTypeEnum_IL2CPP_TYPE_VOID, InvokerMethod,
+};
+use std::any::{type_name, Any};
+use std::error::Error;
+use std::ffi::c_void;
+use std::sync::{Mutex, PoisonError};
+use std::{fmt, mem};
+
+/// Delegate handle
+pub type Il2CppDelegate = NonNullRef<il2cpp_sys_rs::Il2CppDelegate, ()>;
+/// Nullable Delegate handle
+pub type Il2CppDelegateRef = Ref<il2cpp_sys_rs::Il2CppDelegate, ()>;
+
+/// Error raised while creating a delegate
+#[derive(Debug)]
+pub enum DelegateError {
+    /// The class does not derive from `System.Delegate`
+    NotDelegate,
+    /// The closure takes a different number of arguments than the delegate
+    ArgumentCount {
+        /// Number of parameters of the delegate
+        expected: usize,
+        /// Number of arguments of the closure
+        found: usize,
+    },
+    /// A closure argument cannot hold the matching delegate parameter
+    ArgumentType {
+        /// Zero-based parameter index
+        index: usize,
+        /// Managed parameter type name
+        expected: String,
+        /// Rust argument type name
+        found: &'static str,
+    },
+    /// The closure return type cannot be converted to the delegate return type
+    ReturnType {
+        /// Managed return type name
+        expected: String,
+        /// Rust return type name
+        found: &'static str,
+    },
+    /// The runtime failed to allocate the delegate
+    AllocationFailed,
+}
+
+impl fmt::Display for DelegateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotDelegate => f.write_str("not a delegate class"),
+            Self::ArgumentCount { expected, found } => {
+                write!(f, "expected {expected} arguments, found {found}")
+            }
+            Self::ArgumentType {
+                index,
+                expected,
+                found,
+            } => write!(f, "argument {index} is `{found}`, expected `{expected}`"),
+            Self::ReturnType { expected, found } => {
+                write!(f, "return type is `{expected}`, found `{found}`")
+            }
+            Self::AllocationFailed => f.write_str("failed to allocate the delegate"),
+        }
+    }
+}
+
+impl Error for DelegateError {}
+
+/// Value passed to or returned from a delegate backed by Rust, in its native representation
+///
+/// # Safety
+///
+/// `Self` must have the native representation of every managed type it is compatible with
+pub unsafe trait NativeValue: Copy + 'static {
+    /// Returns `true` if the managed `type_` is passed as `Self`
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool;
+
+    /// Reads an argument passed through `runtime_invoke`
+    ///
+    /// # Safety
+    ///
+    /// `arg` must point to the value for value types, or be the object for reference types
+    unsafe fn from_invoke_arg(arg: *mut c_void) -> Self;
+
+    /// Writes a return value of `runtime_invoke`
+    ///
+    /// # Safety
+    ///
+    /// `ret` must point to storage for the return value
+    unsafe fn write_invoke_return(self, ret: *mut c_void);
+}
+
+/// Implements [`NativeValue`] for primitive types
+macro_rules! primitive {
+    ($($ty:ty => $type_enum:ident),* $(,)?) => {
+        $(
+            unsafe impl NativeValue for $ty {
+                #[inline]
+                fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+                    is_by_value(type_, $type_enum)
+                }
+
+                #[inline]
+                unsafe fn from_invoke_arg(arg: *mut c_void) -> Self {
+                    unsafe { (arg as *const Self).read_unaligned() }
+                }
+
+                #[inline]
+                unsafe fn write_invoke_return(self, ret: *mut c_void) {
+                    unsafe { (ret as *mut Self).write_unaligned(self) }
+                }
+            }
+        )*
+    };
+}
+
+primitive! {
+    bool => Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN,
+    i8 => Il2CppTypeEnum_IL2CPP_TYPE_I1,
+    u8 => Il2CppTypeEnum_IL2CPP_TYPE_U1,
+    i16 => Il2CppTypeEnum_IL2CPP_TYPE_I2,
+    u16 => Il2CppTypeEnum_IL2CPP_TYPE_U2,
+    i32 => Il2CppTypeEnum_IL2CPP_TYPE_I4,
+    u32 => Il2CppTypeEnum_IL2CPP_TYPE_U4,
+    i64 => Il2CppTypeEnum_IL2CPP_TYPE_I8,
+    u64 => Il2CppTypeEnum_IL2CPP_TYPE_U8,
+    isize => Il2CppTypeEnum_IL2CPP_TYPE_I,
+    usize => Il2CppTypeEnum_IL2CPP_TYPE_U,
+    f32 => Il2CppTypeEnum_IL2CPP_TYPE_R4,
+    f64 => Il2CppTypeEnum_IL2CPP_TYPE_R8,
+}
+
+unsafe impl NativeValue for () {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_VOID)
+    }
+
+    #[inline]
+    unsafe fn from_invoke_arg(_arg: *mut c_void) -> Self {}
+
+    #[inline]
+    unsafe fn write_invoke_return(self, _ret: *mut c_void) {}
+}
+
+/// Implements [`NativeValue`] for nullable managed reference handles
+macro_rules! reference {
+    ($([$($generics:tt)*] $ty:ty => $is_compatible:ident),* $(,)?) => {
+        $(
+            unsafe impl<$($generics)*> NativeValue for $ty {
+                #[inline]
+                fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+                    $is_compatible(type_)
+                }
+
+                #[inline]
+                unsafe fn from_invoke_arg(arg: *mut c_void) -> Self {
+                    Ref::new(arg as _)
+                }
+
+                #[inline]
+                unsafe fn write_invoke_return(self, ret: *mut c_void) {
+                    unsafe { (ret as *mut *mut c_void).write(self.as_ptr() as _) }
+                }
+            }
+        )*
+    };
+}
+
+reference! {
+    [] Il2CppObjectRef => is_reference,
+    [] Il2CppStringRef => is_string,
+    [T: 'static, R: 'static] Il2CppArrayRef<T, R> => is_array,
+}
+
+/// Rust closure that can back a delegate
+///
+/// Implemented for `Fn` closures and functions of up to 12 [`NativeValue`] arguments,
+/// returning a [`NativeValue`].
+pub trait DelegateFn<A>: Send + Sync + 'static {
+    /// Checks the closure signature against the delegate `Invoke` method
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`DelegateError`] describing the first mismatch
+    fn check(invoke: MethodInfo) -> Result<(), DelegateError>;
+
+    /// Returns the native entry point, called by the delegate `Invoke` method
+    fn method_pointer() -> *const ();
+
+    /// Returns the invoker, called by `runtime_invoke`
+    fn invoker() -> InvokerMethod;
+}
+
+/// Fake method of a delegate backed by a Rust closure
+///
+/// Native calls receive the method as their last argument, which leads back to the closure.
+#[repr(C)]
+struct DelegateMethod<F> {
+    method: il2cpp_sys_rs::MethodInfo,
+    closure: F,
+}
+
+/// Returns the closure of a [`DelegateMethod`]
+///
+/// # Safety
+///
+/// `method` must be the method of a delegate backed by a closure of type `F`
+#[inline]
+unsafe fn closure<'a, F>(method: *const il2cpp_sys_rs::MethodInfo) -> &'a F {
+    unsafe { &(*(method as *const DelegateMethod<F>)).closure }
+}
+
+/// Implements [`DelegateFn`] for closures of a given arity
+macro_rules! delegate_fn {
+    ($count:literal => $($name:ident $arg:ident $index:tt),*) => {
+        impl<Func, Ret, $($name),*> DelegateFn<($($name,)*)> for Func
+        where
+            Func: Fn($($name),*) -> Ret + Send + Sync + 'static,
+            Ret: NativeValue,
+            $($name: NativeValue,)*
+        {
+            fn check(invoke: MethodInfo) -> Result<(), DelegateError> {
+                let parameters = invoke.parameters();
+                if parameters.len() != $count {
+                    return Err(DelegateError::ArgumentCount {
+                        expected: parameters.len(),
+                        found: $count,
+                    });
+                }
+                $(
+                    if !$name::is_compatible(parameters[$index]) {
+                        return Err(DelegateError::ArgumentType {
+                            index: $index,
+                            expected: parameters[$index].name(),
+                            found: type_name::<$name>(),
+                        });
+                    }
+                )*
+
+                let return_type = invoke.return_type();
+                if !Ret::is_compatible(return_type) {
+                    return Err(DelegateError::ReturnType {
+                        expected: return_type.name(),
+                        found: type_name::<Ret>(),
+                    });
+                }
+                Ok(())
+            }
+
+            fn method_pointer() -> *const () {
+                unsafe extern "C-unwind" fn native<Func, Ret, $($name),*>(
+                    _target: *mut c_void,
+                    $($arg: $name,)*
+                    method: *const il2cpp_sys_rs::MethodInfo,
+                ) -> Ret
+                where
+                    Func: Fn($($name),*) -> Ret,
+                {
+                    unsafe { catch_panic(|| closure::<Func>(method)($($arg),*)) }
+                }
+
+                native::<Func, Ret, $($name),*> as *const ()
+            }
+
+            fn invoker() -> InvokerMethod {
+                #[allow(unused_variables)]
+                unsafe extern "C-unwind" fn invoke<Func, Ret, $($name),*>(
+                    _pointer: Il2CppMethodPointer,
+                    method: *const il2cpp_sys_rs::MethodInfo,
+                    _target: *mut c_void,
+                    args: *mut *mut c_void,
+                    ret: *mut c_void,
+                ) where
+                    Func: Fn($($name),*) -> Ret,
+                    Ret: NativeValue,
+                    $($name: NativeValue,)*
+                {
+                    unsafe {
+                        let result = catch_panic(|| {
+                            closure::<Func>(method)($($name::from_invoke_arg(*args.add($index))),*)
+                        });
+                        result.write_invoke_return(ret);
+                    }
+                }
+
+                // Invokers may throw, the runtime catches their exceptions
+                let invoke = invoke::<Func, Ret, $($name),*> as *const ();
+                unsafe { mem::transmute::<*const (), InvokerMethod>(invoke) }
+            }
+        }
+    };
+}
+
+delegate_fn!(0 =>);
+delegate_fn!(1 => A a 0);
+delegate_fn!(2 => A a 0, B b 1);
+delegate_fn!(3 => A a 0, B b 1, C c 2);
+delegate_fn!(4 => A a 0, B b 1, C c 2, D d 3);
+delegate_fn!(5 => A a 0, B b 1, C c 2, D d 3, E e 4);
+delegate_fn!(6 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5);
+delegate_fn!(7 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6);
+delegate_fn!(8 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7);
+delegate_fn!(9 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8);
+delegate_fn!(10 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9);
+delegate_fn!(11 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10);
+delegate_fn!(
+    12 => A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7, I i 8, J j 9, K k 10, L l 11
+);
+
+/// Closure kept alive until its delegate is collected
+struct DelegateEntry {
+    delegate: WeakGcHandle<il2cpp_sys_rs::Il2CppDelegate>,
+    _method: Box<dyn Any>,
+}
+
+// Safety: closures are `Send + Sync`, the fake method is never mutated
+unsafe impl Send for DelegateEntry {}
+
+/// Closures of the delegates created from Rust
+static DELEGATES: Mutex<Vec<DelegateEntry>> = Mutex::new(Vec::new());
+
+/// Drops the closures of the delegates created from Rust that were collected
+///
+/// The closures are also dropped by [`Il2CppDelegate::new`]. A program that stops creating
+/// delegates keeps the closures of the collected ones, with their captures, until this is called.
+///
+/// # Returns
+///
+/// Number of closures dropped
+pub fn collect_dead_delegates() -> usize {
+    let dead = {
+        let mut delegates = DELEGATES.lock().unwrap_or_else(PoisonError::into_inner);
+        take_dead(&mut delegates)
+    };
+    // Dropped once unlocked, the captures may create delegates when dropped
+    dead.len()
+}
+
+/// Removes the entries of the collected delegates
+fn take_dead(delegates: &mut Vec<DelegateEntry>) -> Vec<DelegateEntry> {
+    let (alive, dead) = mem::take(delegates)
+        .into_iter()
+        .partition(|entry| entry.delegate.target().is_some());
+    *delegates = alive;
+    dead
+}
+
+impl Il2CppDelegate {
+    /// Creates a delegate backed by a Rust closure
+    ///
+    /// The closure signature is checked against the delegate `Invoke` method.
+    /// The closure is kept alive as long as the delegate, and dropped once the delegate is
+    /// collected, by the next call to this function or to [`collect_dead_delegates`].
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Delegate class, such as `System.Action` or an inflated `UnityAction<T0, T1>`
+    /// * `closure` - Closure called when the delegate is invoked, a panic is raised as a managed
+    ///   `System.Exception`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DelegateError::NotDelegate`] if `class` is not a delegate class,
+    /// [`DelegateError::AllocationFailed`] if the runtime fails to allocate the delegate,
+    /// or another [`DelegateError`] if the closure signature does not match
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{Il2CppDelegate, Il2CppImage};
+    /// let action = Il2CppImage::corlib().find_class(c"System", c"Action`1").unwrap();
+    /// let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+    ///
+    /// // System.Action<System.Int32>
+    /// let action = action.make_generic(&[int32]).unwrap();
+    /// let delegate = Il2CppDelegate::new(action, |value: i32| println!("{value}")).unwrap();
+    /// ```
+    pub fn new<A, F: DelegateFn<A>>(class: Il2CppClass, closure: F) -> Result<Self, DelegateError> {
+        let delegate_class = Il2CppImage::corlib()
+            .find_class(c"System", c"Delegate")
+            .expect("System.Delegate");
+        if !class.is_subclass_of(delegate_class, false) {
+            return Err(DelegateError::NotDelegate);
+        }
+        let invoke = class
+            .find_method(c"Invoke", -1)
+            .map_err(|_| DelegateError::NotDelegate)?;
+        F::check(invoke)?;
+
+        let method_pointer = F::method_pointer();
+        let mut method = Box::new(DelegateMethod {
+            method: unsafe { mem::zeroed::<il2cpp_sys_rs::MethodInfo>() },
+            closure,
+        });
+        {
+            let invoke = invoke.as_ref();
+            let method = &mut method.method;
+            method.name = invoke.name;
+            method.klass = class.as_ptr();
+            method.return_type = invoke.return_type;
+            method.parameters = invoke.parameters;
+            method.parameters_count = invoke.parameters_count;
+            method.flags = METHOD_ATTRIBUTE_PUBLIC as u16;
+            method.methodPointer =
+                unsafe { mem::transmute::<*const (), Il2CppMethodPointer>(method_pointer) };
+            method.virtualMethodPointer = method.methodPointer;
+            method.invoker_method = F::invoker();
+        }
+
+        // The target is the delegate itself, so the closure is called as an instance method
+        let delegate = Il2CppObject::new(class).map_err(|_| DelegateError::AllocationFailed)?;
+        let delegate = unsafe { delegate.cast::<il2cpp_sys_rs::Il2CppDelegate>() };
+        unsafe {
+            let raw = delegate.as_mut();
+            raw.method_ptr = mem::transmute::<*const (), Il2CppMethodPointer>(method_pointer);
+            // The type of `invoke_impl` depends on the Unity version
+            #[allow(clippy::missing_transmute_annotations)]
+            {
+                raw.invoke_impl = mem::transmute::<*const (), _>(method_pointer);
+            }
+            raw.method = &method.method;
+            il2cpp_gc_wbarrier_set_field(
+                delegate.as_ptr() as _,
+                &mut raw.target as *mut _ as _,
+                delegate.as_ptr() as _,
+            );
+        }
+
+        let entry = DelegateEntry {
+            delegate: WeakGcHandle::new(delegate, false),
+            _method: method,
+        };
+        let _dead = {
+            let mut delegates = DELEGATES.lock().unwrap_or_else(PoisonError::into_inner);
+            let dead = take_dead(&mut delegates);
+            delegates.push(entry);
+            dead
+        };
+        Ok(delegate)
+    }
+
+    /// Returns the method called by the delegate
+    ///
+    /// # Panics
+    ///
+    /// Panics if the method pointer is null
+    #[track_caller]
+    #[inline]
+    pub fn method(self) -> MethodInfo {
+        MethodInfo::from_ptr(self.as_ref().method as _).expect("delegate method")
+    }
+
+    /// Returns the object the delegate method is called on, null for static methods
+    #[inline]
+    pub fn target(self) -> Il2CppObjectRef {
+        Ref::new(self.as_ref().target)
+    }
+
+    /// Returns the delegate as an object
+    #[inline]
+    pub const fn as_object(self) -> Il2CppObject {
+        Il2CppObject::from_ptr(self.as_ptr() as _).unwrap()
+    }
+}
+
+impl fmt::Debug for Il2CppDelegate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Il2CppDelegate")
+            .field("ptr", &self.as_ptr())
+            .field("method", &self.method().name())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{
+        collect_dead_delegates, DelegateError, GcHandle, Il2CppDelegate, Il2CppImage,
+        Il2CppObjectRef, InvokeError, Ref,
+    };
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn invoke_closure() {
+        let image = mock::image(c"DelegateTests.dll");
+        let corlib = Il2CppImage::corlib();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let object = corlib.find_class(c"System", c"Object").unwrap();
+        let callback = ClassBuilder::new(image, c"Game", c"Callback")
+            .delegate(&[(c"a", int32), (c"b", object)], int32)
+            .build();
+
+        let delegate = Il2CppDelegate::new(callback, |a: i32, b: Il2CppObjectRef| {
+            a * 2 + b.is_null() as i32
+        })
+        .unwrap();
+        // Keeps the closure alive while other tests simulate collections
+        let _handle = GcHandle::new(delegate);
+
+        let invoke = callback.find_method(c"Invoke", 2).unwrap();
+        let this = Ref::new(delegate.as_ptr() as _);
+        let result: i32 = invoke.call(this, (20, Il2CppObjectRef::null())).unwrap();
+        assert_eq!(result, 41);
+        assert_eq!(delegate.target().as_ptr() as usize, delegate.as_ptr() as usize);
+    }
+
+    #[test]
+    fn panics_are_thrown() {
+        let image = mock::image(c"DelegateTestsPanic.dll");
+        let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+        let callback = ClassBuilder::new(image, c"Game", c"Callback")
+            .delegate(&[(c"a", int32)], int32)
+            .build();
+
+        let delegate = Il2CppDelegate::new(callback, |a: i32| -> i32 {
+            if a > 0 {
+                panic!("bad {a}");
+            }
+            a
+        })
+        .unwrap();
+        let _handle = GcHandle::new(delegate);
+
+        let invoke = callback.find_method(c"Invoke", 1).unwrap();
+        let this = Ref::new(delegate.as_ptr() as _);
+        match invoke.call::<_, i32>(this, (3,)) {
+            Err(InvokeError::Exception(exception)) => {
+                assert_eq!(exception.target().to_string(), "System.Exception: bad 3");
+            }
+            other => panic!("expected a managed exception, found {other:?}"),
+        }
+        assert_eq!(invoke.call::<_, i32>(this, (-3,)).unwrap(), -3);
+    }
+
+    #[test]
+    fn signature_errors() {
+        let image = mock::image(c"DelegateTestsErrors.dll");
+        let corlib = Il2CppImage::corlib();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let object = corlib.find_class(c"System", c"Object").unwrap();
+        let callback = ClassBuilder::new(image, c"Game", c"Callback")
+            .delegate(&[(c"a", int32), (c"b", object)], int32)
+            .build();
+
+        assert!(matches!(
+            Il2CppDelegate::new(int32, || ()),
+            Err(DelegateError::NotDelegate)
+        ));
+        assert!(matches!(
+            Il2CppDelegate::new(callback, |_a: i32| 0),
+            Err(DelegateError::ArgumentCount { expected: 2, found: 1 })
+        ));
+        assert!(matches!(
+            Il2CppDelegate::new(callback, |_a: f32, _b: Il2CppObjectRef| 0),
+            Err(DelegateError::ArgumentType { index: 0, .. })
+        ));
+        assert!(matches!(
+            Il2CppDelegate::new(callback, |_a: i32, _b: Il2CppObjectRef| ()),
+            Err(DelegateError::ReturnType { .. })
+        ));
+    }
+
+    #[test]
+    fn dead_closures_are_dropped() {
+        let image = mock::image(c"DelegateTestsCollect.dll");
+        let void = Il2CppImage::corlib().find_class(c"System", c"Void").unwrap();
+        let action = ClassBuilder::new(image, c"Game", c"Notify")
+            .delegate(&[], void)
+            .build();
+
+        let hits = Arc::new(AtomicI32::new(0));
+        let captured = hits.clone();
+        let delegate = Il2CppDelegate::new(action, move || {
+            captured.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+        let handle = GcHandle::new(delegate);
+
+        let invoke = action.find_method(c"Invoke", 0).unwrap();
+        let () = invoke.call(Ref::new(delegate.as_ptr() as _), ()).unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Alive while the strong handle is held
+        mock::collect();
+        collect_dead_delegates();
+        assert_eq!(Arc::strong_count(&hits), 2);
+
+        drop(handle);
+        mock::collect();
+        assert!(collect_dead_delegates() >= 1);
+        assert_eq!(Arc::strong_count(&hits), 1);
+    }
+}
@@ -0,0 +1,45 @@
+//! Il2CppDomain
+
+use crate::runtime::{il2cpp_domain_get, il2cpp_domain_get_assemblies};
+use crate::{Il2CppAssembly, NonNullRef, Ref};
+use std::{fmt, slice};
+
+/// Domain handle
+pub type Il2CppDomain = NonNullRef<il2cpp_sys_rs::Il2CppDomain, ()>;
+/// Nullable Domain handle
+pub type Il2CppDomainRef = Ref<il2cpp_sys_rs::Il2CppDomain, ()>;
+
+impl Il2CppDomain {
+    /// Returns the domain of the runtime
+    ///
+    /// # Panics
+    ///
+    /// Panics if the domain pointer is null
+    #[track_caller]
+    #[inline]
+    pub fn get() -> Self {
+        Ref::new(unsafe { il2cpp_domain_get() }).unwrap_non_null()
+    }
+
+    /// Returns the loaded assemblies
+    #[inline]
+    pub fn assemblies(self) -> &'static [Il2CppAssembly] {
+        unsafe {
+            let mut size = 0;
+            let assemblies = il2cpp_domain_get_assemblies(self.as_ptr(), &mut size);
+            if assemblies.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(assemblies as *const Il2CppAssembly, size)
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Il2CppDomain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Il2CppDomain")
+            .field("assemblies", &self.assemblies().len())
+            .finish()
+    }
+}
@@ -0,0 +1,437 @@
+//! C#-like declarations of loaded classes
+//!
+//! Renders what an image contains, like external dumpers do, from the running game.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use il2cpp_rs::dump::Dumper;
+//! use il2cpp_rs::Il2CppAssembly;
+//!
+//! let image = Il2CppAssembly::from_name(c"Assembly-CSharp").unwrap().image();
+//! let file = std::fs::File::create("Assembly-CSharp.cs")?;
+//!
+//! Dumper::new()
+//!     .namespace("Game")
+//!     .filter(|class| !class.is_enum())
+//!     .dump_image(image, std::io::BufWriter::new(file))?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use crate::flags::{
+    METHOD_ATTRIBUTE_FAM_AND_ASSEM, METHOD_ATTRIBUTE_FAM_OR_ASSEM, METHOD_ATTRIBUTE_FAMILY,
+    METHOD_ATTRIBUTE_NEW_SLOT, METHOD_ATTRIBUTE_PRIVATE, METHOD_ATTRIBUTE_PUBLIC,
+    TYPE_ATTRIBUTE_NESTED_ASSEMBLY, TYPE_ATTRIBUTE_NESTED_FAM_AND_ASSEM,
+    TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM, TYPE_ATTRIBUTE_NESTED_FAMILY,
+    TYPE_ATTRIBUTE_NESTED_PRIVATE, TYPE_ATTRIBUTE_NESTED_PUBLIC, TYPE_ATTRIBUTE_PUBLIC,
+};
+use crate::{Il2CppClass, Il2CppImage, MethodInfo, PropertyInfo};
+use std::io::{self, Write};
+use std::{fmt, iter};
+
+/// Base classes implied by the declaration keyword
+const IMPLICIT_BASES: [&str; 3] = ["System.Object", "System.ValueType", "System.Enum"];
+
+/// Renders C#-like declarations of classes
+///
+/// Every class is dumped unless filtered out by namespace or predicate.
+pub struct Dumper<'a> {
+    /// Namespaces to dump, every namespace if empty
+    namespaces: Vec<String>,
+    filter: Option<Box<dyn Fn(Il2CppClass) -> bool + 'a>>,
+    /// Base address of the runtime module, to compute RVAs
+    module_base: Option<usize>,
+}
+
+impl<'a> Dumper<'a> {
+    /// Creates a dumper of every class
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            namespaces: Vec::new(),
+            filter: None,
+            module_base: None,
+        }
+    }
+
+    /// Dumps the classes of `namespace`, can be called several times
+    ///
+    /// Nested classes belong to the namespace of their outermost declaring class.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Exact namespace, empty for global
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespaces.push(namespace.into());
+        self
+    }
+
+    /// Dumps the classes matching `predicate`
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Returns `true` for the classes to dump
+    pub fn filter(mut self, predicate: impl Fn(Il2CppClass) -> bool + 'a) -> Self {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    /// Sets the base address of the runtime module, such as `GameAssembly`
+    ///
+    /// Method RVAs are printed relative to it, otherwise only absolute addresses are printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - Address the module is loaded at
+    pub fn module_base(mut self, base: usize) -> Self {
+        self.module_base = Some(base);
+        self
+    }
+
+    /// Returns `true` if `class` passes the namespace and predicate filters
+    pub fn includes(&self, class: Il2CppClass) -> bool {
+        let outermost = iter::successors(Some(class), |class| class.declaring_type())
+            .last()
+            .unwrap_or(class);
+        let namespace = outermost.namespace().to_string_lossy();
+        (self.namespaces.is_empty() || self.namespaces.iter().any(|ns| *ns == namespace))
+            && self.filter.as_ref().is_none_or(|filter| filter(class))
+    }
+
+    /// Writes the declarations of the classes of `image`
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Image to dump
+    /// * `out` - Output, buffered by the caller if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `out`
+    pub fn dump_image(&self, image: Il2CppImage, mut out: impl Write) -> io::Result<()> {
+        writeln!(out, "// Image: {}", image.name().to_string_lossy())?;
+        for class in image.classes().filter(|&class| self.includes(class)) {
+            writeln!(out)?;
+            self.dump_class(class, &mut out)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the declaration of `class`, whether it passes the filters or not
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Class to dump
+    /// * `out` - Output
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of `out`
+    pub fn dump_class(&self, class: Il2CppClass, mut out: impl Write) -> io::Result<()> {
+        let outermost = iter::successors(Some(class), |class| class.declaring_type())
+            .last()
+            .unwrap_or(class);
+        writeln!(out, "// Namespace: {}", outermost.namespace().to_string_lossy())?;
+
+        let bases: Vec<_> = class
+            .parent()
+            .map(|parent| parent.qualified_name('.'))
+            .filter(|parent| !IMPLICIT_BASES.contains(&parent.as_ref()))
+            .into_iter()
+            .chain(class.interfaces().map(|interface| interface.qualified_name('.')))
+            .collect();
+        write!(
+            out,
+            "{} {}{} {}",
+            type_accessibility(class),
+            type_modifiers(class),
+            type_keyword(class),
+            class.nested_name().replace('/', ".")
+        )?;
+        if !bases.is_empty() {
+            write!(out, " : {}", bases.join(", "))?;
+        }
+        writeln!(out, " // Token: {:#010x}", class.token())?;
+        writeln!(out, "{{")?;
+
+        let mut section = Section::new(&mut out);
+        for field in class.fields() {
+            section.header("Fields")?;
+            let mut modifiers = String::new();
+            if field.is_const() {
+                modifiers.push_str("const ");
+            } else if field.is_static() {
+                modifiers.push_str("static ");
+            }
+            if field.is_readonly() {
+                modifiers.push_str("readonly ");
+            }
+            write!(
+                section.out,
+                "\t{} {modifiers}{} {}; ",
+                member_accessibility(field.accessibility()),
+                field.type_().name(),
+                field.name().to_string_lossy(),
+            )?;
+            if field.is_const() {
+                writeln!(section.out, "// Constant")?;
+            } else {
+                writeln!(section.out, "// {:#x}", field.offset())?;
+            }
+        }
+
+        let mut section = section.next();
+        for property in class.properties() {
+            section.header("Properties")?;
+            let accessors = property.getter().into_iter().chain(property.setter());
+            let static_ = accessors.clone().any(MethodInfo::is_static);
+            let accessibility = accessors.map(MethodInfo::accessibility).max().unwrap_or(0);
+            writeln!(
+                section.out,
+                "\t{} {}{} {} {{ {}}}",
+                member_accessibility(accessibility),
+                if static_ { "static " } else { "" },
+                property.type_().name(),
+                property.name().to_string_lossy(),
+                property_accessors(property),
+            )?;
+        }
+
+        let mut section = section.next();
+        for &method in class.methods() {
+            section.header("Methods")?;
+            match (method.address(), self.module_base) {
+                (Some(address), Some(base)) => writeln!(
+                    section.out,
+                    "\t// RVA: {:#x} VA: {address:#x}",
+                    address.wrapping_sub(base)
+                )?,
+                (Some(address), None) => writeln!(section.out, "\t// VA: {address:#x}")?,
+                (None, _) => writeln!(section.out, "\t// RVA: -1")?,
+            }
+            writeln!(
+                section.out,
+                "\t{} {}{}{}",
+                member_accessibility(method.accessibility()),
+                method_modifiers(method),
+                method.signature(),
+                if method.is_abstract() { ";" } else { " { }" },
+            )?;
+        }
+
+        writeln!(out, "}}")
+    }
+}
+
+impl Default for Dumper<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Dumper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dumper")
+            .field("namespaces", &self.namespaces)
+            .field("filter", &self.filter.is_some())
+            .field("module_base", &self.module_base)
+            .finish()
+    }
+}
+
+/// Member section of a class body, separated from the previous one by a blank line
+struct Section<'w, W: Write> {
+    out: &'w mut W,
+    /// Whether a previous section was written
+    separated: bool,
+    /// Whether the header of this section was written
+    started: bool,
+}
+
+impl<'w, W: Write> Section<'w, W> {
+    fn new(out: &'w mut W) -> Self {
+        Self {
+            out,
+            separated: false,
+            started: false,
+        }
+    }
+
+    /// Writes the section header before its first member
+    fn header(&mut self, name: &str) -> io::Result<()> {
+        if !self.started {
+            if self.separated {
+                writeln!(self.out)?;
+            }
+            writeln!(self.out, "\t// {name}")?;
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    fn next(self) -> Self {
+        Self {
+            separated: self.separated || self.started,
+            started: false,
+            out: self.out,
+        }
+    }
+}
+
+/// Returns the C# keyword of the class visibility
+fn type_accessibility(class: Il2CppClass) -> &'static str {
+    match class.accessibility() {
+        TYPE_ATTRIBUTE_PUBLIC | TYPE_ATTRIBUTE_NESTED_PUBLIC => "public",
+        TYPE_ATTRIBUTE_NESTED_PRIVATE => "private",
+        TYPE_ATTRIBUTE_NESTED_FAMILY => "protected",
+        TYPE_ATTRIBUTE_NESTED_ASSEMBLY => "internal",
+        TYPE_ATTRIBUTE_NESTED_FAM_AND_ASSEM => "private protected",
+        TYPE_ATTRIBUTE_NESTED_FAM_OR_ASSEM => "protected internal",
+        _ => "internal",
+    }
+}
+
+/// Returns the `static`, `abstract` or `sealed` modifier implied by the class flags
+fn type_modifiers(class: Il2CppClass) -> &'static str {
+    if class.is_interface() || class.is_value_type() {
+        // Interfaces are abstract and value types are sealed
+        return "";
+    }
+    match (class.is_abstract(), class.is_sealed()) {
+        (true, true) => "static ",
+        (true, false) => "abstract ",
+        (false, true) => "sealed ",
+        (false, false) => "",
+    }
+}
+
+/// Returns the C# keyword declaring the class
+fn type_keyword(class: Il2CppClass) -> &'static str {
+    if class.is_interface() {
+        "interface"
+    } else if class.is_enum() {
+        "enum"
+    } else if class.is_value_type() {
+        "struct"
+    } else {
+        "class"
+    }
+}
+
+/// Returns the C# keyword of a field or method accessibility
+fn member_accessibility(accessibility: u32) -> &'static str {
+    match accessibility {
+        METHOD_ATTRIBUTE_PUBLIC => "public",
+        METHOD_ATTRIBUTE_FAMILY => "protected",
+        METHOD_ATTRIBUTE_FAM_OR_ASSEM => "protected internal",
+        METHOD_ATTRIBUTE_FAM_AND_ASSEM => "private protected",
+        METHOD_ATTRIBUTE_PRIVATE => "private",
+        // Compiler controlled members are not accessible either
+        0 => "private",
+        _ => "internal",
+    }
+}
+
+/// Returns the `static`, `abstract`, `virtual`, `override` or `sealed` modifiers of a method
+fn method_modifiers(method: MethodInfo) -> &'static str {
+    let new_slot = method.flags() & METHOD_ATTRIBUTE_NEW_SLOT != 0;
+    if method.is_static() {
+        "static "
+    } else if method.is_abstract() {
+        if new_slot { "abstract " } else { "abstract override " }
+    } else if method.is_virtual() && method.is_final() {
+        if new_slot { "" } else { "sealed override " }
+    } else if method.is_virtual() {
+        if new_slot { "virtual " } else { "override " }
+    } else {
+        ""
+    }
+}
+
+/// Returns the accessors of a property, such as `get; set; `
+fn property_accessors(property: PropertyInfo) -> String {
+    let mut accessors = String::new();
+    if property.getter().is_some() {
+        accessors.push_str("get; ");
+    }
+    if property.setter().is_some() {
+        accessors.push_str("set; ");
+    }
+    accessors
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::Dumper;
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppImage, Ref};
+    use std::ffi::CStr;
+    use std::mem;
+
+    fn corlib(name: &CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn dump_image() {
+        let image = mock::image(c"DumpTests.dll");
+        let player = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .static_field(c"count", corlib(c"Int32"))
+            .method(c"get_Health", &[], corlib(c"Int32"), |_| Ok(Ref::null()))
+            .method(c"Jump", &[(c"height", corlib(c"Single"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .property(c"Health", Some(c"get_Health"), None)
+            .build();
+        let inner = ClassBuilder::new(image, c"", c"Inner").nested_in(player).build();
+        ClassBuilder::new(image, c"Other", c"Hidden").build();
+
+        // Mock methods have no code, give `Jump` a fake address, never called
+        let jump = player.find_method(c"Jump", 1).unwrap();
+        unsafe {
+            (*jump.as_ptr()).methodPointer =
+                Some(mem::transmute::<usize, unsafe extern "C" fn()>(0x1801_2340));
+        }
+
+        let dump = |dumper: Dumper| {
+            let mut out = Vec::new();
+            dumper.namespace("Game").dump_image(image, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let expected = |jump: &str| {
+            format!(
+                "// Image: DumpTests.dll\n\
+                 \n\
+                 // Namespace: Game\n\
+                 public class Player // Token: {:#010x}\n\
+                 {{\n\
+                 \t// Fields\n\
+                 \tpublic System.Int32 health; // 0x10\n\
+                 \tpublic static System.Int32 count; // 0x0\n\
+                 \n\
+                 \t// Properties\n\
+                 \tpublic System.Int32 Health {{ get; }}\n\
+                 \n\
+                 \t// Methods\n\
+                 \t// RVA: -1\n\
+                 \tpublic System.Int32 get_Health() {{ }}\n\
+                 \t{jump}\n\
+                 \tpublic System.Void Jump(System.Single height) {{ }}\n\
+                 }}\n\
+                 \n\
+                 // Namespace: Game\n\
+                 public class Player.Inner // Token: {:#010x}\n\
+                 {{\n\
+                 }}\n",
+                player.token(),
+                inner.token(),
+            )
+        };
+
+        let out = dump(Dumper::new());
+        assert_eq!(out, expected("// VA: 0x18012340"));
+        let out = dump(Dumper::new().module_base(0x1800_0000));
+        assert_eq!(out, expected("// RVA: 0x12340 VA: 0x18012340"));
+    }
+}
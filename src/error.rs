@@ -0,0 +1,298 @@
+//! Il2CppError
+
+use crate::{
+    DelegateError, EventError, Exception, GcHandle, GenericError, Il2CppClass, Il2CppImage,
+    InvokeError, LookupError, MethodInfo, PropertyInfo,
+};
+use std::borrow::Cow;
+use std::error::Error;
+use std::ffi::CString;
+use std::fmt;
+
+/// Error raised by lookups, allocations and invocations through the runtime
+///
+/// A managed exception is always reported as [`Il2CppError::ManagedException`], so it cannot be
+/// mistaken for a missing member.
+#[derive(Debug)]
+pub enum Il2CppError {
+    /// The image has no such class
+    ClassNotFound {
+        /// Image searched
+        image: Il2CppImage,
+        /// Namespace of the class
+        namespace: CString,
+        /// Class name
+        name: CString,
+    },
+    /// The class has no such method
+    MethodNotFound {
+        /// Class searched
+        class: Il2CppClass,
+        /// Method name
+        name: CString,
+        /// Parameter count, `-1` for any
+        arity: i32,
+    },
+    /// Several overloads match the method parameter types
+    AmbiguousMethod {
+        /// Class searched
+        class: Il2CppClass,
+        /// Method name
+        name: CString,
+        /// Every matching overload
+        candidates: Vec<MethodInfo>,
+    },
+    /// The class has no such field
+    FieldNotFound {
+        /// Class searched
+        class: Il2CppClass,
+        /// Field name
+        name: CString,
+    },
+    /// The class has no such property
+    PropertyNotFound {
+        /// Class searched
+        class: Il2CppClass,
+        /// Property name
+        name: CString,
+    },
+    /// The class and its base classes have no such event
+    EventNotFound {
+        /// Class searched
+        class: Il2CppClass,
+        /// Event name
+        name: CString,
+    },
+    /// The property cannot be read
+    NoGetter(PropertyInfo),
+    /// The property cannot be written
+    NoSetter(PropertyInfo),
+    /// The runtime threw a managed exception
+    ///
+    /// Held by a strong GC handle, so the error can outlive the call and move across threads.
+    ManagedException(GcHandle<il2cpp_sys_rs::Il2CppException>),
+    /// A value is not of the expected type
+    TypeMismatch {
+        /// Expected type
+        expected: Cow<'static, str>,
+        /// Actual type
+        found: Cow<'static, str>,
+    },
+    /// The runtime failed to allocate an object
+    AllocationFailed(&'static str),
+    /// A typed invocation failed
+    Invoke(InvokeError),
+    /// A lazy lookup failed
+    Lookup(LookupError),
+    /// A generic instantiation failed
+    ///
+    /// Boxed, since [`GenericError`] holds the runtime errors of the instantiation.
+    Generic(Box<GenericError>),
+    /// A closure does not match the delegate signature
+    Delegate(DelegateError),
+    /// An event subscription failed
+    Event(EventError),
+    /// The installed runtime does not export the `il2cpp_*` function backing the operation
+    MissingExport(&'static str),
+}
+
+// Safety: the metadata handles (images, classes, methods and properties) point to runtime
+// metadata, immutable and never freed. Managed exceptions are rooted by their GC handle.
+unsafe impl Send for Il2CppError {}
+unsafe impl Sync for Il2CppError {}
+
+impl fmt::Display for Il2CppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClassNotFound {
+                image,
+                namespace,
+                name,
+            } => {
+                f.write_str("class ")?;
+                if !namespace.is_empty() {
+                    write!(f, "{}.", namespace.to_string_lossy())?;
+                }
+                write!(
+                    f,
+                    "{} not found in image {}",
+                    name.to_string_lossy(),
+                    image.name().to_string_lossy()
+                )
+            }
+            Self::MethodNotFound { class, name, arity } => {
+                write!(f, "method {}.{}", class.full_name(), name.to_string_lossy())?;
+                if *arity >= 0 {
+                    write!(f, " with {arity} parameters")?;
+                }
+                f.write_str(" not found")
+            }
+            Self::AmbiguousMethod {
+                class,
+                name,
+                candidates,
+            } => {
+                write!(
+                    f,
+                    "method {}.{} is ambiguous, {} overloads match:",
+                    class.full_name(),
+                    name.to_string_lossy(),
+                    candidates.len()
+                )?;
+                for candidate in candidates {
+                    write!(f, " `{candidate}`")?;
+                }
+                Ok(())
+            }
+            Self::FieldNotFound { class, name } => write!(
+                f,
+                "field {}.{} not found",
+                class.full_name(),
+                name.to_string_lossy()
+            ),
+            Self::PropertyNotFound { class, name } => write!(
+                f,
+                "property {}.{} not found",
+                class.full_name(),
+                name.to_string_lossy()
+            ),
+            Self::EventNotFound { class, name } => write!(
+                f,
+                "event {}.{} not found",
+                class.full_name(),
+                name.to_string_lossy()
+            ),
+            Self::NoGetter(property) => {
+                write!(f, "property {}.{property} has no getter", property.parent().full_name())
+            }
+            Self::NoSetter(property) => {
+                write!(f, "property {}.{property} has no setter", property.parent().full_name())
+            }
+            Self::ManagedException(exception) => {
+                write!(f, "managed exception: {}", exception.target())
+            }
+            Self::TypeMismatch { expected, found } => {
+                write!(f, "expected `{expected}`, found `{found}`")
+            }
+            Self::AllocationFailed(what) => write!(f, "failed to allocate {what}"),
+            Self::Invoke(err) => fmt::Display::fmt(err, f),
+            Self::Lookup(err) => fmt::Display::fmt(err, f),
+            Self::Generic(err) => fmt::Display::fmt(err, f),
+            Self::Delegate(err) => fmt::Display::fmt(err, f),
+            Self::Event(err) => fmt::Display::fmt(err, f),
+            Self::MissingExport(name) => write!(f, "the IL2CPP runtime does not export {name}"),
+        }
+    }
+}
+
+impl Error for Il2CppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Invoke(err) => Some(err),
+            Self::Lookup(err) => Some(err),
+            Self::Generic(err) => Some(err),
+            Self::Delegate(err) => Some(err),
+            Self::Event(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Exception> for Il2CppError {
+    fn from(exception: Exception) -> Self {
+        Self::ManagedException(GcHandle::new(exception))
+    }
+}
+
+/// Managed exceptions thrown by the invoked method are kept as [`Il2CppError::ManagedException`]
+impl From<InvokeError> for Il2CppError {
+    fn from(err: InvokeError) -> Self {
+        match err {
+            InvokeError::Exception(exception) => Self::ManagedException(exception),
+            err => Self::Invoke(err),
+        }
+    }
+}
+
+impl From<LookupError> for Il2CppError {
+    fn from(err: LookupError) -> Self {
+        Self::Lookup(err)
+    }
+}
+
+/// Runtime errors and managed exceptions of the instantiation are unwrapped
+impl From<GenericError> for Il2CppError {
+    fn from(err: GenericError) -> Self {
+        match err {
+            GenericError::Runtime(err) => err,
+            GenericError::Invoke(err) => err.into(),
+            err => Self::Generic(Box::new(err)),
+        }
+    }
+}
+
+/// Allocation failures are kept as [`Il2CppError::AllocationFailed`]
+impl From<DelegateError> for Il2CppError {
+    fn from(err: DelegateError) -> Self {
+        match err {
+            DelegateError::AllocationFailed => Self::AllocationFailed("delegate"),
+            err => Self::Delegate(err),
+        }
+    }
+}
+
+/// Managed exceptions thrown by the accessor are kept as [`Il2CppError::ManagedException`]
+impl From<EventError> for Il2CppError {
+    fn from(err: EventError) -> Self {
+        match err {
+            EventError::Invoke(err) => err.into(),
+            err => Self::Event(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Il2CppError, LookupError};
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<Il2CppError>();
+        assert_send_sync::<LookupError>();
+        assert_send_sync::<Box<dyn std::error::Error + Send + Sync>>();
+        let _: Box<dyn std::error::Error + Send + Sync> =
+            Il2CppError::AllocationFailed("string").into();
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn conversions() {
+        use crate::mock;
+        use crate::{DelegateError, EventError, GcHandle, GenericError, InvokeError};
+        use std::error::Error;
+
+        let err = Il2CppError::from(EventError::Invoke(InvokeError::Exception(GcHandle::new(
+            mock::exception("cannot subscribe"),
+        ))));
+        assert!(matches!(err, Il2CppError::ManagedException(_)));
+        assert_eq!(err.to_string(), "managed exception: System.Exception: cannot subscribe");
+
+        let err = Il2CppError::from(GenericError::Runtime(Il2CppError::AllocationFailed("type")));
+        assert!(matches!(err, Il2CppError::AllocationFailed("type")));
+        let err = Il2CppError::from(GenericError::Invoke(InvokeError::Exception(GcHandle::new(
+            mock::exception("constraint"),
+        ))));
+        assert!(matches!(err, Il2CppError::ManagedException(_)));
+
+        let err = Il2CppError::from(GenericError::NotGenericDefinition);
+        assert_eq!(err.to_string(), "not a generic definition");
+        assert!(err.source().is_some());
+        let err = Il2CppError::from(DelegateError::NotDelegate);
+        assert!(matches!(err, Il2CppError::Delegate(DelegateError::NotDelegate)));
+        let err = Il2CppError::from(EventError::MissingAccessor("add"));
+        assert_eq!(err.to_string(), "the event has no `add` method");
+        let err = Il2CppError::from(LookupError::AssemblyNotFound(c"Assembly-CSharp"));
+        assert_eq!(err.to_string(), "assembly Assembly-CSharp not loaded");
+    }
+}
@@ -0,0 +1,456 @@
+//! EventInfo
+
+use crate::runtime::il2cpp_class_get_events;
+use crate::{
+    GcHandle, Il2CppClass, Il2CppDelegate, Il2CppError, Il2CppObjectRef, Il2CppTypeHandle,
+    InvokeError, MethodInfo, NonNullRef, Ref, WeakGcHandle,
+};
+use std::error::Error;
+use std::ffi::{c_void, CStr};
+use std::{fmt, iter, ptr};
+
+/// EventInfo handle
+pub type EventInfo = NonNullRef<il2cpp_sys_rs::EventInfo, ()>;
+/// Nullable EventInfo handle
+pub type EventInfoRef = Ref<il2cpp_sys_rs::EventInfo, ()>;
+
+/// Error raised by [`EventInfo::subscribe`] and [`Subscription::unsubscribe`]
+#[derive(Debug)]
+pub enum EventError {
+    /// The event has no `add` or `remove` accessor
+    MissingAccessor(&'static str),
+    /// The delegate is not an instance of the event type
+    DelegateType {
+        /// Event type name
+        expected: String,
+        /// Delegate class name
+        found: String,
+    },
+    /// The accessor call failed
+    Invoke(InvokeError),
+}
+
+impl fmt::Display for EventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAccessor(accessor) => write!(f, "the event has no `{accessor}` method"),
+            Self::DelegateType { expected, found } => {
+                write!(f, "delegate is `{found}`, expected `{expected}`")
+            }
+            Self::Invoke(err) => write!(f, "event accessor failed: {err}"),
+        }
+    }
+}
+
+impl Error for EventError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Invoke(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<InvokeError> for EventError {
+    #[inline]
+    fn from(err: InvokeError) -> Self {
+        Self::Invoke(err)
+    }
+}
+
+impl EventInfo {
+    /// Returns the event name
+    #[inline]
+    pub const fn name(self) -> &'static CStr {
+        // Safety: `name` is never null
+        unsafe { CStr::from_ptr(self.as_ref().name) }
+    }
+
+    /// Returns the event type, the delegate type of its handlers
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type pointer is null
+    #[track_caller]
+    #[inline]
+    pub const fn type_(self) -> Il2CppTypeHandle {
+        Ref::new(self.as_ref().eventType as _).unwrap_non_null()
+    }
+
+    /// Returns the parent class of the event
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parent class pointer is null
+    #[track_caller]
+    #[inline]
+    pub const fn parent(self) -> Il2CppClass {
+        Il2CppClass::from_ptr(self.as_ref().parent as _).unwrap()
+    }
+
+    /// Returns the `add` accessor, if any
+    #[inline]
+    pub const fn add_method(self) -> Option<MethodInfo> {
+        Ref::new(self.as_ref().add as _).non_null()
+    }
+
+    /// Returns the `remove` accessor, if any
+    #[inline]
+    pub const fn remove_method(self) -> Option<MethodInfo> {
+        Ref::new(self.as_ref().remove as _).non_null()
+    }
+
+    /// Returns the `raise` accessor, if any
+    ///
+    /// C# compilers never emit one, the declaring class invokes the backing delegate instead.
+    #[inline]
+    pub const fn raise_method(self) -> Option<MethodInfo> {
+        Ref::new(self.as_ref().raise as _).non_null()
+    }
+
+    /// Returns `true` if the event is static
+    #[inline]
+    pub fn is_static(self) -> bool {
+        self.add_method().is_some_and(MethodInfo::is_static)
+    }
+
+    /// Returns the event token
+    #[inline]
+    pub const fn token(self) -> u32 {
+        self.as_ref().token
+    }
+
+    /// Adds `delegate` to the handlers of the event
+    ///
+    /// The handler is removed when the returned [`Subscription`] is dropped, or by
+    /// [`Subscription::unsubscribe`] to handle failures.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Instance raising the event, null for a static event
+    /// * `delegate` - Handler, an instance of the event type
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EventError`] if the delegate type does not match or if `add` fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{Il2CppClass, Il2CppDelegate, Il2CppError, Ref};
+    /// # fn example(class: Il2CppClass, delegate: Il2CppDelegate) -> Result<(), Il2CppError> {
+    /// let event = class.find_event(c"sceneLoaded")?;
+    /// let subscription = event.subscribe(Ref::null(), delegate)?;
+    /// // ...
+    /// subscription.unsubscribe()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe(
+        self,
+        target: Il2CppObjectRef,
+        delegate: Il2CppDelegate,
+    ) -> Result<Subscription, EventError> {
+        let add = self.add_method().ok_or(EventError::MissingAccessor("add"))?;
+        let delegate_class = delegate.as_object().class();
+        if let Some(event_class) = self.type_().class()
+            && !delegate_class.is_subclass_of(event_class, false)
+        {
+            return Err(EventError::DelegateType {
+                expected: self.type_().name(),
+                found: delegate_class.full_name().into_owned(),
+            });
+        }
+
+        add.call::<_, ()>(target, (delegate,))?;
+        Ok(Subscription {
+            event: self,
+            target: target.non_null().map(|target| WeakGcHandle::new(target, false)),
+            delegate: Some(GcHandle::new(delegate)),
+        })
+    }
+}
+
+impl fmt::Display for EventInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name().to_string_lossy(), f)
+    }
+}
+
+impl fmt::Debug for EventInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventInfo")
+            .field("name", &self.name().to_string_lossy())
+            .field("type", &self.type_().name())
+            .finish()
+    }
+}
+
+/// Event handler added by [`EventInfo::subscribe`]
+///
+/// Removes the handler when dropped. Dropping is best-effort: a missing `remove` accessor or a
+/// failed call is ignored, and the handler may stay subscribed. Use [`Subscription::unsubscribe`]
+/// to handle these errors.
+///
+/// The subscription keeps the delegate alive, but not the instance raising the event: nothing is
+/// removed if it was collected.
+pub struct Subscription {
+    event: EventInfo,
+    target: Option<WeakGcHandle<il2cpp_sys_rs::Il2CppObject>>,
+    /// `None` once the handler is removed
+    delegate: Option<GcHandle<il2cpp_sys_rs::Il2CppDelegate>>,
+}
+
+impl Subscription {
+    /// Returns the subscribed event
+    #[inline]
+    pub fn event(&self) -> EventInfo {
+        self.event
+    }
+
+    /// Returns the handler
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime does not resolve the delegate handle
+    #[track_caller]
+    #[inline]
+    pub fn delegate(&self) -> Il2CppDelegate {
+        self.delegate.as_ref().expect("subscribed delegate").target()
+    }
+
+    /// Removes the handler
+    ///
+    /// Unlike dropping the subscription, failures are reported. The handler is not removed again
+    /// when the subscription is dropped, even if this failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`EventError`] if the event has no `remove` accessor or if `remove` fails
+    #[inline]
+    pub fn unsubscribe(mut self) -> Result<(), EventError> {
+        self.remove()
+    }
+
+    /// Keeps the handler subscribed for as long as the instance raising the event lives
+    #[inline]
+    pub fn forget(mut self) {
+        // The event keeps the delegate alive from now on
+        self.delegate = None;
+    }
+
+    fn remove(&mut self) -> Result<(), EventError> {
+        let Some(delegate) = self.delegate.take() else {
+            return Ok(());
+        };
+        let target = match &self.target {
+            Some(target) => match target.target() {
+                Some(target) => target.into(),
+                // The instance was collected along with its handlers
+                None => return Ok(()),
+            },
+            None => Ref::null(),
+        };
+
+        let remove = self
+            .event
+            .remove_method()
+            .ok_or(EventError::MissingAccessor("remove"))?;
+        remove.call::<_, ()>(target, (delegate.target(),))?;
+        Ok(())
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _ = self.remove();
+    }
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Subscription")
+            .field("event", &self.event)
+            .field("delegate", &self.delegate)
+            .finish()
+    }
+}
+
+impl Il2CppClass {
+    /// Returns the events declared by the class
+    ///
+    /// Inherited events are not included.
+    pub fn events(self) -> impl Iterator<Item = EventInfo> {
+        let mut iter: *mut c_void = ptr::null_mut();
+        iter::from_fn(move || {
+            EventInfo::from_ptr(unsafe { il2cpp_class_get_events(self.as_ptr(), &mut iter) as _ })
+        })
+    }
+
+    /// Finds an event by name, in the class or its base classes
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Event name
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::EventNotFound`] if neither the class nor its base classes declare
+    /// the event
+    pub fn find_event(self, name: &CStr) -> Result<EventInfo, Il2CppError> {
+        iter::successors(Some(self), |class| class.parent())
+            .flat_map(Il2CppClass::events)
+            .find(|event| event.name() == name)
+            .ok_or_else(|| Il2CppError::EventNotFound {
+                class: self,
+                name: name.to_owned(),
+            })
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{
+        EventError, GcHandle, Il2CppDelegate, Il2CppError, Il2CppImage, Il2CppObject, Ref,
+    };
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn events() {
+        let image = mock::image(c"EventTests.dll");
+        let corlib = Il2CppImage::corlib();
+        let void = corlib.find_class(c"System", c"Void").unwrap();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let handler = ClassBuilder::new(image, c"Game", c"Handler")
+            .delegate(&[(c"value", int32)], void)
+            .build();
+        let base = ClassBuilder::new(image, c"Game", c"Base")
+            .static_method(c"add_Changed", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .static_method(c"remove_Changed", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .event(c"Changed", handler, c"add_Changed", c"remove_Changed")
+            .build();
+        let derived = ClassBuilder::new(image, c"Game", c"Derived")
+            .parent(base)
+            .method(c"add_A", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .method(c"remove_A", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .method(c"add_B", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .method(c"remove_B", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .event(c"A", handler, c"add_A", c"remove_A")
+            .event(c"B", handler, c"add_B", c"remove_B")
+            .build();
+
+        let names: Vec<_> = derived.events().map(|event| event.name().to_owned()).collect();
+        assert_eq!(names, [c"A".to_owned(), c"B".to_owned()]);
+        assert_eq!(base.events().count(), 1);
+
+        let changed = derived.find_event(c"Changed").unwrap();
+        assert_eq!(changed.parent().as_ptr(), base.as_ptr());
+        assert_eq!(changed.type_().name(), "Game.Handler");
+        assert_eq!(changed.add_method().unwrap().name(), c"add_Changed");
+        assert!(changed.raise_method().is_none());
+        assert!(changed.is_static());
+
+        let a = derived.find_event(c"A").unwrap();
+        assert!(!a.is_static());
+        let err = derived.find_event(c"Missing").unwrap_err();
+        assert!(matches!(err, Il2CppError::EventNotFound { .. }));
+        assert_eq!(err.to_string(), "event Game.Derived.Missing not found");
+    }
+
+    #[test]
+    fn subscribe() {
+        static HANDLERS: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+        let image = mock::image(c"EventTestsSubscribe.dll");
+        let corlib = Il2CppImage::corlib();
+        let void = corlib.find_class(c"System", c"Void").unwrap();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let handler = ClassBuilder::new(image, c"Game", c"Handler")
+            .delegate(&[(c"value", int32)], void)
+            .build();
+        let other = ClassBuilder::new(image, c"Game", c"Other").delegate(&[], void).build();
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .static_method(c"add_Changed", &[(c"value", handler)], void, |call| {
+                HANDLERS.lock().unwrap().push(call.arg_object::<()>(0).as_ptr() as usize);
+                Ok(Ref::null())
+            })
+            .static_method(c"remove_Changed", &[(c"value", handler)], void, |call| {
+                let delegate = call.arg_object::<()>(0).as_ptr() as usize;
+                HANDLERS.lock().unwrap().retain(|&handler| handler != delegate);
+                Ok(Ref::null())
+            })
+            .static_method(c"add_Broken", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .static_method(c"remove_Broken", &[(c"value", handler)], void, |_| {
+                Err(mock::exception("cannot remove"))
+            })
+            .event(c"Changed", handler, c"add_Changed", c"remove_Changed")
+            .event(c"Broken", handler, c"add_Broken", c"remove_Broken")
+            .build();
+        let invoke = handler.find_method(c"Invoke", 1).unwrap();
+        let raise = |value: i32| {
+            for &delegate in HANDLERS.lock().unwrap().clone().iter() {
+                let () = invoke.call(Ref::new(delegate as _), (value,)).unwrap();
+            }
+        };
+
+        let hits = Arc::new(AtomicI32::new(0));
+        let captured = hits.clone();
+        let delegate = Il2CppDelegate::new(handler, move |value: i32| {
+            captured.fetch_add(value, Ordering::SeqCst);
+        })
+        .unwrap();
+        let _handle = GcHandle::new(delegate);
+        let changed = class.find_event(c"Changed").unwrap();
+
+        let subscription = changed.subscribe(Ref::null(), delegate).unwrap();
+        assert_eq!(subscription.delegate().as_ptr(), delegate.as_ptr());
+        raise(3);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        drop(subscription);
+        raise(3);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        changed.subscribe(Ref::null(), delegate).unwrap().unsubscribe().unwrap();
+        assert!(HANDLERS.lock().unwrap().is_empty());
+        changed.subscribe(Ref::null(), delegate).unwrap().forget();
+        assert_eq!(HANDLERS.lock().unwrap().len(), 1);
+
+        // Reported by unsubscribe, ignored on drop
+        let broken = class.find_event(c"Broken").unwrap();
+        assert!(matches!(
+            broken.subscribe(Ref::null(), delegate).unwrap().unsubscribe(),
+            Err(EventError::Invoke(_))
+        ));
+        drop(broken.subscribe(Ref::null(), delegate).unwrap());
+
+        let wrong = Il2CppDelegate::new(other, || ()).unwrap();
+        assert!(matches!(
+            changed.subscribe(Ref::null(), wrong),
+            Err(EventError::DelegateType { .. })
+        ));
+    }
+
+    #[test]
+    fn instance_events() {
+        let image = mock::image(c"EventTestsInstance.dll");
+        let corlib = Il2CppImage::corlib();
+        let void = corlib.find_class(c"System", c"Void").unwrap();
+        let handler = ClassBuilder::new(image, c"Game", c"Handler").delegate(&[], void).build();
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .method(c"add_Died", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .method(c"remove_Died", &[(c"value", handler)], void, |_| Ok(Ref::null()))
+            .event(c"Died", handler, c"add_Died", c"remove_Died")
+            .build();
+        let delegate = Il2CppDelegate::new(handler, || ()).unwrap();
+        let _handle = GcHandle::new(delegate);
+        let died = class.find_event(c"Died").unwrap();
+
+        // Instance accessors called without an instance
+        assert!(matches!(died.subscribe(Ref::null(), delegate), Err(EventError::Invoke(_))));
+
+        let player = Il2CppObject::new(class).unwrap();
+        died.subscribe(player.into(), delegate).unwrap().unsubscribe().unwrap();
+    }
+}
@@ -1,16 +1,137 @@
 //! Il2CppException
 
-use crate::{NonNullRef, Ref};
-use il2cpp_sys_rs::il2cpp_format_exception;
-use std::ffi::{CStr, CString};
+use crate::runtime::{
+    il2cpp_exception_from_name_msg, il2cpp_format_exception, il2cpp_format_stack_trace,
+    il2cpp_raise_exception,
+};
+use crate::{Il2CppClass, Il2CppError, Il2CppImage, Il2CppString, NonNullRef, Ref};
+use std::any::Any;
+use std::convert::Infallible;
+use std::ffi::{c_char, CStr, CString};
 use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 
 /// Exception handle
 pub type Exception = NonNullRef<il2cpp_sys_rs::Il2CppException, ()>;
 /// Nullable Exception handle
 pub type ExceptionRef = Ref<il2cpp_sys_rs::Il2CppException, ()>;
 
+/// Managed exception class matched by [`Exception::is`]
+///
+/// Implemented by the marker types of [`exceptions`] for the core library exceptions.
+pub trait ExceptionClass {
+    /// Returns the exception class, or `None` if it is not loaded
+    fn class() -> Option<Il2CppClass>;
+}
+
 impl Exception {
+    /// Creates an exception of a class with a message
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Image containing the exception class
+    /// * `namespace` - Namespace of the class, such as `System`
+    /// * `name` - Class name, such as `InvalidOperationException`
+    /// * `message` - Exception message
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::ClassNotFound`] if the image has no such class,
+    /// or [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the exception
+    pub fn new(
+        image: Il2CppImage,
+        namespace: &CStr,
+        name: &CStr,
+        message: &CStr,
+    ) -> Result<Self, Il2CppError> {
+        // The runtime does not check that the class exists
+        image.find_class(namespace, name)?;
+
+        let exception = unsafe {
+            il2cpp_exception_from_name_msg(
+                image.as_ptr(),
+                namespace.as_ptr(),
+                name.as_ptr(),
+                message.as_ptr(),
+            )
+        };
+        Self::from_ptr(exception).ok_or(Il2CppError::AllocationFailed("exception"))
+    }
+
+    /// Throws the exception into the managed code that called the current native function
+    ///
+    /// The exception unwinds the Rust frames up to the runtime, running their destructors.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from a native function called by the runtime, such as a hook, an internal
+    /// call or a delegate backed by a closure. Every Rust function between the runtime and this
+    /// call must use the `C-unwind` ABI, and none of them can be within [`panic::catch_unwind`].
+    #[inline]
+    pub unsafe fn raise(self) -> ! {
+        unsafe { il2cpp_raise_exception(self.as_ptr()) }
+    }
+
+    /// Returns the runtime class of the exception
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class pointer is null
+    #[track_caller]
+    #[inline]
+    pub const fn class(self) -> Il2CppClass {
+        unsafe { Il2CppClass::from_ptr(self.as_ref().object.__bindgen_anon_1.klass) }.unwrap()
+    }
+
+    /// Returns the exception message, `Exception.Message`, if any
+    #[inline]
+    pub const fn message(self) -> Option<Il2CppString> {
+        Il2CppString::from_ptr(self.as_ref().message)
+    }
+
+    /// Returns the managed stack trace, `Exception.StackTrace`, if it has been captured
+    ///
+    /// Exceptions thrown by the runtime only keep the native instruction pointers,
+    /// see [`Self::format_stack_trace`].
+    #[inline]
+    pub const fn stack_trace(self) -> Option<Il2CppString> {
+        Il2CppString::from_ptr(self.as_ref().stack_trace)
+    }
+
+    /// Returns the exception that caused this one, `Exception.InnerException`, if any
+    #[inline]
+    pub const fn inner_exception(self) -> Option<Self> {
+        Self::from_ptr(self.as_ref().inner_ex)
+    }
+
+    /// Returns the HRESULT code of the exception, `Exception.HResult`
+    #[inline]
+    pub const fn hresult(self) -> i32 {
+        self.as_ref().hresult
+    }
+
+    /// Returns `true` if the exception is of class `E` or derives from it
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{exceptions, Il2CppError, MethodInfo, Ref};
+    /// # fn example(method: MethodInfo) {
+    /// match method.invoke::<()>(Ref::null(), &mut []) {
+    ///     Err(Il2CppError::ManagedException(exception))
+    ///         if exception.target().is::<exceptions::NullReferenceException>() =>
+    ///     {
+    ///         println!("null reference");
+    ///     }
+    ///     _ => {}
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is<E: ExceptionClass>(self) -> bool {
+        E::class().is_some_and(|class| self.class().is_subclass_of(class, false))
+    }
+
     /// Format the exception into a string
     ///
     /// If the formatted exception exceeds `N` bytes, the output will be truncated.
@@ -21,21 +142,316 @@ impl Exception {
     pub fn format<const N: usize>(self) -> CString {
         unsafe {
             let mut buffer = vec![0i8; N];
-            il2cpp_format_exception(self.as_ptr(), buffer.as_mut_ptr(), buffer.len() as i32);
+            // The runtime does not terminate truncated output
+            let size = buffer.len().saturating_sub(1) as i32;
+            il2cpp_format_exception(self.as_ptr(), buffer.as_mut_ptr(), size);
 
             CStr::from_ptr(buffer.as_ptr()).to_owned()
         }
     }
+
+    /// Formats the exception into a string, with its class, message and inner exceptions
+    ///
+    /// Unlike [`Self::format`], the output is never truncated.
+    pub fn format_full(self) -> CString {
+        let Ok(output) = format_growing::<Infallible>(|buffer, size| {
+            unsafe { il2cpp_format_exception(self.as_ptr(), buffer, size) };
+            Ok(())
+        });
+        output
+    }
+
+    /// Formats the stack trace of the exception, resolving its native instruction pointers
+    ///
+    /// The output is never truncated.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::MissingExport`] if the runtime does not export
+    /// `il2cpp_format_stack_trace`
+    pub fn format_stack_trace(self) -> Result<CString, Il2CppError> {
+        format_growing(|buffer, size| unsafe {
+            il2cpp_format_stack_trace(self.as_ptr(), buffer, size)
+        })
+    }
+}
+
+/// Runs the body of a native function called by the runtime, raising its panics as managed
+/// `System.Exception`
+///
+/// A Rust panic cannot unwind into the runtime. The panic message becomes the exception message,
+/// and the exception is thrown into the managed caller instead.
+///
+/// # Safety
+///
+/// Same as [`Exception::raise`], the calling function must use the `C-unwind` ABI
+///
+/// # Example
+///
+/// ```no_run
+/// use il2cpp_rs::catch_panic;
+///
+/// extern "C-unwind" fn get_delta_time() -> f32 {
+///     unsafe { catch_panic(|| compute_delta_time().expect("no frame")) }
+/// }
+/// # fn compute_delta_time() -> Option<f32> { None }
+/// ```
+pub unsafe fn catch_panic<R>(body: impl FnOnce() -> R) -> R {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(result) => result,
+        Err(payload) => unsafe { panic_exception(&*payload).raise() },
+    }
+}
+
+/// Creates the `System.Exception` raised for a panic
+///
+/// # Panics
+///
+/// Panics if `System.Exception` cannot be created
+fn panic_exception(payload: &(dyn Any + Send)) -> Exception {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.as_str()
+    } else {
+        "Rust panic"
+    };
+    // Interior nul bytes cannot be passed to the runtime
+    let message = CString::new(message.replace('\0', "")).unwrap();
+
+    Exception::new(Il2CppImage::corlib(), c"System", c"Exception", &message)
+        .expect("System.Exception")
+}
+
+/// Formats into a buffer grown until the output fits
+///
+/// The runtime copies at most `size` bytes, without a terminator when the output is truncated.
+/// Stops at the first error returned by `format`.
+fn format_growing<E>(format: impl Fn(*mut c_char, i32) -> Result<(), E>) -> Result<CString, E> {
+    let mut buffer: Vec<c_char> = vec![0; 1024];
+    loop {
+        let size = buffer.len() - 1;
+        format(buffer.as_mut_ptr(), size as i32)?;
+
+        let output = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+        if output.count_bytes() < size - 1 {
+            return Ok(output.to_owned());
+        }
+        buffer = vec![0; buffer.len() * 2];
+    }
 }
 
 impl fmt::Display for Exception {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt(&self.format::<1024>().to_string_lossy(), f)
+        fmt::Display::fmt(&self.format_full().to_string_lossy(), f)
     }
 }
 
 impl fmt::Debug for Exception {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(&self.format::<1024>().to_string_lossy(), f)
+        f.debug_struct("Exception")
+            .field("class", &self.class().full_name())
+            .field("message", &self.message())
+            .field("hresult", &format_args!("{:#010x}", self.hresult()))
+            .field("inner_exception", &self.inner_exception())
+            .finish()
+    }
+}
+
+/// Marker types of the core library exceptions, matched by [`Exception::is`]
+pub mod exceptions {
+    use super::ExceptionClass;
+    use crate::{Il2CppClass, LazyClass};
+    use std::ffi::CStr;
+
+    /// Returns `s` as a C string, `s` must end with a nul byte
+    const fn c_str(s: &'static str) -> &'static CStr {
+        match CStr::from_bytes_with_nul(s.as_bytes()) {
+            Ok(s) => s,
+            Err(_) => panic!("invalid C string"),
+        }
+    }
+
+    /// Declares a marker type per exception class of `mscorlib`
+    macro_rules! corlib_exceptions {
+        ($($namespace:literal => $($name:ident),+;)*) => {$($(
+            #[doc = concat!("`", $namespace, ".", stringify!($name), "`")]
+            #[derive(Clone, Copy, Debug)]
+            pub struct $name;
+
+            impl ExceptionClass for $name {
+                fn class() -> Option<Il2CppClass> {
+                    static CLASS: LazyClass = LazyClass::new(
+                        c"mscorlib",
+                        c_str(concat!($namespace, "\0")),
+                        c_str(concat!(stringify!($name), "\0")),
+                    );
+                    CLASS.try_get().ok()
+                }
+            }
+        )+)*};
+    }
+
+    corlib_exceptions! {
+        "System" =>
+            SystemException,
+            ArgumentException,
+            ArgumentNullException,
+            ArgumentOutOfRangeException,
+            ArithmeticException,
+            DivideByZeroException,
+            FormatException,
+            IndexOutOfRangeException,
+            InvalidCastException,
+            InvalidOperationException,
+            NotImplementedException,
+            NotSupportedException,
+            NullReferenceException,
+            ObjectDisposedException,
+            OutOfMemoryException,
+            OverflowException;
+        "System.Collections.Generic" => KeyNotFoundException;
+        "System.IO" => IOException, FileNotFoundException;
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::{catch_panic, exceptions, Exception};
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Il2CppString, InvokeError, Ref};
+    use std::ffi::CString;
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn new() {
+        mock::install();
+        let exception =
+            Exception::new(Il2CppImage::corlib(), c"System", c"ArgumentException", c"bad value")
+                .unwrap();
+        assert_eq!(exception.class().full_name(), "System.ArgumentException");
+        assert_eq!(exception.message().unwrap().to_string(), "bad value");
+        assert!(exception.is::<exceptions::ArgumentException>());
+        assert!(exception.is::<exceptions::SystemException>());
+        assert!(!exception.is::<exceptions::NullReferenceException>());
+
+        assert!(matches!(
+            Exception::new(Il2CppImage::corlib(), c"System", c"MissingException", c"x"),
+            Err(Il2CppError::ClassNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn raise_and_catch_panic() {
+        let image = mock::image(c"ExceptionTests.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Thrower")
+            .static_method(c"Raise", &[], corlib(c"Void"), |_| unsafe {
+                Exception::new(Il2CppImage::corlib(), c"System", c"ArgumentException", c"raised")
+                    .unwrap()
+                    .raise()
+            })
+            .static_method(c"Panic", &[], corlib(c"Void"), |_| unsafe {
+                catch_panic(|| panic!("panicked {}", 42))
+            })
+            .static_method(c"PanicAny", &[], corlib(c"Void"), |_| unsafe {
+                // Not a string payload, unlike the panics reported by the mock itself
+                catch_panic(|| std::panic::panic_any(42))
+            })
+            .static_method(c"Return", &[], corlib(c"Void"), |_| unsafe {
+                catch_panic(|| Ok(Ref::null()))
+            })
+            .build();
+
+        let call = |name| class.find_method(name, 0).unwrap().call::<_, ()>(Ref::null(), ());
+
+        match call(c"Raise") {
+            Err(InvokeError::Exception(exception)) => {
+                let exception = exception.target();
+                assert!(exception.is::<exceptions::ArgumentException>());
+                assert_eq!(exception.message().unwrap().to_string(), "raised");
+            }
+            other => panic!("expected a managed exception, found {other:?}"),
+        }
+        for (name, message) in [(c"Panic", "panicked 42"), (c"PanicAny", "Rust panic")] {
+            match call(name) {
+                Err(InvokeError::Exception(exception)) => {
+                    let exception = exception.target();
+                    assert_eq!(exception.class().full_name(), "System.Exception");
+                    assert_eq!(exception.message().unwrap().to_string(), message);
+                }
+                other => panic!("expected a managed exception, found {other:?}"),
+            }
+        }
+        assert!(call(c"Return").is_ok());
+    }
+
+    #[test]
+    fn accessors() {
+        let inner = mock::exception("inner");
+        let exception = mock::exception("outer");
+        assert!(exception.stack_trace().is_none());
+        assert!(exception.inner_exception().is_none());
+
+        let trace = "  at Game.Player.Jump ()\n".repeat(100);
+        unsafe {
+            let raw = exception.as_ptr();
+            (*raw).inner_ex = inner.as_ptr();
+            (*raw).hresult = 0x8013_1500_u32 as i32;
+            (*raw).stack_trace = Il2CppString::new(&CString::new(trace.clone()).unwrap())
+                .unwrap()
+                .as_ptr();
+        }
+
+        assert_eq!(exception.message().unwrap().to_string(), "outer");
+        assert_eq!(exception.stack_trace().unwrap().to_string(), trace);
+        assert_eq!(exception.inner_exception().map(Exception::as_ptr), Some(inner.as_ptr()));
+        assert_eq!(exception.hresult(), 0x8013_1500_u32 as i32);
+    }
+
+    #[test]
+    fn formatting() {
+        let exception = mock::exception("short");
+        assert_eq!(exception.format::<1024>().to_str().unwrap(), "System.Exception: short");
+        assert_eq!(exception.format::<9>().to_str().unwrap(), "System.");
+        assert_eq!(exception.format_full().to_str().unwrap(), "System.Exception: short");
+        assert_eq!(exception.format_stack_trace().unwrap().to_str().unwrap(), "");
+
+        // Longer than the first 1024 byte buffer
+        let message = "x".repeat(3000);
+        let trace = "  at Game.Player.Jump ()\n".repeat(200);
+        let exception = mock::exception(&message);
+        unsafe {
+            (*exception.as_ptr()).stack_trace =
+                Il2CppString::new(&CString::new(trace.clone()).unwrap()).unwrap().as_ptr();
+        }
+        assert_eq!(exception.format::<1024>().count_bytes(), 1022);
+        assert_eq!(
+            exception.format_full().to_str().unwrap(),
+            format!("System.Exception: {message}")
+        );
+        assert_eq!(exception.format_stack_trace().unwrap().to_str().unwrap(), trace);
+        assert_eq!(exception.to_string(), format!("System.Exception: {message}"));
+    }
+
+    #[test]
+    fn format_growing() {
+        // Output of exactly the buffer size minus one is ambiguous, the buffer has to grow
+        for len in [0, 1021, 1022, 1023, 2045, 2046, 5000] {
+            let text = "y".repeat(len);
+            let output = super::format_growing::<()>(|buffer, size| {
+                let copied = text.len().min(size as usize);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(text.as_ptr(), buffer as *mut u8, copied);
+                }
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(output.to_str().unwrap(), text);
+        }
+
+        assert_eq!(super::format_growing(|_, _| Err("failed")), Err("failed"));
     }
 }
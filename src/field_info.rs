@@ -4,11 +4,12 @@ use crate::flags::{
     FIELD_ATTRIBUTE_FIELD_ACCESS_MASK, FIELD_ATTRIBUTE_INIT_ONLY, FIELD_ATTRIBUTE_LITERAL,
     FIELD_ATTRIBUTE_STATIC,
 };
-use crate::{Il2CppClass, NonNullRef, Ref};
-use il2cpp_sys_rs::{
-    il2cpp_class_get_field_from_name, il2cpp_field_get_value, il2cpp_field_static_get_value, il2cpp_type_get_name,
-    Il2CppObject, Il2CppType,
+use crate::runtime::{
+    il2cpp_class_get_field_from_name, il2cpp_field_get_value, il2cpp_field_set_value,
+    il2cpp_field_static_get_value, il2cpp_field_static_set_value,
 };
+use crate::{Il2CppClass, Il2CppTypeHandle, NonNullRef, Ref};
+use il2cpp_sys_rs::Il2CppObject;
 use std::ffi::CStr;
 use std::{fmt, mem};
 
@@ -32,7 +33,7 @@ impl FieldInfo {
     /// Panics if the type pointer is null
     #[track_caller]
     #[inline]
-    pub const fn type_(self) -> NonNullRef<Il2CppType, ()> {
+    pub const fn type_(self) -> Il2CppTypeHandle {
         Ref::new(self.as_ref().type_ as _).unwrap_non_null()
     }
 
@@ -56,7 +57,7 @@ impl FieldInfo {
     /// Returns the raw field flags
     #[inline]
     pub fn flags(self) -> u32 {
-        self.type_().as_ref().attrs()
+        self.type_().attrs()
     }
 
     /// Field accessibility
@@ -135,6 +136,51 @@ impl FieldInfo {
         value
     }
 
+    /// Set the field value
+    ///
+    /// # Safety
+    ///
+    /// The field must be of type `T`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is static
+    ///
+    /// # Arguments
+    ///
+    /// * `this` - Class instance containing the field.
+    /// * `value` - New value
+    #[allow(unsafe_op_in_unsafe_fn)]
+    #[track_caller]
+    #[inline]
+    pub unsafe fn set_value<T>(self, this: *mut Il2CppObject, mut value: T) {
+        assert!(!self.is_static());
+
+        il2cpp_field_set_value(this as _, self.as_ptr(), &mut value as *mut _ as _);
+    }
+
+    /// Set the static field value
+    ///
+    /// # Safety
+    ///
+    /// The field must be of type `T`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not static
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - New value
+    #[allow(unsafe_op_in_unsafe_fn)]
+    #[track_caller]
+    #[inline]
+    pub unsafe fn set_static_value<T>(self, mut value: T) {
+        assert!(self.is_static());
+
+        il2cpp_field_static_set_value(self.as_ptr(), &mut value as *mut _ as _);
+    }
+
     /// Returns the field token
     #[inline]
     pub const fn token(self) -> u32 {
@@ -175,9 +221,7 @@ impl fmt::Debug for FieldInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FieldInfo")
             .field("name", &self.name().to_string_lossy())
-            .field("type", &unsafe {
-                CStr::from_ptr(il2cpp_type_get_name(self.type_().as_ptr())).to_string_lossy()
-            })
+            .field("type", &self.type_().name())
             .field("offset", &self.offset())
             .finish()
     }
@@ -0,0 +1,254 @@
+//! GcHandle
+
+use crate::runtime::{
+    il2cpp_gchandle_free, il2cpp_gchandle_get_target, il2cpp_gchandle_new,
+    il2cpp_gchandle_new_weakref,
+};
+use crate::{Il2CppError, NonNullRef};
+use std::any::type_name;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Strong GC handle
+///
+/// Keeps a managed object alive until the handle is dropped, so the object can be stored
+/// outside of managed memory, such as in a Rust struct kept between frames.
+///
+/// `T` must be a managed object type, starting with an `Il2CppObject` header.
+pub struct GcHandle<T, G = ()> {
+    handle: u32,
+    pinned: bool,
+    _marker: PhantomData<fn() -> NonNullRef<T, G>>,
+}
+
+/// Weak GC handle
+///
+/// Tracks a managed object without keeping it alive.
+///
+/// `T` must be a managed object type, starting with an `Il2CppObject` header.
+pub struct WeakGcHandle<T, G = ()> {
+    handle: u32,
+    _marker: PhantomData<fn() -> NonNullRef<T, G>>,
+}
+
+impl<T, G> GcHandle<T, G> {
+    /// Creates a strong handle to `object`
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to keep alive
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime fails to allocate the handle, see [`Self::try_new`]
+    #[track_caller]
+    #[inline]
+    pub fn new(object: NonNullRef<T, G>) -> Self {
+        Self::try_new(object).expect("failed to allocate a GC handle")
+    }
+
+    /// Creates a strong handle to `object`
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to keep alive
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime fails to allocate the handle
+    #[inline]
+    pub fn try_new(object: NonNullRef<T, G>) -> Result<Self, Il2CppError> {
+        Self::with_pinning(object, false)
+    }
+
+    /// Creates a pinned strong handle to `object`
+    ///
+    /// Pinned objects are also never moved by the GC, so their address can be handed to native code.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to keep alive
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime fails to allocate the handle, see [`Self::try_pinned`]
+    #[track_caller]
+    #[inline]
+    pub fn pinned(object: NonNullRef<T, G>) -> Self {
+        Self::try_pinned(object).expect("failed to allocate a pinned GC handle")
+    }
+
+    /// Creates a pinned strong handle to `object`
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to keep alive
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime fails to allocate the handle
+    #[inline]
+    pub fn try_pinned(object: NonNullRef<T, G>) -> Result<Self, Il2CppError> {
+        Self::with_pinning(object, true)
+    }
+
+    #[inline]
+    fn with_pinning(object: NonNullRef<T, G>, pinned: bool) -> Result<Self, Il2CppError> {
+        // The runtime returns `0` when the handle table cannot grow
+        match unsafe { il2cpp_gchandle_new(object.as_ptr() as _, pinned) } {
+            0 => Err(Il2CppError::AllocationFailed("GC handle")),
+            handle => Ok(Self {
+                handle,
+                pinned,
+                _marker: PhantomData,
+            }),
+        }
+    }
+
+    /// Returns the object held by the handle
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime does not resolve the handle
+    #[track_caller]
+    #[inline]
+    pub fn target(&self) -> NonNullRef<T, G> {
+        NonNullRef::from_ptr(unsafe { il2cpp_gchandle_get_target(self.handle) as _ })
+            .expect("strong GC handle target")
+    }
+
+    /// Returns `true` if the handle pins its object
+    #[inline]
+    pub const fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Returns the raw handle
+    #[inline]
+    pub const fn raw(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl<T, G> WeakGcHandle<T, G> {
+    /// Creates a weak handle to `object`
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to track
+    /// * `track_resurrection` - Whether the handle keeps tracking the object during finalization
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime fails to allocate the handle, see [`Self::try_new`]
+    #[track_caller]
+    #[inline]
+    pub fn new(object: NonNullRef<T, G>, track_resurrection: bool) -> Self {
+        Self::try_new(object, track_resurrection).expect("failed to allocate a weak GC handle")
+    }
+
+    /// Creates a weak handle to `object`
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Managed object to track
+    /// * `track_resurrection` - Whether the handle keeps tracking the object during finalization
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime fails to allocate the handle
+    pub fn try_new(
+        object: NonNullRef<T, G>,
+        track_resurrection: bool,
+    ) -> Result<Self, Il2CppError> {
+        match unsafe { il2cpp_gchandle_new_weakref(object.as_ptr() as _, track_resurrection) } {
+            0 => Err(Il2CppError::AllocationFailed("weak GC handle")),
+            handle => Ok(Self {
+                handle,
+                _marker: PhantomData,
+            }),
+        }
+    }
+
+    /// Returns the tracked object, if it was not collected
+    #[inline]
+    pub fn target(&self) -> Option<NonNullRef<T, G>> {
+        NonNullRef::from_ptr(unsafe { il2cpp_gchandle_get_target(self.handle) as _ })
+    }
+
+    /// Creates a strong handle to the tracked object, if it was not collected
+    ///
+    /// Also returns `None` if the runtime fails to allocate the strong handle.
+    #[inline]
+    pub fn upgrade(&self) -> Option<GcHandle<T, G>> {
+        self.target().and_then(|target| GcHandle::try_new(target).ok())
+    }
+
+    /// Returns the raw handle
+    #[inline]
+    pub const fn raw(&self) -> u32 {
+        self.handle
+    }
+}
+
+impl<T, G> Drop for GcHandle<T, G> {
+    fn drop(&mut self) {
+        unsafe { il2cpp_gchandle_free(self.handle) }
+    }
+}
+
+impl<T, G> Drop for WeakGcHandle<T, G> {
+    fn drop(&mut self) {
+        unsafe { il2cpp_gchandle_free(self.handle) }
+    }
+}
+
+// Safety: handles are plain integers and the runtime handle table is thread-safe
+unsafe impl<T, G> Send for GcHandle<T, G> {}
+unsafe impl<T, G> Sync for GcHandle<T, G> {}
+unsafe impl<T, G> Send for WeakGcHandle<T, G> {}
+unsafe impl<T, G> Sync for WeakGcHandle<T, G> {}
+
+impl<T, G> fmt::Debug for GcHandle<T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("handle", &self.handle)
+            .field("pinned", &self.pinned)
+            .finish()
+    }
+}
+
+impl<T, G> fmt::Debug for WeakGcHandle<T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("handle", &self.handle)
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock;
+    use crate::{GcHandle, Il2CppString, WeakGcHandle};
+
+    #[test]
+    fn strong_and_weak() {
+        mock::install();
+        let string = Il2CppString::new(c"GcHandleTests").unwrap();
+        let weak = WeakGcHandle::new(string, false);
+        {
+            let strong = GcHandle::new(string);
+            assert_ne!(strong.raw(), 0);
+            mock::collect();
+            assert_eq!(weak.target().unwrap().as_ptr(), strong.target().as_ptr());
+            assert!(weak.upgrade().is_some());
+        }
+        mock::collect();
+        assert!(weak.target().is_none());
+        assert!(weak.upgrade().is_none());
+
+        let pinned = GcHandle::try_pinned(string).unwrap();
+        assert!(pinned.is_pinned());
+        assert_eq!(pinned.target().as_ptr(), string.as_ptr());
+    }
+}
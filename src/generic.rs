@@ -0,0 +1,324 @@
+//! Generic instantiation
+
+use crate::runtime::{
+    il2cpp_method_get_from_reflection, il2cpp_method_get_object, il2cpp_method_is_generic,
+    il2cpp_method_is_inflated,
+};
+use crate::{
+    Il2CppClass, Il2CppError, Il2CppImage, Il2CppObject, Il2CppSzArray, Il2CppTypeHandle,
+    InvokeError, MethodInfo,
+};
+use il2cpp_sys_rs::Il2CppGenericContainer;
+use std::error::Error;
+use std::{fmt, ptr};
+
+/// Error raised while instantiating a generic definition
+#[derive(Debug)]
+pub enum GenericError {
+    /// The class or method is not a generic definition
+    NotGenericDefinition,
+    /// The reflection call failed, such as when the type arguments do not satisfy the constraints
+    Invoke(InvokeError),
+    /// A reflection member is missing or the runtime failed to allocate an object
+    Runtime(Il2CppError),
+    /// The inflated method has no compiled code
+    ///
+    /// IL2CPP only compiles the instantiations used by the game, and shares the code of
+    /// reference type arguments.
+    MissingCode,
+}
+
+impl fmt::Display for GenericError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotGenericDefinition => f.write_str("not a generic definition"),
+            Self::Invoke(err) => write!(f, "generic instantiation failed: {err}"),
+            Self::Runtime(err) => write!(f, "generic instantiation failed: {err}"),
+            Self::MissingCode => f.write_str("no code was generated for the generic instantiation"),
+        }
+    }
+}
+
+impl Error for GenericError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NotGenericDefinition | Self::MissingCode => None,
+            Self::Invoke(err) => Some(err),
+            Self::Runtime(err) => Some(err),
+        }
+    }
+}
+
+impl From<InvokeError> for GenericError {
+    fn from(err: InvokeError) -> Self {
+        Self::Invoke(err)
+    }
+}
+
+impl From<Il2CppError> for GenericError {
+    fn from(err: Il2CppError) -> Self {
+        Self::Runtime(err)
+    }
+}
+
+impl Il2CppClass {
+    /// Instantiates a generic definition with type arguments
+    ///
+    /// Goes through `System.Type.MakeGenericType`, so the runtime creates the inflated class
+    /// if it was not used by the game.
+    ///
+    /// # Arguments
+    ///
+    /// * `arguments` - Type arguments, in declaration order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenericError::NotGenericDefinition`] if the class is not a generic definition,
+    /// [`GenericError::Invoke`] if the runtime rejects the type arguments,
+    /// or [`GenericError::Runtime`] if `System.Type.MakeGenericType` or a runtime export is
+    /// missing, or an allocation fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::Il2CppImage;
+    /// let corlib = Il2CppImage::corlib();
+    /// let list = corlib.find_class(c"System.Collections.Generic", c"List`1").unwrap();
+    /// let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+    ///
+    /// // System.Collections.Generic.List<System.Int32>
+    /// let list_int32 = list.make_generic(&[int32]).unwrap();
+    /// ```
+    pub fn make_generic(self, arguments: &[Il2CppClass]) -> Result<Il2CppClass, GenericError> {
+        if !self.is_generic() {
+            return Err(GenericError::NotGenericDefinition);
+        }
+
+        let definition = self.type_().system_type()?;
+        let make_generic_type = definition.class().find_method(c"MakeGenericType", 1)?;
+        let inflated = make_generic_type
+            .call::<_, Il2CppObject>(definition.into(), (system_types(arguments)?,))?;
+
+        Ok(Il2CppClass::from_system_type(inflated)?)
+    }
+
+    /// Returns the type arguments of an inflated generic class
+    ///
+    /// Empty if the class is not inflated.
+    #[inline]
+    pub fn generic_arguments(self) -> &'static [Il2CppTypeHandle] {
+        self.type_().generic_arguments()
+    }
+
+    /// Returns the generic definition of an inflated generic class
+    ///
+    /// # Returns
+    ///
+    /// Open generic class, such as ``List`1`` for `List<int>`, if the class is inflated,
+    /// otherwise `None`
+    pub fn generic_definition(self) -> Option<Il2CppClass> {
+        let generic_class = unsafe { self.as_ref().generic_class.as_ref()? };
+        Il2CppTypeHandle::from_ptr(generic_class.type_ as _)?.class()
+    }
+}
+
+impl MethodInfo {
+    /// Returns `true` if the method is a generic method definition, such as `T Get<T>()`
+    #[inline]
+    pub fn is_generic(self) -> bool {
+        unsafe { il2cpp_method_is_generic(self.as_ptr()) }
+    }
+
+    /// Returns `true` if the method is inflated, either generic itself or declared by an
+    /// inflated class
+    #[inline]
+    pub fn is_inflated(self) -> bool {
+        unsafe { il2cpp_method_is_inflated(self.as_ptr()) }
+    }
+
+    /// Returns the number of type parameters of a generic method
+    ///
+    /// Type parameters of the declaring class are not counted.
+    ///
+    /// # Returns
+    ///
+    /// Number of type parameters of a generic method definition, or number of type arguments
+    /// of an inflated generic method, otherwise `0`
+    pub fn generic_parameter_count(self) -> usize {
+        let method = self.as_ref();
+        unsafe {
+            if self.is_inflated() {
+                method
+                    .__bindgen_anon_2
+                    .genericMethod
+                    .as_ref()
+                    .and_then(|generic_method| generic_method.context.method_inst.as_ref())
+                    .map_or(0, |inst| inst.type_argc as usize)
+            } else if self.is_generic() {
+                let container = method.__bindgen_anon_2.genericContainerHandle;
+                (container as *const Il2CppGenericContainer)
+                    .as_ref()
+                    .map_or(0, |container| container.type_argc as usize)
+            } else {
+                0
+            }
+        }
+    }
+
+    /// Instantiates a generic method definition with type arguments
+    ///
+    /// Goes through `System.Reflection.MethodInfo.MakeGenericMethod`, the inflated method can be
+    /// invoked like any other method.
+    ///
+    /// # Arguments
+    ///
+    /// * `arguments` - Type arguments, in declaration order
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GenericError::NotGenericDefinition`] if the method is not a generic definition,
+    /// [`GenericError::Invoke`] if the runtime rejects the type arguments,
+    /// [`GenericError::MissingCode`] if the instantiation was not compiled,
+    /// or [`GenericError::Runtime`] if `System.Reflection.MethodInfo.MakeGenericMethod` or a
+    /// runtime export is missing, or an allocation fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{Il2CppImage, Ref};
+    /// let corlib = Il2CppImage::corlib();
+    /// let array = corlib.find_class(c"System", c"Array").unwrap();
+    /// let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+    ///
+    /// // System.Array.Empty<System.Int32>()
+    /// let empty = array.find_method(c"Empty", 0).unwrap();
+    /// let empty_int32 = empty.make_generic(&[int32]).unwrap();
+    /// let result = empty_int32.invoke::<()>(Ref::null(), &mut []).unwrap();
+    /// ```
+    pub fn make_generic(self, arguments: &[Il2CppClass]) -> Result<MethodInfo, GenericError> {
+        if !self.is_generic() {
+            return Err(GenericError::NotGenericDefinition);
+        }
+
+        let definition = unsafe { il2cpp_method_get_object(self.as_ptr(), ptr::null_mut()) }?;
+        let definition = Il2CppObject::from_ptr(definition as _)
+            .ok_or(Il2CppError::AllocationFailed("System.Reflection.MethodInfo object"))?;
+        let make_generic_method = definition.class().find_method(c"MakeGenericMethod", 1)?;
+        let inflated = make_generic_method
+            .call::<_, Il2CppObject>(definition.into(), (system_types(arguments)?,))?;
+
+        let method = unsafe { il2cpp_method_get_from_reflection(inflated.as_ptr() as _) }?;
+        let method = MethodInfo::from_ptr(method as _).ok_or_else(|| Il2CppError::TypeMismatch {
+            expected: "System.Reflection.MethodInfo".into(),
+            found: inflated.class().full_name(),
+        })?;
+        if method.as_ref().methodPointer.is_none() {
+            return Err(GenericError::MissingCode);
+        }
+        Ok(method)
+    }
+}
+
+/// Creates a `System.Type[]` holding the type objects of `classes`
+///
+/// # Errors
+///
+/// Returns [`Il2CppError::ClassNotFound`] if `System.Type` is missing,
+/// [`Il2CppError::MissingExport`] if the runtime does not export `il2cpp_type_get_object`,
+/// or [`Il2CppError::AllocationFailed`] if an allocation fails
+pub(crate) fn system_types(
+    classes: &[Il2CppClass],
+) -> Result<Il2CppSzArray<*mut il2cpp_sys_rs::Il2CppObject>, Il2CppError> {
+    let type_class = Il2CppImage::corlib().find_class(c"System", c"Type")?;
+    let array = Il2CppSzArray::new(type_class, classes.len())?;
+    for (index, class) in classes.iter().enumerate() {
+        unsafe { array.set_object(index, class.type_().system_type()?.as_ptr()) };
+    }
+    Ok(array)
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{GenericError, Il2CppImage, Il2CppObject, InvokeError, Ref};
+
+    #[test]
+    fn make_generic_class() {
+        let image = mock::image(c"GenericTests.dll");
+        let corlib = Il2CppImage::corlib();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let string = corlib.find_class(c"System", c"String").unwrap();
+        let list = ClassBuilder::new(image, c"Game", c"List`1").generic(1).build();
+        let dictionary = ClassBuilder::new(image, c"Game", c"Dictionary`2").generic(2).build();
+
+        let list_int32 = list.make_generic(&[int32]).unwrap();
+        assert!(list_int32.is_inflated());
+        assert!(!list_int32.is_generic());
+        assert_eq!(list_int32.generic_definition().unwrap().as_ptr(), list.as_ptr());
+        assert_eq!(list_int32.generic_arguments(), &[int32.type_()]);
+        assert_eq!(list_int32.type_().name(), "Game.List<System.Int32>");
+        // Instantiations are cached by the runtime
+        assert_eq!(list.make_generic(&[int32]).unwrap().as_ptr(), list_int32.as_ptr());
+
+        let nested = dictionary.make_generic(&[string, list_int32]).unwrap();
+        assert_eq!(
+            nested.type_().name(),
+            "Game.Dictionary<System.String,Game.List<System.Int32>>"
+        );
+    }
+
+    #[test]
+    fn make_generic_class_errors() {
+        let image = mock::image(c"GenericTestsErrors.dll");
+        let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+        let dictionary = ClassBuilder::new(image, c"Game", c"Dictionary`2").generic(2).build();
+
+        assert!(matches!(
+            dictionary.make_generic(&[int32]),
+            Err(GenericError::Invoke(InvokeError::Exception(_)))
+        ));
+        assert!(matches!(
+            int32.make_generic(&[int32]),
+            Err(GenericError::NotGenericDefinition)
+        ));
+        assert!(int32.generic_definition().is_none());
+    }
+
+    #[test]
+    fn make_generic_method() {
+        let image = mock::image(c"GenericMethodTests.dll");
+        let corlib = Il2CppImage::corlib();
+        let int32 = corlib.find_class(c"System", c"Int32").unwrap();
+        let object = corlib.find_class(c"System", c"Object").unwrap();
+        let class = ClassBuilder::new(image, c"Game", c"Util")
+            .generic_static_method(c"Id", 1, &[(c"value", object)], object, |call| {
+                Ok(call.arg_object(0))
+            })
+            .static_method(c"Plain", &[], int32, |_| Ok(Ref::null()))
+            .build();
+
+        let id = class.find_method(c"Id", 1).unwrap();
+        assert!(id.is_generic());
+        assert_eq!(id.generic_parameter_count(), 1);
+
+        let id_int32 = id.make_generic(&[int32]).unwrap();
+        assert!(id_int32.is_inflated());
+        assert!(!id_int32.is_generic());
+        assert_eq!(id_int32.generic_parameter_count(), 1);
+        assert_eq!(id.make_generic(&[int32]).unwrap().as_ptr(), id_int32.as_ptr());
+
+        let boxed = Il2CppObject::box_value(int32, &7_i32).unwrap();
+        let result: Il2CppObject = id_int32.call(Ref::null(), (boxed,)).unwrap();
+        assert_eq!(unsafe { result.unbox::<i32>() }, 7);
+
+        let plain = class.find_method(c"Plain", 0).unwrap();
+        assert!(matches!(
+            plain.make_generic(&[int32]),
+            Err(GenericError::NotGenericDefinition)
+        ));
+        assert!(matches!(
+            id.make_generic(&[int32, int32]),
+            Err(GenericError::Invoke(_))
+        ));
+    }
+}
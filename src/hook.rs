@@ -0,0 +1,459 @@
+//! Hook
+
+use crate::{MethodInfo, NativeFunction};
+use iced_x86::{
+    BlockEncoder, BlockEncoderOptions, Decoder, DecoderError, DecoderOptions, FlowControl,
+    Instruction, InstructionBlock,
+};
+use region::Protection;
+use std::error::Error;
+use std::mem::ManuallyDrop;
+use std::{fmt, ptr, slice};
+
+/// `jmp rel32`
+const JMP_REL32_LEN: usize = 5;
+/// `jmp [rip+0]` followed by the absolute address
+const JMP_ABS_LEN: usize = 14;
+/// Longest possible x86 instruction
+const MAX_INSTRUCTION_LEN: usize = 15;
+/// Size of the memory block holding the relay and the trampoline of a hook
+const BLOCK_SIZE: usize = 0x1000;
+/// Offset of the trampoline in its block, after the relay
+const TRAMPOLINE_OFFSET: usize = 0x10;
+/// Maximum distance reachable by a `rel32` displacement, with some margin for the block size
+const REL32_RANGE: usize = 0x7fff_0000;
+/// Step between the addresses tried when allocating a block near a target
+const ALLOCATION_STEP: usize = 0x1_0000;
+
+/// Error raised while installing a hook
+#[derive(Debug)]
+pub enum HookError {
+    /// The method has no compiled code
+    MissingCode,
+    /// The function starts with an instruction that cannot be decoded
+    InvalidInstruction(usize),
+    /// The function returns or jumps away before the patched bytes
+    TooShort,
+    /// The overwritten instructions cannot be moved to the trampoline
+    Relocation(String),
+    /// No free memory was found within reach of the function
+    Allocation,
+    /// The function code could not be made writable
+    Protection(region::Error),
+}
+
+impl fmt::Display for HookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCode => f.write_str("the method has no compiled code"),
+            Self::InvalidInstruction(address) => {
+                write!(f, "invalid instruction at {address:#x}")
+            }
+            Self::TooShort => f.write_str("the function is too short to be hooked"),
+            Self::Relocation(reason) => {
+                write!(f, "failed to relocate the function entry: {reason}")
+            }
+            Self::Allocation => f.write_str("no free memory within reach of the function"),
+            Self::Protection(err) => write!(f, "failed to change the memory protection: {err}"),
+        }
+    }
+}
+
+impl Error for HookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Protection(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Installed inline hook
+///
+/// The entry of the target function jumps to the replacement until the handle is dropped,
+/// which restores the original bytes. Dropping cannot report a failure to restore them,
+/// use [`HookHandle::unhook`] to handle it.
+///
+/// The relay and trampoline memory is never freed, as other threads may still be running
+/// through it after the hook is removed.
+pub struct HookHandle<F: NativeFunction> {
+    target: usize,
+    original: F,
+    /// Overwritten bytes of the function entry
+    saved: [u8; JMP_REL32_LEN + MAX_INSTRUCTION_LEN],
+    saved_len: usize,
+}
+
+impl<F: NativeFunction> HookHandle<F> {
+    /// Detours a native function to `replacement`
+    ///
+    /// The first instructions of `target` are overwritten by a jump to `replacement`, and
+    /// moved to a trampoline returned by [`HookHandle::original`].
+    ///
+    /// # Safety
+    ///
+    /// - `target` and `replacement` must be functions of the signature `F`
+    /// - No thread may be executing the first instructions of `target` while it is patched
+    /// - Code must not branch back into the first instructions of `target`
+    /// - Hooks of the same function must be dropped in the reverse order of their creation
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Function to hook
+    /// * `replacement` - Function called instead of `target`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`HookError`] if the function entry cannot be patched
+    pub unsafe fn new(target: F, replacement: F) -> Result<Self, HookError> {
+        let target = target.address() as usize;
+
+        // Instructions overwritten by the jump, decoded one at a time so that no byte past
+        // them is read, the function may end right before an unmapped page
+        let mut instructions: Vec<Instruction> = Vec::new();
+        let mut patched_len = 0;
+        while patched_len < JMP_REL32_LEN {
+            if let Some(last) = instructions.last()
+                && matches!(
+                    last.flow_control(),
+                    FlowControl::Return
+                        | FlowControl::UnconditionalBranch
+                        | FlowControl::IndirectBranch
+                        | FlowControl::Interrupt
+                        | FlowControl::Exception
+                )
+            {
+                return Err(HookError::TooShort);
+            }
+
+            let instruction = unsafe { decode_at(target + patched_len)? };
+            patched_len += instruction.len();
+            instructions.push(instruction);
+        }
+
+        let block = alloc_near(target).ok_or(HookError::Allocation)?;
+        let trampoline = block + TRAMPOLINE_OFFSET;
+
+        // Trampoline, the relocated instructions followed by a jump back to the function
+        let mut trampoline_code = BlockEncoder::encode(
+            64,
+            InstructionBlock::new(&instructions, trampoline as u64),
+            BlockEncoderOptions::NONE,
+        )
+        .map_err(|err| HookError::Relocation(err.to_string()))?
+        .code_buffer;
+        trampoline_code.extend_from_slice(&jmp_abs(target + patched_len));
+        if TRAMPOLINE_OFFSET + trampoline_code.len() > BLOCK_SIZE {
+            return Err(HookError::Relocation("trampoline too large".into()));
+        }
+
+        unsafe {
+            // Relay, reachable from the function entry with a `rel32` jump
+            ptr::copy_nonoverlapping(
+                jmp_abs(replacement.address() as usize).as_ptr(),
+                block as *mut u8,
+                JMP_ABS_LEN,
+            );
+            ptr::copy_nonoverlapping(
+                trampoline_code.as_ptr(),
+                trampoline as *mut u8,
+                trampoline_code.len(),
+            );
+        }
+
+        let mut saved = [0; JMP_REL32_LEN + MAX_INSTRUCTION_LEN];
+        saved[..patched_len]
+            .copy_from_slice(unsafe { slice::from_raw_parts(target as *const u8, patched_len) });
+
+        // Function entry, jumps to the relay and pads the leftover bytes with `int3`
+        let mut entry = vec![0xcc; patched_len];
+        entry[0] = 0xe9;
+        let displacement = block as i64 - (target + JMP_REL32_LEN) as i64;
+        entry[1..JMP_REL32_LEN].copy_from_slice(&(displacement as i32).to_le_bytes());
+        unsafe { write_code(target, &entry)? };
+
+        Ok(Self {
+            target,
+            original: unsafe { F::from_address(trampoline as _) },
+            saved,
+            saved_len: patched_len,
+        })
+    }
+
+    /// Returns the trampoline calling the original function
+    #[inline]
+    pub fn original(&self) -> F {
+        self.original
+    }
+
+    /// Returns the address of the hooked function
+    #[inline]
+    pub fn target(&self) -> *const () {
+        self.target as _
+    }
+
+    /// Removes the hook, restoring the original bytes of the function entry
+    ///
+    /// Same as dropping the handle, but reports a failure to restore the bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HookError::Protection`] if the function code could not be made writable,
+    /// the function then stays hooked
+    pub fn unhook(self) -> Result<(), HookError> {
+        let this = ManuallyDrop::new(self);
+        unsafe { this.restore() }
+    }
+
+    /// Restores the original bytes of the function entry
+    ///
+    /// # Safety
+    ///
+    /// No thread may be executing the first instructions of the function
+    unsafe fn restore(&self) -> Result<(), HookError> {
+        unsafe { write_code(self.target, &self.saved[..self.saved_len]) }
+    }
+}
+
+impl<F: NativeFunction> Drop for HookHandle<F> {
+    fn drop(&mut self) {
+        // Best effort, panicking here would abort during unwinding, see `unhook`
+        let _ = unsafe { self.restore() };
+    }
+}
+
+// Safety: the handle only holds addresses and function pointers
+unsafe impl<F: NativeFunction> Send for HookHandle<F> {}
+unsafe impl<F: NativeFunction> Sync for HookHandle<F> {}
+
+impl<F: NativeFunction> fmt::Debug for HookHandle<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookHandle")
+            .field("target", &self.target())
+            .field("original", &self.original.address())
+            .finish()
+    }
+}
+
+impl MethodInfo {
+    /// Detours the native code of the method to `replacement`
+    ///
+    /// Native method signatures take the instance first for instance methods, then the
+    /// arguments, then the `MethodInfo` pointer.
+    /// Methods sharing their code, such as generic instantiations over reference types,
+    /// are hooked together.
+    ///
+    /// # Safety
+    ///
+    /// See [`HookHandle::new`], `F` must be the native signature of the method
+    ///
+    /// # Arguments
+    ///
+    /// * `replacement` - Function called instead of the method
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HookError::MissingCode`] if the method has no compiled code, or another
+    /// [`HookError`] if its entry cannot be patched
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{HookHandle, Il2CppImage, MethodInfo};
+    /// # use il2cpp_rs::sys::MethodInfo as RawMethodInfo;
+    /// # use std::sync::OnceLock;
+    /// type Abs = extern "C" fn(i32, *const RawMethodInfo) -> i32;
+    ///
+    /// static HOOK: OnceLock<HookHandle<Abs>> = OnceLock::new();
+    ///
+    /// extern "C" fn abs(value: i32, method: *const RawMethodInfo) -> i32 {
+    ///     HOOK.get().unwrap().original()(value, method) + 1
+    /// }
+    ///
+    /// let class = Il2CppImage::corlib().find_class(c"System", c"Math").unwrap();
+    /// let method = class.find_method_exact(c"Abs", &["System.Int32"]).unwrap();
+    /// let hook = unsafe { method.hook(abs as Abs) }.unwrap();
+    /// HOOK.set(hook).unwrap();
+    /// ```
+    pub unsafe fn hook<F: NativeFunction>(
+        self,
+        replacement: F,
+    ) -> Result<HookHandle<F>, HookError> {
+        let target = self.as_ref().methodPointer.ok_or(HookError::MissingCode)?;
+        unsafe { HookHandle::new(F::from_address(target as _), replacement) }
+    }
+}
+
+/// Decodes the instruction at `address`, reading no byte past its end
+///
+/// # Safety
+///
+/// `address` must be mapped code
+unsafe fn decode_at(address: usize) -> Result<Instruction, HookError> {
+    for len in 1..=MAX_INSTRUCTION_LEN {
+        let code = unsafe { slice::from_raw_parts(address as *const u8, len) };
+        let mut decoder = Decoder::with_ip(64, code, address as u64, DecoderOptions::NONE);
+        let instruction = decoder.decode();
+        match decoder.last_error() {
+            DecoderError::None => return Ok(instruction),
+            // The instruction is longer, read one more byte
+            DecoderError::NoMoreBytes => {}
+            _ => break,
+        }
+    }
+    Err(HookError::InvalidInstruction(address))
+}
+
+/// Encodes `jmp [rip+0]` to `address`
+fn jmp_abs(address: usize) -> [u8; JMP_ABS_LEN] {
+    let mut code = [0xff, 0x25, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    code[6..].copy_from_slice(&(address as u64).to_le_bytes());
+    code
+}
+
+/// Overwrites code at `address`
+///
+/// # Safety
+///
+/// `address` must be mapped code, not executed while it is written
+unsafe fn write_code(address: usize, code: &[u8]) -> Result<(), HookError> {
+    unsafe {
+        let _guard = region::protect_with_handle(
+            address as *const u8,
+            code.len(),
+            Protection::READ_WRITE_EXECUTE,
+        )
+        .map_err(HookError::Protection)?;
+        ptr::copy_nonoverlapping(code.as_ptr(), address as *mut u8, code.len());
+    }
+    Ok(())
+}
+
+/// Allocates an executable block within `rel32` reach of `target`
+///
+/// Tries addresses at increasing distance below, then above the target.
+fn alloc_near(target: usize) -> Option<usize> {
+    let target = target & !(ALLOCATION_STEP - 1);
+    (1..REL32_RANGE / ALLOCATION_STEP)
+        .flat_map(|step| {
+            let distance = step * ALLOCATION_STEP;
+            [target.checked_sub(distance), target.checked_add(distance)]
+        })
+        .flatten()
+        .find_map(|address| alloc_at(address, target))
+}
+
+/// Allocates an executable block at `address`
+///
+/// The kernel may map the block elsewhere, which is rejected if out of reach of `target`.
+#[cfg(unix)]
+fn alloc_at(address: usize, target: usize) -> Option<usize> {
+    unsafe {
+        let block = libc::mmap(
+            address as _,
+            BLOCK_SIZE,
+            libc::PROT_READ | libc::PROT_WRITE | libc::PROT_EXEC,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        );
+        if block == libc::MAP_FAILED {
+            return None;
+        }
+        if (block as usize).abs_diff(target) < REL32_RANGE {
+            Some(block as usize)
+        } else {
+            libc::munmap(block, BLOCK_SIZE);
+            None
+        }
+    }
+}
+
+/// Allocates an executable block at `address`, fails if the address is in use
+#[cfg(windows)]
+fn alloc_at(address: usize, _target: usize) -> Option<usize> {
+    let allocation =
+        region::alloc_at(address as *const u8, BLOCK_SIZE, Protection::READ_WRITE_EXECUTE).ok()?;
+    let block = allocation.as_ptr::<u8>() as usize;
+    // Trampolines are never freed
+    std::mem::forget(allocation);
+    Some(block)
+}
+
+#[cfg(all(test, target_os = "linux", target_arch = "x86_64"))]
+mod tests {
+    use super::HookHandle;
+    use crate::NativeFunction;
+    use std::hint::black_box;
+    use std::slice;
+    use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+
+    type Add = extern "C" fn(i32, i32) -> i32;
+    type Read = extern "C" fn() -> i32;
+
+    #[inline(never)]
+    extern "C" fn add(a: i32, b: i32) -> i32 {
+        black_box(a).wrapping_add(black_box(b))
+    }
+
+    /// Trampoline of the `add` hook
+    static ADD_ORIGINAL: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn add_replacement(a: i32, b: i32) -> i32 {
+        let original = unsafe { Add::from_address(ADD_ORIGINAL.load(Ordering::SeqCst) as _) };
+        original(a, b) * 10
+    }
+
+    static COUNTER: AtomicI32 = AtomicI32::new(5);
+
+    #[inline(never)]
+    extern "C" fn read_counter() -> i32 {
+        COUNTER.load(Ordering::SeqCst)
+    }
+
+    extern "C" fn read_replacement() -> i32 {
+        -1
+    }
+
+    /// Returns the first bytes of `function`
+    fn entry(function: impl NativeFunction) -> Vec<u8> {
+        unsafe { slice::from_raw_parts(function.address() as *const u8, 16) }.to_vec()
+    }
+
+    #[test]
+    fn hook_and_restore() {
+        // Called through a pointer so that the calls are not inlined
+        let call: Add = black_box(add);
+        let original_entry = entry(add as Add);
+        assert_eq!(call(2, 3), 5);
+
+        let hook = unsafe { HookHandle::new(add as Add, add_replacement as Add) }.unwrap();
+        ADD_ORIGINAL.store(hook.original().address() as usize, Ordering::SeqCst);
+        assert_ne!(entry(add as Add), original_entry);
+
+        // The replacement is called, and reaches the old body through the trampoline
+        assert_eq!(call(2, 3), 50);
+        assert_eq!(hook.original()(2, 3), 5);
+
+        drop(hook);
+        assert_eq!(entry(add as Add), original_entry);
+        assert_eq!(call(2, 3), 5);
+    }
+
+    #[test]
+    fn unhook() {
+        let call: Read = black_box(read_counter);
+        let original_entry = entry(read_counter as Read);
+
+        let hook =
+            unsafe { HookHandle::new(read_counter as Read, read_replacement as Read) }.unwrap();
+        assert_eq!(call(), -1);
+        // The trampoline runs the relocated instructions, reading the current value
+        COUNTER.store(7, Ordering::SeqCst);
+        assert_eq!(hook.original()(), 7);
+
+        hook.unhook().unwrap();
+        assert_eq!(entry(read_counter as Read), original_entry);
+        assert_eq!(call(), 7);
+    }
+}
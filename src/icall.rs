@@ -0,0 +1,169 @@
+//! Internal calls
+//!
+//! `[MethodImpl(MethodImplOptions.InternalCall)]` methods are implemented by native functions
+//! registered by name, such as `UnityEngine.Time::get_deltaTime`.
+//!
+//! Internal calls have no trailing `MethodInfo` parameter: instance methods take the instance
+//! first, then the arguments.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use il2cpp_rs::icall;
+//!
+//! type GetDeltaTime = extern "C" fn() -> f32;
+//!
+//! extern "C" fn get_delta_time() -> f32 {
+//!     1.0 / 60.0
+//! }
+//!
+//! unsafe {
+//!     let original = icall::resolve::<GetDeltaTime>(c"UnityEngine.Time::get_deltaTime")?;
+//!     icall::register(c"UnityEngine.Time::get_deltaTime", get_delta_time as GetDeltaTime)?;
+//! }
+//! # Ok::<(), il2cpp_rs::Il2CppError>(())
+//! ```
+
+use crate::runtime::{il2cpp_add_internal_call, il2cpp_resolve_icall};
+use crate::{Il2CppClass, Il2CppError, MethodInfo, NativeFunction};
+use std::ffi::{CStr, CString};
+
+/// Registers the native implementation of an internal call
+///
+/// The runtime resolves an internal call on its first use, so the implementation must be
+/// registered before the method is first called.
+///
+/// # Safety
+///
+/// `function` must match the native signature of the internal call
+///
+/// # Arguments
+///
+/// * `name` - Internal call name, see [`MethodInfo::icall_name`]
+/// * `function` - Native implementation
+///
+/// # Errors
+///
+/// Returns [`Il2CppError::MissingExport`] if the runtime does not export
+/// `il2cpp_add_internal_call`
+#[inline]
+pub unsafe fn register<F: NativeFunction>(name: &CStr, function: F) -> Result<(), Il2CppError> {
+    unsafe {
+        let function = <unsafe extern "C" fn()>::from_address(function.address());
+        il2cpp_add_internal_call(name.as_ptr(), Some(function))
+    }
+}
+
+/// Resolves the native implementation of an internal call
+///
+/// Names with a signature, such as `UnityEngine.Transform::SetParent(UnityEngine.Transform)`,
+/// fall back to the implementation registered without one.
+///
+/// # Safety
+///
+/// `F` must match the native signature of the internal call
+///
+/// # Arguments
+///
+/// * `name` - Internal call name, see [`MethodInfo::icall_name`]
+///
+/// # Returns
+///
+/// Native implementation if one is registered, otherwise `None`
+///
+/// # Errors
+///
+/// Returns [`Il2CppError::MissingExport`] if the runtime does not export `il2cpp_resolve_icall`
+#[inline]
+pub unsafe fn resolve<F: NativeFunction>(name: &CStr) -> Result<Option<F>, Il2CppError> {
+    let function = unsafe { il2cpp_resolve_icall(name.as_ptr()) }?;
+    Ok(function.map(|function| unsafe { F::from_address(function as *const ()) }))
+}
+
+impl MethodInfo {
+    /// Returns the internal call name of the method, such as `UnityEngine.Time::get_deltaTime`
+    ///
+    /// Nested classes are separated from their declaring class by `/`.
+    pub fn icall_name(self) -> CString {
+        let name = format!(
+            "{}::{}",
+            self.declaring_type().qualified_name('/'),
+            self.name().to_string_lossy()
+        );
+        CString::new(name).expect("internal call name")
+    }
+
+    /// Returns the internal call name of the method with its parameter types,
+    /// such as `UnityEngine.Transform::SetParent(UnityEngine.Transform,System.Boolean)`
+    ///
+    /// Distinguishes overloads registered under the same name.
+    pub fn icall_signature(self) -> CString {
+        let parameters: Vec<_> = self.parameters().iter().map(|type_| type_.name()).collect();
+        let mut name = self.icall_name().into_bytes();
+        name.extend_from_slice(format!("({})", parameters.join(",")).as_bytes());
+        CString::new(name).expect("internal call signature")
+    }
+}
+
+impl Il2CppClass {
+    /// Returns the methods of the class implemented by internal calls
+    ///
+    /// Inherited methods are not included.
+    pub fn internal_calls(self) -> impl Iterator<Item = MethodInfo> {
+        self.methods()
+            .iter()
+            .copied()
+            .filter(|method| method.is_internal_call())
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{icall, Il2CppClass, Il2CppImage};
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    type Scale = extern "C" fn(i32, f32) -> f32;
+
+    extern "C" fn scale(value: i32, factor: f32) -> f32 {
+        value as f32 * factor
+    }
+
+    #[test]
+    fn register_and_resolve() {
+        let image = mock::image(c"IcallTests.dll");
+        let outer = ClassBuilder::new(image, c"Game", c"Physics").build();
+        let class = ClassBuilder::new(image, c"", c"Solver")
+            .nested_in(outer)
+            .internal_call(
+                c"Scale",
+                &[(c"value", corlib(c"Int32")), (c"factor", corlib(c"Single"))],
+                corlib(c"Single"),
+            )
+            .static_method(c"Managed", &[], corlib(c"Void"), |_| Ok(crate::Ref::null()))
+            .build();
+
+        let methods: Vec<_> = class.internal_calls().collect();
+        assert_eq!(methods.len(), 1);
+        let method = methods[0];
+        assert_eq!(method.icall_name().to_str().unwrap(), "Game.Physics/Solver::Scale");
+        assert_eq!(
+            method.icall_signature().to_str().unwrap(),
+            "Game.Physics/Solver::Scale(System.Int32,System.Single)"
+        );
+
+        unsafe {
+            assert!(icall::resolve::<Scale>(&method.icall_name()).unwrap().is_none());
+            icall::register(&method.icall_name(), scale as Scale).unwrap();
+
+            let resolved = icall::resolve::<Scale>(&method.icall_name()).unwrap().unwrap();
+            assert_eq!(resolved(3, 1.5), 4.5);
+            // The signature falls back to the name registered without one
+            let resolved = icall::resolve::<Scale>(&method.icall_signature()).unwrap().unwrap();
+            assert_eq!(resolved as usize, scale as Scale as usize);
+        }
+    }
+}
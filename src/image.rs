@@ -1,8 +1,8 @@
 //! Il2CppImage
 
-use crate::{Il2CppAssembly, Il2CppClass, NonNullRef, Ref};
-use il2cpp_sys_rs::{il2cpp_get_corlib, il2cpp_image_get_class, il2cpp_image_get_class_count};
-use std::ffi::CStr;
+use crate::runtime::{il2cpp_get_corlib, il2cpp_image_get_class, il2cpp_image_get_class_count};
+use crate::{Il2CppAssembly, Il2CppClass, Il2CppError, NonNullRef, Ref};
+use std::ffi::{CStr, CString};
 use std::fmt;
 
 /// Image handle
@@ -60,14 +60,38 @@ impl Il2CppImage {
     /// * `namespace` - Namespace of the class, empty for global
     /// * `name` - Simple class name.
     ///   For generic **definitions**, include the arity suffix (e.g. `List`1`, `Dictionary`2`).
-    ///   Do **not** include type arguments here. For nested types, use `Outer`1/Inner`2`.
+    ///   Do **not** include type arguments here. For nested types, use `Outer`1/Inner`2` or
+    ///   `Outer`1+Inner`2`, see [`Il2CppClass::nested_name`].
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Class handle if found, otherwise `None`
-    #[inline]
-    pub fn find_class(self, namespace: &CStr, name: &CStr) -> Option<Il2CppClass> {
-        Il2CppClass::from_name(self, namespace, name)
+    /// Returns [`Il2CppError::ClassNotFound`] if the image has no such class
+    pub fn find_class(self, namespace: &CStr, name: &CStr) -> Result<Il2CppClass, Il2CppError> {
+        self.find_nested(namespace, name).ok_or_else(|| Il2CppError::ClassNotFound {
+            image: self,
+            namespace: namespace.to_owned(),
+            name: name.to_owned(),
+        })
+    }
+
+    /// Finds a class by namespace and name, nested classes separated by `/` or `+`
+    fn find_nested(self, namespace: &CStr, name: &CStr) -> Option<Il2CppClass> {
+        let bytes = name.to_bytes();
+        if !bytes.contains(&b'/') && !bytes.contains(&b'+') {
+            return Il2CppClass::from_name(self, namespace, name);
+        }
+
+        // Nested classes are looked up from the outermost declaring class
+        let mut path = bytes.split(|&byte| byte == b'/' || byte == b'+');
+        let outermost = CString::new(path.next()?).ok()?;
+        path.try_fold(
+            Il2CppClass::from_name(self, namespace, &outermost)?,
+            |class, name| {
+                class
+                    .nested_types()
+                    .find(|nested| nested.name().to_bytes() == name)
+            },
+        )
     }
 }
 
@@ -126,3 +150,40 @@ impl fmt::Debug for Il2CppImage {
             .finish()
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::Il2CppError;
+
+    #[test]
+    fn find_class() {
+        let image = mock::image(c"ImageTests.dll");
+        let player = ClassBuilder::new(image, c"Game", c"Player").build();
+        let inventory = ClassBuilder::new(image, c"", c"Inventory")
+            .nested_in(player)
+            .build();
+
+        let found = image.find_class(c"Game", c"Player").unwrap();
+        assert_eq!(found.as_ptr(), player.as_ptr());
+        let nested = image.find_class(c"Game", c"Player/Inventory").unwrap();
+        assert_eq!(nested.as_ptr(), inventory.as_ptr());
+        let nested = image.find_class(c"Game", c"Player+Inventory").unwrap();
+        assert_eq!(nested.as_ptr(), inventory.as_ptr());
+    }
+
+    #[test]
+    fn find_class_not_found() {
+        let image = mock::image(c"ImageTestsMissing.dll");
+        ClassBuilder::new(image, c"Game", c"Player").build();
+
+        let err = image.find_class(c"Game", c"Enemy").unwrap_err();
+        assert!(matches!(err, Il2CppError::ClassNotFound { .. }));
+        assert_eq!(
+            err.to_string(),
+            "class Game.Enemy not found in image ImageTestsMissing.dll"
+        );
+        assert!(image.find_class(c"Other", c"Player").is_err());
+        assert!(image.find_class(c"Game", c"Player/Missing").is_err());
+    }
+}
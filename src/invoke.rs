@@ -0,0 +1,807 @@
+//! Typed method invocation
+
+use crate::{
+    GcHandle, Il2CppArray, Il2CppArrayRef, Il2CppClass, Il2CppDelegate, Il2CppDelegateRef,
+    Il2CppObject, Il2CppObjectRef, Il2CppString, Il2CppStringRef, Il2CppTypeHandle, MethodInfo,
+    Ref,
+};
+use il2cpp_sys_rs::{
+    Il2CppTypeEnum, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY,
+    Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN, Il2CppTypeEnum_IL2CPP_TYPE_CHAR,
+    Il2CppTypeEnum_IL2CPP_TYPE_CLASS, Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST,
+    Il2CppTypeEnum_IL2CPP_TYPE_I, Il2CppTypeEnum_IL2CPP_TYPE_I1, Il2CppTypeEnum_IL2CPP_TYPE_I2,
+    Il2CppTypeEnum_IL2CPP_TYPE_I4, Il2CppTypeEnum_IL2CPP_TYPE_I8,
+    Il2CppTypeEnum_IL2CPP_TYPE_OBJECT, Il2CppTypeEnum_IL2CPP_TYPE_R4,
+    Il2CppTypeEnum_IL2CPP_TYPE_R8, Il2CppTypeEnum_IL2CPP_TYPE_STRING,
+    Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY, Il2CppTypeEnum_IL2CPP_TYPE_U,
+    Il2CppTypeEnum_IL2CPP_TYPE_U1, Il2CppTypeEnum_IL2CPP_TYPE_U2, Il2CppTypeEnum_IL2CPP_TYPE_U4,
+    Il2CppTypeEnum_IL2CPP_TYPE_U8, Il2CppTypeEnum_IL2CPP_TYPE_VOID,
+};
+use std::any::type_name;
+use std::error::Error;
+use std::ffi::c_void;
+use std::fmt;
+
+/// Error raised by [`MethodInfo::call`]
+#[derive(Debug)]
+pub enum InvokeError {
+    /// An instance method was called without a target
+    NullInstance,
+    /// The argument count does not match the method parameter count
+    ArgumentCount {
+        /// Method parameter count
+        expected: usize,
+        /// Argument count
+        found: usize,
+    },
+    /// An argument type does not match its parameter type
+    ArgumentType {
+        /// Zero-based parameter index
+        index: usize,
+        /// Managed parameter type name
+        expected: String,
+        /// Rust argument type name
+        found: &'static str,
+    },
+    /// The requested return type does not match the method return type
+    ReturnType {
+        /// Managed return type name
+        expected: String,
+        /// Rust return type name
+        found: &'static str,
+    },
+    /// An argument could not be converted to its managed representation
+    InvalidArgument {
+        /// Zero-based parameter index
+        index: usize,
+        /// Reason of the failure
+        reason: &'static str,
+    },
+    /// The returned value could not be converted to the requested Rust type
+    InvalidReturn(&'static str),
+    /// The method threw a managed exception, held by a strong GC handle
+    Exception(GcHandle<il2cpp_sys_rs::Il2CppException>),
+}
+
+impl fmt::Display for InvokeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NullInstance => f.write_str("instance method called without a target"),
+            Self::ArgumentCount { expected, found } => {
+                write!(f, "expected {expected} arguments, found {found}")
+            }
+            Self::ArgumentType {
+                index,
+                expected,
+                found,
+            } => write!(f, "argument {index} is `{found}`, expected `{expected}`"),
+            Self::ReturnType { expected, found } => {
+                write!(f, "return type is `{expected}`, requested `{found}`")
+            }
+            Self::InvalidArgument { index, reason } => write!(f, "argument {index}: {reason}"),
+            Self::InvalidReturn(reason) => write!(f, "invalid return value: {reason}"),
+            Self::Exception(exception) => write!(f, "managed exception: {}", exception.target()),
+        }
+    }
+}
+
+impl Error for InvokeError {}
+
+/// Rust value passed as a managed method argument
+///
+/// Value types are passed by pointer to their storage, reference types by object pointer.
+pub trait IntoIl2CppArg {
+    /// Argument storage, kept alive until the call returns
+    type Storage;
+
+    /// Returns `true` if the value can be passed to a parameter of type `type_`
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool;
+
+    /// Returns `true` if this value can be passed to a parameter of type `type_`
+    ///
+    /// Managed objects also check their runtime class, by default only
+    /// [`Self::is_compatible`] is checked.
+    #[inline]
+    fn is_compatible_value(&self, type_: Il2CppTypeHandle) -> bool {
+        Self::is_compatible(type_)
+    }
+
+    /// Converts the value into its managed representation
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason of the failure if the value has no managed representation
+    fn into_storage(self) -> Result<Self::Storage, &'static str>;
+
+    /// Returns the argument pointer passed to the runtime
+    fn as_arg(storage: &mut Self::Storage) -> *mut c_void;
+}
+
+/// Rust value converted from a managed method return value
+pub trait FromIl2CppReturn: Sized {
+    /// Returns `true` if a method returning `type_` can be converted into this type
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool;
+
+    /// Converts the value returned by the runtime
+    ///
+    /// # Safety
+    ///
+    /// `result` must be returned by a method whose return type is compatible with this type
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvokeError::InvalidReturn`] if the value cannot be represented by this type
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError>;
+}
+
+/// Tuple of Rust values passed as managed method arguments
+pub trait IntoIl2CppArgs {
+    /// Storage of every argument, kept alive until the call returns
+    type Storage;
+
+    /// Number of arguments
+    const COUNT: usize;
+
+    /// Checks every argument type against `parameters`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvokeError::ArgumentType`] on the first mismatching argument
+    fn check(&self, parameters: &[Il2CppTypeHandle]) -> Result<(), InvokeError>;
+
+    /// Converts every argument into its managed representation
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvokeError::InvalidArgument`] on the first argument that fails to convert
+    fn into_storage(self) -> Result<Self::Storage, InvokeError>;
+
+    /// Returns the argument pointers passed to the runtime
+    fn as_args(storage: &mut Self::Storage) -> Vec<*mut c_void>;
+}
+
+impl MethodInfo {
+    /// Invokes the method with typed arguments
+    ///
+    /// The argument count, the argument types and the return type are checked against the
+    /// method signature before the call.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - Target instance, null for a static method
+    /// * `arguments` - Tuple of arguments, such as `(1, "x")`
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvokeError`] if the signature does not match or if the method throws
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use il2cpp_rs::{InvokeError, MethodInfo, Ref};
+    /// # fn example(method: MethodInfo) -> Result<(), InvokeError> {
+    /// let result = method.call::<(i32, &str), f32>(Ref::null(), (1, "x"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call<A: IntoIl2CppArgs, R: FromIl2CppReturn>(
+        self,
+        object: Il2CppObjectRef,
+        arguments: A,
+    ) -> Result<R, InvokeError> {
+        if object.is_null() && !self.is_static() {
+            return Err(InvokeError::NullInstance);
+        }
+
+        let parameters = self.parameters();
+        if parameters.len() != A::COUNT {
+            return Err(InvokeError::ArgumentCount {
+                expected: parameters.len(),
+                found: A::COUNT,
+            });
+        }
+        arguments.check(parameters)?;
+
+        let return_type = self.return_type();
+        if !R::is_compatible(return_type) {
+            return Err(InvokeError::ReturnType {
+                expected: return_type.name(),
+                found: type_name::<R>(),
+            });
+        }
+
+        let mut storage = arguments.into_storage()?;
+        let mut args = A::as_args(&mut storage);
+        let result = self
+            .invoke_raw(object, &mut args)
+            .map_err(|exception| InvokeError::Exception(GcHandle::new(exception)))?;
+
+        unsafe { R::from_return(result) }
+    }
+}
+
+/// Checks one argument type against its parameter
+#[inline]
+fn check_argument<T: IntoIl2CppArg>(
+    argument: &T,
+    index: usize,
+    parameter: Il2CppTypeHandle,
+) -> Result<(), InvokeError> {
+    if argument.is_compatible_value(parameter) {
+        Ok(())
+    } else {
+        Err(InvokeError::ArgumentType {
+            index,
+            expected: parameter.name(),
+            found: type_name::<T>(),
+        })
+    }
+}
+
+/// Returns `true` if `type_` is passed by value and is of kind `type_enum`
+#[inline]
+pub(crate) fn is_by_value(type_: Il2CppTypeHandle, type_enum: Il2CppTypeEnum) -> bool {
+    !type_.is_byref() && type_.raw_kind() == type_enum
+}
+
+/// Returns `true` if `type_` is passed by value and is a reference type
+#[allow(non_upper_case_globals)]
+#[inline]
+pub(crate) fn is_reference(type_: Il2CppTypeHandle) -> bool {
+    !type_.is_byref()
+        && match type_.raw_kind() {
+            Il2CppTypeEnum_IL2CPP_TYPE_CLASS
+            | Il2CppTypeEnum_IL2CPP_TYPE_OBJECT
+            | Il2CppTypeEnum_IL2CPP_TYPE_STRING
+            | Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY
+            | Il2CppTypeEnum_IL2CPP_TYPE_ARRAY => true,
+            Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST => !type_.is_value_type(),
+            _ => false,
+        }
+}
+
+/// Returns `true` if `type_` is a string, or an object that can hold one
+#[inline]
+pub(crate) fn is_string(type_: Il2CppTypeHandle) -> bool {
+    is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_STRING)
+        || is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_OBJECT)
+}
+
+/// Returns `true` if `type_` is an array, or an object that can hold one
+#[inline]
+pub(crate) fn is_array(type_: Il2CppTypeHandle) -> bool {
+    is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY)
+        || is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY)
+        || is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_OBJECT)
+}
+
+/// Returns `true` if `object` is an instance of the class of `type_`, null matches any class
+#[inline]
+fn is_instance(object: Option<Il2CppObject>, type_: Il2CppTypeHandle) -> bool {
+    object.is_none_or(|object| {
+        type_
+            .class()
+            .is_some_and(|class| object.class().is_subclass_of(class, true))
+    })
+}
+
+/// Returns `true` if the elements of `array` are of the element type of `type_`,
+/// null matches any array
+///
+/// Arrays passed as `System.Object` are checked like any other object.
+#[inline]
+fn is_array_instance(array: Option<Il2CppObject>, type_: Il2CppTypeHandle) -> bool {
+    let Some(element_type) = type_.element_type() else {
+        return is_instance(array, type_);
+    };
+    array.is_none_or(|array| {
+        let element_class = Il2CppClass::from_ptr(array.class().as_ref().element_class);
+        element_class
+            .zip(element_type.class())
+            .is_some_and(|(element_class, class)| element_class.is_subclass_of(class, true))
+    })
+}
+
+/// Returns the boxed value returned by the runtime
+#[inline]
+fn boxed(result: Il2CppObjectRef) -> Result<Il2CppObject, InvokeError> {
+    result
+        .non_null()
+        .ok_or(InvokeError::InvalidReturn("null boxed value"))
+}
+
+/// Implements the marshalling traits for primitive value types
+macro_rules! primitive {
+    ($($ty:ty => $type_enum:ident),* $(,)?) => {
+        $(
+            impl IntoIl2CppArg for $ty {
+                type Storage = Self;
+
+                #[inline]
+                fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+                    is_by_value(type_, $type_enum)
+                }
+
+                #[inline]
+                fn into_storage(self) -> Result<Self, &'static str> {
+                    Ok(self)
+                }
+
+                #[inline]
+                fn as_arg(storage: &mut Self) -> *mut c_void {
+                    storage as *mut Self as _
+                }
+            }
+
+            impl FromIl2CppReturn for $ty {
+                #[inline]
+                fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+                    is_by_value(type_, $type_enum)
+                }
+
+                #[inline]
+                unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+                    Ok(unsafe { boxed(result)?.unbox_ptr::<Self>().read_unaligned() })
+                }
+            }
+        )*
+    };
+}
+
+primitive! {
+    i8 => Il2CppTypeEnum_IL2CPP_TYPE_I1,
+    u8 => Il2CppTypeEnum_IL2CPP_TYPE_U1,
+    i16 => Il2CppTypeEnum_IL2CPP_TYPE_I2,
+    u16 => Il2CppTypeEnum_IL2CPP_TYPE_U2,
+    i32 => Il2CppTypeEnum_IL2CPP_TYPE_I4,
+    u32 => Il2CppTypeEnum_IL2CPP_TYPE_U4,
+    i64 => Il2CppTypeEnum_IL2CPP_TYPE_I8,
+    u64 => Il2CppTypeEnum_IL2CPP_TYPE_U8,
+    isize => Il2CppTypeEnum_IL2CPP_TYPE_I,
+    usize => Il2CppTypeEnum_IL2CPP_TYPE_U,
+    f32 => Il2CppTypeEnum_IL2CPP_TYPE_R4,
+    f64 => Il2CppTypeEnum_IL2CPP_TYPE_R8,
+}
+
+impl IntoIl2CppArg for bool {
+    /// Managed booleans are one byte wide
+    type Storage = u8;
+
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN)
+    }
+
+    #[inline]
+    fn into_storage(self) -> Result<u8, &'static str> {
+        Ok(self as u8)
+    }
+
+    #[inline]
+    fn as_arg(storage: &mut u8) -> *mut c_void {
+        storage as *mut u8 as _
+    }
+}
+
+impl FromIl2CppReturn for bool {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Ok(unsafe { boxed(result)?.unbox_ptr::<u8>().read() } != 0)
+    }
+}
+
+impl IntoIl2CppArg for char {
+    /// Managed chars are UTF-16 code units
+    type Storage = u16;
+
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_CHAR)
+    }
+
+    #[inline]
+    fn into_storage(self) -> Result<u16, &'static str> {
+        u16::try_from(u32::from(self)).map_err(|_| "char is outside the basic multilingual plane")
+    }
+
+    #[inline]
+    fn as_arg(storage: &mut u16) -> *mut c_void {
+        storage as *mut u16 as _
+    }
+}
+
+impl FromIl2CppReturn for char {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_CHAR)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        let unit = unsafe { boxed(result)?.unbox_ptr::<u16>().read_unaligned() };
+        char::from_u32(unit as u32).ok_or(InvokeError::InvalidReturn("lone UTF-16 surrogate"))
+    }
+}
+
+impl FromIl2CppReturn for () {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_VOID)
+    }
+
+    #[inline]
+    unsafe fn from_return(_result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Ok(())
+    }
+}
+
+impl IntoIl2CppArg for &str {
+    type Storage = *mut c_void;
+
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_string(type_)
+    }
+
+    #[inline]
+    fn into_storage(self) -> Result<*mut c_void, &'static str> {
+        let mut chars: Vec<_> = self.encode_utf16().collect();
+        let len = i32::try_from(chars.len()).map_err(|_| "string is too long")?;
+        chars.push(0);
+
+        unsafe { Il2CppString::new_utf16(&chars, len) }
+            .map(|string| string.as_ptr() as _)
+            .map_err(|_| "failed to allocate the string")
+    }
+
+    #[inline]
+    fn as_arg(storage: &mut *mut c_void) -> *mut c_void {
+        *storage
+    }
+}
+
+impl FromIl2CppReturn for String {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_STRING)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        let string = unsafe { Il2CppString::from_return(result)? };
+        string
+            .to_utf8()
+            .map_err(|_| InvokeError::InvalidReturn("string is not valid UTF-16"))
+    }
+}
+
+/// Implements the marshalling traits for managed reference handles
+macro_rules! reference {
+    ($([$($generics:tt)*] $ty:ty => $is_compatible:ident $(, $is_instance:ident)?;)*) => {
+        $(
+            impl<$($generics)*> IntoIl2CppArg for $ty {
+                type Storage = *mut c_void;
+
+                #[inline]
+                fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+                    $is_compatible(type_)
+                }
+$(
+                #[inline]
+                fn is_compatible_value(&self, type_: Il2CppTypeHandle) -> bool {
+                    let object = Il2CppObject::from_ptr(self.as_ptr() as _);
+                    $is_compatible(type_) && $is_instance(object, type_)
+                }
+)?
+
+                #[inline]
+                fn into_storage(self) -> Result<*mut c_void, &'static str> {
+                    Ok(self.as_ptr() as _)
+                }
+
+                #[inline]
+                fn as_arg(storage: &mut *mut c_void) -> *mut c_void {
+                    *storage
+                }
+            }
+        )*
+    };
+}
+
+reference! {
+    [] Il2CppObject => is_reference, is_instance;
+    [] Il2CppObjectRef => is_reference, is_instance;
+    [] Il2CppString => is_string;
+    [] Il2CppStringRef => is_string;
+    [] Il2CppDelegate => is_reference, is_instance;
+    [] Il2CppDelegateRef => is_reference, is_instance;
+    [T, R] Il2CppArray<T, R> => is_array, is_array_instance;
+    [T, R] Il2CppArrayRef<T, R> => is_array, is_array_instance;
+}
+
+impl FromIl2CppReturn for Il2CppObject {
+    /// Value types are boxed by the runtime
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        !is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_VOID)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        result
+            .non_null()
+            .ok_or(InvokeError::InvalidReturn("null object"))
+    }
+}
+
+impl FromIl2CppReturn for Il2CppObjectRef {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        !is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_VOID)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Ok(result)
+    }
+}
+
+impl FromIl2CppReturn for Il2CppString {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_STRING)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Self::from_ptr(result.as_ptr() as _).ok_or(InvokeError::InvalidReturn("null string"))
+    }
+}
+
+impl FromIl2CppReturn for Il2CppStringRef {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_STRING)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Ok(Ref::new(result.as_ptr() as _))
+    }
+}
+
+impl<T, R> FromIl2CppReturn for Il2CppArray<T, R> {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY)
+            || is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Self::from_ptr(result.as_ptr() as _).ok_or(InvokeError::InvalidReturn("null array"))
+    }
+}
+
+impl<T, R> FromIl2CppReturn for Il2CppArrayRef<T, R> {
+    #[inline]
+    fn is_compatible(type_: Il2CppTypeHandle) -> bool {
+        is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY)
+            || is_by_value(type_, Il2CppTypeEnum_IL2CPP_TYPE_ARRAY)
+    }
+
+    #[inline]
+    unsafe fn from_return(result: Il2CppObjectRef) -> Result<Self, InvokeError> {
+        Ok(Ref::new(result.as_ptr() as _))
+    }
+}
+
+impl IntoIl2CppArgs for () {
+    type Storage = ();
+
+    const COUNT: usize = 0;
+
+    #[inline]
+    fn check(&self, _parameters: &[Il2CppTypeHandle]) -> Result<(), InvokeError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn into_storage(self) -> Result<(), InvokeError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn as_args(_storage: &mut ()) -> Vec<*mut c_void> {
+        Vec::new()
+    }
+}
+
+/// Implements [`IntoIl2CppArgs`] for tuples
+macro_rules! tuple {
+    ($count:literal => $($name:ident $index:tt),+) => {
+        impl<$($name: IntoIl2CppArg),+> IntoIl2CppArgs for ($($name,)+) {
+            type Storage = ($($name::Storage,)+);
+
+            const COUNT: usize = $count;
+
+            #[inline]
+            fn check(&self, parameters: &[Il2CppTypeHandle]) -> Result<(), InvokeError> {
+                $(check_argument(&self.$index, $index, parameters[$index])?;)+
+                Ok(())
+            }
+
+            #[inline]
+            fn into_storage(self) -> Result<Self::Storage, InvokeError> {
+                Ok(($(
+                    self.$index
+                        .into_storage()
+                        .map_err(|reason| InvokeError::InvalidArgument { index: $index, reason })?,
+                )+))
+            }
+
+            #[inline]
+            fn as_args(storage: &mut Self::Storage) -> Vec<*mut c_void> {
+                vec![$($name::as_arg(&mut storage.$index)),+]
+            }
+        }
+    };
+}
+
+tuple!(1 => A 0);
+tuple!(2 => A 0, B 1);
+tuple!(3 => A 0, B 1, C 2);
+tuple!(4 => A 0, B 1, C 2, D 3);
+tuple!(5 => A 0, B 1, C 2, D 3, E 4);
+tuple!(6 => A 0, B 1, C 2, D 3, E 4, F 5);
+tuple!(7 => A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple!(8 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+tuple!(9 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+tuple!(10 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+tuple!(11 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+tuple!(12 => A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{
+        Il2CppClass, Il2CppImage, Il2CppObject, Il2CppObjectRef, Il2CppString, Il2CppStringRef,
+        Il2CppSzArray, Il2CppSzArrayRef, InvokeError, Ref,
+    };
+    use std::ffi::{CStr, CString};
+
+    fn corlib(name: &CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    /// `Game.Typed` with `float Mix(int a, string b)`, `string Greet(string name)`,
+    /// an instance method and a throwing method
+    fn typed_class(image_name: &CStr) -> Il2CppClass {
+        let image = mock::image(image_name);
+        ClassBuilder::new(image, c"Game", c"Typed")
+            .static_method(
+                c"Mix",
+                &[(c"a", corlib(c"Int32")), (c"b", corlib(c"String"))],
+                corlib(c"Single"),
+                |call| {
+                    let a = unsafe { call.arg::<i32>(0) };
+                    let b = call.arg_object::<il2cpp_sys_rs::Il2CppString>(1).unwrap_non_null();
+                    let mixed = a as f32 + b.to_utf8_lossy().len() as f32;
+                    Ok(Il2CppObject::box_value(corlib(c"Single"), &mixed).unwrap().into())
+                },
+            )
+            .static_method(c"Greet", &[(c"name", corlib(c"String"))], corlib(c"String"), |call| {
+                let name = call.arg_object::<il2cpp_sys_rs::Il2CppString>(0).unwrap_non_null();
+                let greeting = CString::new(format!("Hello {}", name.to_utf8_lossy())).unwrap();
+                let greeting = Il2CppString::new(&greeting).unwrap();
+                Ok(Ref::new(greeting.as_ptr() as _))
+            })
+            .static_method(c"Flag", &[(c"value", corlib(c"Boolean"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .method(c"Level", &[], corlib(c"Int32"), |_| {
+                Ok(Il2CppObject::box_value(corlib(c"Int32"), &7).unwrap().into())
+            })
+            .static_method(c"Fail", &[], corlib(c"Int32"), |_| Err(mock::exception("typed")))
+            .build()
+    }
+
+    #[test]
+    fn call() {
+        let class = typed_class(c"InvokeTests.dll");
+
+        let mix = class.find_method(c"Mix", 2).unwrap();
+        assert_eq!(mix.call::<_, f32>(Ref::null(), (1, "xyz")).unwrap(), 4.0);
+        let name = Il2CppString::new(c"ab").unwrap();
+        let boxed: Il2CppObject = mix.call(Ref::null(), (1, name)).unwrap();
+        assert_eq!(unsafe { boxed.unbox::<f32>() }, 3.0);
+
+        let greet = class.find_method(c"Greet", 1).unwrap();
+        let greeting: String = greet.call(Ref::null(), ("Alice",)).unwrap();
+        assert_eq!(greeting, "Hello Alice");
+        let greeting: Il2CppStringRef = greet.call(Ref::null(), ("Bob",)).unwrap();
+        assert_eq!(greeting.unwrap_non_null().to_utf8_lossy(), "Hello Bob");
+
+        let () = class.find_method(c"Flag", 1).unwrap().call(Ref::null(), (true,)).unwrap();
+
+        let level = class.find_method(c"Level", 0).unwrap();
+        let object = Il2CppObject::new(class).unwrap();
+        assert_eq!(level.call::<_, i32>(object.into(), ()).unwrap(), 7);
+    }
+
+    #[test]
+    fn call_errors() {
+        let class = typed_class(c"InvokeTestsErrors.dll");
+        let mix = class.find_method(c"Mix", 2).unwrap();
+
+        assert!(matches!(
+            mix.call::<_, f32>(Ref::null(), (1,)),
+            Err(InvokeError::ArgumentCount { expected: 2, found: 1 })
+        ));
+        let err = mix.call::<_, f32>(Ref::null(), (1.0_f32, "x")).unwrap_err();
+        assert!(matches!(err, InvokeError::ArgumentType { index: 0, .. }));
+        assert_eq!(err.to_string(), "argument 0 is `f32`, expected `System.Int32`");
+        let err = mix.call::<_, i32>(Ref::null(), (1, "x")).unwrap_err();
+        assert_eq!(err.to_string(), "return type is `System.Single`, requested `i32`");
+
+        let level = class.find_method(c"Level", 0).unwrap();
+        assert!(matches!(level.call::<_, i32>(Ref::null(), ()), Err(InvokeError::NullInstance)));
+
+        let fail = class.find_method(c"Fail", 0).unwrap();
+        match fail.call::<_, i32>(Ref::null(), ()) {
+            Err(InvokeError::Exception(exception)) => {
+                assert_eq!(exception.target().to_string(), "System.Exception: typed");
+            }
+            other => panic!("expected a managed exception, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn call_argument_classes() {
+        let image = mock::image(c"InvokeTestsClasses.dll");
+        let unit = ClassBuilder::new(image, c"Game", c"Unit").build();
+        let player = ClassBuilder::new(image, c"Game", c"Player").parent(unit).build();
+        let item = ClassBuilder::new(image, c"Game", c"Item").build();
+        let int32_array = Il2CppSzArray::<i32>::array_class_get(corlib(c"Int32")).unwrap();
+        let class = ClassBuilder::new(image, c"Game", c"Checker")
+            .static_method(c"Target", &[(c"unit", unit)], corlib(c"Void"), |_| Ok(Ref::null()))
+            .static_method(c"Sum", &[(c"values", int32_array)], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .static_method(c"Store", &[(c"value", corlib(c"Object"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .build();
+
+        let target = class.find_method(c"Target", 1).unwrap();
+        let player_object = Il2CppObject::new(player).unwrap();
+        assert!(target.call::<_, ()>(Ref::null(), (Il2CppObject::new(unit).unwrap(),)).is_ok());
+        assert!(target.call::<_, ()>(Ref::null(), (player_object,)).is_ok());
+        assert!(target.call::<_, ()>(Ref::null(), (Il2CppObjectRef::null(),)).is_ok());
+        let err = target
+            .call::<_, ()>(Ref::null(), (Il2CppObject::new(item).unwrap(),))
+            .unwrap_err();
+        assert!(matches!(err, InvokeError::ArgumentType { index: 0, .. }));
+        let found = std::any::type_name::<Il2CppObject>();
+        assert_eq!(err.to_string(), format!("argument 0 is `{found}`, expected `Game.Unit`"));
+
+        let sum = class.find_method(c"Sum", 1).unwrap();
+        let ints = Il2CppSzArray::<i32>::new(corlib(c"Int32"), 2).unwrap();
+        let floats = Il2CppSzArray::<f32>::new(corlib(c"Single"), 2).unwrap();
+        assert!(sum.call::<_, ()>(Ref::null(), (ints,)).is_ok());
+        assert!(sum.call::<_, ()>(Ref::null(), (Il2CppSzArrayRef::<i32>::null(),)).is_ok());
+        assert!(matches!(
+            sum.call::<_, ()>(Ref::null(), (floats,)),
+            Err(InvokeError::ArgumentType { index: 0, .. })
+        ));
+
+        let store = class.find_method(c"Store", 1).unwrap();
+        assert!(store.call::<_, ()>(Ref::null(), (floats,)).is_ok());
+        assert!(store.call::<_, ()>(Ref::null(), (player_object,)).is_ok());
+    }
+}
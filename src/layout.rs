@@ -0,0 +1,294 @@
+//! Rust mirrors of managed object layouts
+
+use crate::{FieldInfo, Il2CppClass, Il2CppDomain};
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::sync::OnceLock;
+use std::{fmt, ptr};
+
+/// Rust struct mirroring the memory layout of a managed class
+///
+/// Implemented by `#[derive(Il2CppLayout)]` with the `derive` feature, on `#[repr(C)]` structs:
+///
+/// ```ignore
+/// use il2cpp_rs::{Il2CppLayout, Il2CppStringRef};
+///
+/// #[derive(Il2CppLayout)]
+/// #[il2cpp(class = "Game.Player", assembly = "Assembly-CSharp")]
+/// #[repr(C)]
+/// struct Player {
+///     // Not a managed field, the object header is part of the instance offsets
+///     #[il2cpp(skip)]
+///     header: il2cpp_rs::sys::Il2CppObject,
+///     health: i32,
+///     #[il2cpp(name = "m_Name")]
+///     name: Il2CppStringRef,
+/// }
+///
+/// // At startup, before casting objects to `Player`
+/// Player::validate().unwrap_or_else(|err| panic!("{err}"));
+/// ```
+///
+/// The derive macro also generates a getter per field, such as `health()`,
+/// and a setter writing through the runtime, such as `set_health(value)`.
+/// Getters return the fields by value, mirrored fields must be `Copy`.
+pub trait Il2CppLayout: Sized {
+    /// Full name of the mirrored class, such as `Game.Player` or `Game.Player+Inventory`
+    const CLASS: &'static str;
+    /// Assembly declaring the class, every loaded assembly is searched if `None`
+    const ASSEMBLY: Option<&'static CStr>;
+    /// Mirrored fields, in declaration order
+    const FIELDS: &'static [LayoutField];
+
+    /// Finds the mirrored class
+    ///
+    /// # Returns
+    ///
+    /// Class handle if loaded, otherwise `None`
+    fn class() -> Option<Il2CppClass> {
+        find_class(Self::ASSEMBLY, Self::CLASS)
+    }
+
+    /// Checks the offset and size of every mirrored field against the runtime
+    ///
+    /// Value types are mirrored unboxed, their runtime offsets are taken without the object header.
+    ///
+    /// # Returns
+    ///
+    /// The mirrored class
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LayoutError::ClassNotFound`] if the class is not loaded,
+    /// or [`LayoutError::Mismatch`] listing every field that does not match
+    fn validate() -> Result<Il2CppClass, LayoutError> {
+        let class = Self::class().ok_or(LayoutError::ClassNotFound(Self::CLASS))?;
+        let header = header_size(class);
+
+        let mismatches: Vec<_> = Self::FIELDS
+            .iter()
+            .filter_map(|&field| {
+                let Ok(info) = class.find_field(field.managed_name) else {
+                    return Some(FieldMismatch::Missing(field));
+                };
+                if info.is_static() {
+                    return Some(FieldMismatch::Static(field));
+                }
+
+                let offset = info.offset().saturating_sub(header);
+                if offset != field.offset {
+                    return Some(FieldMismatch::Offset {
+                        field,
+                        runtime: offset,
+                    });
+                }
+                // Generic parameters have no class to compare against
+                let size = info.type_().class()?.array_element_size();
+                (size != field.size).then_some(FieldMismatch::Size {
+                    field,
+                    runtime: size,
+                })
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(class)
+        } else {
+            Err(LayoutError::Mismatch {
+                class: Self::CLASS,
+                mismatches,
+            })
+        }
+    }
+}
+
+/// Field of an [`Il2CppLayout`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutField {
+    /// Rust field name
+    pub name: &'static str,
+    /// Managed field name
+    pub managed_name: &'static CStr,
+    /// Byte offset of the Rust field
+    pub offset: usize,
+    /// Byte size of the Rust field
+    pub size: usize,
+}
+
+/// Field of an [`Il2CppLayout`] that does not match the runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldMismatch {
+    /// The class has no field of that name
+    Missing(LayoutField),
+    /// The managed field is static, so it is not part of the instance
+    Static(LayoutField),
+    /// The managed field is at another offset
+    Offset {
+        /// Rust field
+        field: LayoutField,
+        /// Managed field offset
+        runtime: usize,
+    },
+    /// The managed field type has another size
+    Size {
+        /// Rust field
+        field: LayoutField,
+        /// Managed field size
+        runtime: usize,
+    },
+}
+
+impl FieldMismatch {
+    /// Returns the mismatching Rust field
+    #[inline]
+    pub const fn field(&self) -> LayoutField {
+        match *self {
+            Self::Missing(field)
+            | Self::Static(field)
+            | Self::Offset { field, .. }
+            | Self::Size { field, .. } => field,
+        }
+    }
+}
+
+impl fmt::Display for FieldMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = self.field();
+        write!(f, "`{}`", field.name)?;
+        if field.name.as_bytes() != field.managed_name.to_bytes() {
+            write!(f, " (`{}`)", field.managed_name.to_string_lossy())?;
+        }
+        match self {
+            Self::Missing(_) => f.write_str(" is not a field of the class"),
+            Self::Static(_) => f.write_str(" is a static field"),
+            Self::Offset { field, runtime } => {
+                write!(f, " is at offset {:#x}, expected {runtime:#x}", field.offset)
+            }
+            Self::Size { field, runtime } => {
+                write!(f, " is {} bytes wide, expected {runtime}", field.size)
+            }
+        }
+    }
+}
+
+/// Error raised by [`Il2CppLayout::validate`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The mirrored class is not loaded
+    ClassNotFound(&'static str),
+    /// Fields do not match the runtime layout
+    Mismatch {
+        /// Full name of the mirrored class
+        class: &'static str,
+        /// Every mismatching field, in declaration order
+        mismatches: Vec<FieldMismatch>,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClassNotFound(class) => write!(f, "class `{class}` not found"),
+            Self::Mismatch { class, mismatches } => {
+                write!(f, "layout of `{class}` does not match the runtime:")?;
+                for mismatch in mismatches {
+                    write!(f, "\n  {mismatch}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+/// Type of a field of an [`Il2CppLayout`], returned by value by the derived getters
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not `Copy`, `Il2CppLayout` getters return the fields by value",
+    label = "not `Copy`",
+    note = "mirror the field with a `Copy` type, such as an object handle, or skip it with \
+            `#[il2cpp(skip)]`"
+)]
+pub trait LayoutValue: Copy {}
+
+impl<T: Copy> LayoutValue for T {}
+
+/// Reads a field of an [`Il2CppLayout`], used by the derived getters
+#[doc(hidden)]
+#[inline]
+pub fn layout_value<T: LayoutValue>(value: &T) -> T {
+    *value
+}
+
+/// Writes a field of an [`Il2CppLayout`] through the runtime, used by the derived setters
+///
+/// Reference fields of objects are written with the GC write barrier.
+///
+/// # Safety
+///
+/// `object` must be a live instance of the class, unboxed for value types,
+/// and the field must be of type `T`
+///
+/// # Panics
+///
+/// Panics if the class or the field is not found
+///
+/// # Arguments
+///
+/// * `object` - Mirror of the instance
+/// * `field` - Cached field handle
+/// * `name` - Managed field name
+/// * `value` - New value
+#[doc(hidden)]
+#[track_caller]
+pub unsafe fn set_layout_field<L: Il2CppLayout, T>(
+    object: &mut L,
+    field: &OnceLock<usize>,
+    name: &CStr,
+    value: T,
+) {
+    let field = *field.get_or_init(|| {
+        L::class()
+            .and_then(|class| class.find_field(name).ok())
+            .unwrap_or_else(|| panic!("field {}.{} not found", L::CLASS, name.to_string_lossy()))
+            .as_ptr() as usize
+    });
+    let field = FieldInfo::from_ptr(field as _).unwrap();
+    let parent = field.parent();
+
+    unsafe {
+        if parent.is_value_type() {
+            let offset = field.offset() - header_size(parent);
+            ptr::write_unaligned((object as *mut L as *mut u8).add(offset) as *mut T, value);
+        } else {
+            field.set_value(object as *mut L as _, value);
+        }
+    }
+}
+
+/// Returns the size of the object header included in the field offsets of unboxed instances
+fn header_size(class: Il2CppClass) -> usize {
+    if class.is_value_type() {
+        size_of::<il2cpp_sys_rs::Il2CppObject>()
+    } else {
+        0
+    }
+}
+
+/// Finds a class by full name, nested classes separated by `+` or `/`
+fn find_class(assembly: Option<&CStr>, full_name: &str) -> Option<Il2CppClass> {
+    let outer_end = full_name.find(['+', '/']).unwrap_or(full_name.len());
+    let (namespace, name) = match full_name[..outer_end].rfind('.') {
+        Some(dot) => (&full_name[..dot], &full_name[dot + 1..]),
+        None => ("", full_name),
+    };
+    let namespace = CString::new(namespace).ok()?;
+    let name = CString::new(name).ok()?;
+
+    Il2CppDomain::get()
+        .assemblies()
+        .iter()
+        .filter(|candidate| assembly.is_none_or(|assembly| candidate.name() == assembly))
+        .find_map(|assembly| assembly.image().find_class(&namespace, &name).ok())
+}
@@ -0,0 +1,492 @@
+//! Lookups resolved once and cached, for `static` items
+
+use crate::{FieldInfo, Il2CppAssembly, Il2CppClass, Il2CppError, MethodInfo};
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Error raised when a lazy lookup cannot be resolved
+///
+/// The lookup path is displayed in full, such as
+/// `method Game.Player.Jump(System.Single) not found in assembly Assembly-CSharp`.
+#[derive(Debug)]
+pub enum LookupError {
+    /// The assembly is not loaded
+    AssemblyNotFound(&'static CStr),
+    /// The assembly has no such class
+    ClassNotFound(&'static LazyClass),
+    /// The class has no such method
+    MethodNotFound(&'static LazyMethod),
+    /// Several overloads match the method parameter types
+    AmbiguousMethod(&'static LazyMethod, Vec<MethodInfo>),
+    /// The class has no such field
+    FieldNotFound(&'static LazyField),
+    /// The lookup failed for another reason, such as a missing runtime export
+    Lookup {
+        /// Item looked up, such as `method Game.Player.Jump(System.Single)`
+        path: String,
+        /// Assembly searched
+        assembly: &'static CStr,
+        /// Error of the lookup
+        source: Box<Il2CppError>,
+    },
+}
+
+impl fmt::Display for LookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AssemblyNotFound(assembly) => {
+                write!(f, "assembly {} not loaded", assembly.to_string_lossy())
+            }
+            Self::ClassNotFound(class) => write!(
+                f,
+                "class {class} not found in assembly {}",
+                class.assembly.to_string_lossy()
+            ),
+            Self::MethodNotFound(method) => write!(
+                f,
+                "method {method} not found in assembly {}",
+                method.class.assembly.to_string_lossy()
+            ),
+            Self::AmbiguousMethod(method, candidates) => write!(
+                f,
+                "method {method} is ambiguous in assembly {}, {} overloads match",
+                method.class.assembly.to_string_lossy(),
+                candidates.len()
+            ),
+            Self::FieldNotFound(field) => write!(
+                f,
+                "field {field} not found in assembly {}",
+                field.class.assembly.to_string_lossy()
+            ),
+            Self::Lookup {
+                path,
+                assembly,
+                source,
+            } => write!(
+                f,
+                "lookup of {path} in assembly {} failed: {source}",
+                assembly.to_string_lossy()
+            ),
+        }
+    }
+}
+
+impl Error for LookupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Lookup { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl LookupError {
+    /// Wraps an error of the runtime other than a missing item
+    fn lookup(path: String, assembly: &'static CStr, source: Il2CppError) -> Self {
+        Self::Lookup {
+            path,
+            assembly,
+            source: Box::new(source),
+        }
+    }
+}
+
+// Safety: the lazy lookups are `Sync`, the candidates point to runtime metadata, immutable
+// and never freed, and the source error is `Send + Sync`
+unsafe impl Send for LookupError {}
+unsafe impl Sync for LookupError {}
+
+/// Class resolved on first use and cached
+///
+/// Lazy lookups are meant for `static` items, their errors borrow them to display the lookup path.
+///
+/// # Example
+///
+/// ```no_run
+/// use il2cpp_rs::{LazyClass, LazyField, LazyMethod};
+///
+/// static PLAYER: LazyClass = LazyClass::new(c"Assembly-CSharp", c"Game", c"Player");
+/// static JUMP: LazyMethod = LazyMethod::new(&PLAYER, c"Jump", 1);
+/// static HEALTH: LazyField = LazyField::new(&PLAYER, c"health");
+///
+/// // Resolved by the first call, later calls return the cached handle
+/// let jump = JUMP.get();
+/// ```
+pub struct LazyClass {
+    assembly: &'static CStr,
+    namespace: &'static CStr,
+    /// Class name, nested classes separated by `/` or `+`
+    name: &'static CStr,
+    class: OnceLock<Il2CppClass>,
+}
+
+/// Method resolved on first use and cached
+pub struct LazyMethod {
+    class: &'static LazyClass,
+    name: &'static CStr,
+    /// Parameter count, `-1` for any
+    arity: i32,
+    /// Parameter types, if the overload is selected by type
+    parameters: Option<&'static [&'static str]>,
+    method: OnceLock<MethodInfo>,
+}
+
+/// Field resolved on first use and cached
+pub struct LazyField {
+    class: &'static LazyClass,
+    name: &'static CStr,
+    field: OnceLock<FieldInfo>,
+}
+
+// Safety: the cached handles point to runtime metadata, immutable and never freed
+unsafe impl Send for LazyClass {}
+unsafe impl Sync for LazyClass {}
+unsafe impl Send for LazyMethod {}
+unsafe impl Sync for LazyMethod {}
+unsafe impl Send for LazyField {}
+unsafe impl Sync for LazyField {}
+
+impl LazyClass {
+    /// Creates an unresolved class lookup
+    ///
+    /// # Arguments
+    ///
+    /// * `assembly` - Assembly name, such as `Assembly-CSharp`
+    /// * `namespace` - Class namespace, empty for global, of the outermost class if nested
+    /// * `name` - Class name, such as `Player` or `Player/Inventory`
+    #[inline]
+    pub const fn new(
+        assembly: &'static CStr,
+        namespace: &'static CStr,
+        name: &'static CStr,
+    ) -> Self {
+        Self {
+            assembly,
+            namespace,
+            name,
+            class: OnceLock::new(),
+        }
+    }
+
+    /// Returns the class, resolving it on first use
+    ///
+    /// # Panics
+    ///
+    /// Panics with the lookup path if the class cannot be resolved
+    #[track_caller]
+    #[inline]
+    pub fn get(&'static self) -> Il2CppClass {
+        self.try_get().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns the class, resolving it on first use
+    ///
+    /// Failed resolutions are not cached, the lookup is retried by the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LookupError::AssemblyNotFound`], [`LookupError::ClassNotFound`],
+    /// or [`LookupError::Lookup`] with the error of the runtime if the lookup fails otherwise
+    pub fn try_get(&'static self) -> Result<Il2CppClass, LookupError> {
+        if let Some(&class) = self.class.get() {
+            return Ok(class);
+        }
+
+        let assembly = Il2CppAssembly::from_name(self.assembly)
+            .ok_or(LookupError::AssemblyNotFound(self.assembly))?;
+        let class = assembly
+            .image()
+            .find_class(self.namespace, self.name)
+            .map_err(|err| match err {
+                Il2CppError::ClassNotFound { .. } => LookupError::ClassNotFound(self),
+                err => LookupError::lookup(format!("class {self}"), self.assembly, err),
+            })?;
+        Ok(*self.class.get_or_init(|| class))
+    }
+}
+
+impl fmt::Display for LazyClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.namespace.is_empty() {
+            write!(f, "{}.", self.namespace.to_string_lossy())?;
+        }
+        f.write_str(&self.name.to_string_lossy())
+    }
+}
+
+impl fmt::Debug for LazyClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyClass")
+            .field("assembly", &self.assembly)
+            .field("namespace", &self.namespace)
+            .field("name", &self.name)
+            .field("class", &self.class.get())
+            .finish()
+    }
+}
+
+impl LazyMethod {
+    /// Creates an unresolved method lookup by parameter count
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Class declaring the method, or one of its subclasses
+    /// * `name` - Simple method name
+    /// * `arity` - Parameter count, `-1` for any
+    #[inline]
+    pub const fn new(class: &'static LazyClass, name: &'static CStr, arity: i32) -> Self {
+        Self {
+            class,
+            name,
+            arity,
+            parameters: None,
+            method: OnceLock::new(),
+        }
+    }
+
+    /// Creates an unresolved method lookup by parameter types
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Class declaring the method, or one of its subclasses
+    /// * `name` - Simple method name
+    /// * `parameters` - Parameter types, as fully qualified names such as `System.Single`
+    #[inline]
+    pub const fn exact(
+        class: &'static LazyClass,
+        name: &'static CStr,
+        parameters: &'static [&'static str],
+    ) -> Self {
+        Self {
+            class,
+            name,
+            arity: parameters.len() as i32,
+            parameters: Some(parameters),
+            method: OnceLock::new(),
+        }
+    }
+
+    /// Returns the method, resolving it on first use
+    ///
+    /// # Panics
+    ///
+    /// Panics with the lookup path if the method cannot be resolved
+    #[track_caller]
+    #[inline]
+    pub fn get(&'static self) -> MethodInfo {
+        self.try_get().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns the method, resolving it on first use
+    ///
+    /// Failed resolutions are not cached, the lookup is retried by the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`LookupError`] of the class, [`LookupError::MethodNotFound`],
+    /// [`LookupError::AmbiguousMethod`] if several overloads match the parameter types,
+    /// or [`LookupError::Lookup`] with the error of the runtime if the lookup fails otherwise
+    pub fn try_get(&'static self) -> Result<MethodInfo, LookupError> {
+        if let Some(&method) = self.method.get() {
+            return Ok(method);
+        }
+
+        let class = self.class.try_get()?;
+        let method = match self.parameters {
+            Some(parameters) => class.find_method_exact(self.name, parameters),
+            None => class.find_method(self.name, self.arity),
+        };
+        let method = method.map_err(|err| match err {
+            Il2CppError::MethodNotFound { .. } => LookupError::MethodNotFound(self),
+            Il2CppError::AmbiguousMethod { candidates, .. } => {
+                LookupError::AmbiguousMethod(self, candidates)
+            }
+            err => LookupError::lookup(format!("method {self}"), self.class.assembly, err),
+        })?;
+        Ok(*self.method.get_or_init(|| method))
+    }
+}
+
+impl fmt::Display for LazyMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.class, self.name.to_string_lossy())?;
+        match self.parameters {
+            Some(parameters) => write!(f, "({})", parameters.join(", ")),
+            None if self.arity < 0 => Ok(()),
+            None => write!(f, " with {} parameters", self.arity),
+        }
+    }
+}
+
+impl fmt::Debug for LazyMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyMethod")
+            .field("class", &self.class)
+            .field("name", &self.name)
+            .field("arity", &self.arity)
+            .field("parameters", &self.parameters)
+            .field("method", &self.method.get())
+            .finish()
+    }
+}
+
+impl LazyField {
+    /// Creates an unresolved field lookup
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Class declaring the field
+    /// * `name` - Field name
+    #[inline]
+    pub const fn new(class: &'static LazyClass, name: &'static CStr) -> Self {
+        Self {
+            class,
+            name,
+            field: OnceLock::new(),
+        }
+    }
+
+    /// Returns the field, resolving it on first use
+    ///
+    /// # Panics
+    ///
+    /// Panics with the lookup path if the field cannot be resolved
+    #[track_caller]
+    #[inline]
+    pub fn get(&'static self) -> FieldInfo {
+        self.try_get().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Returns the field, resolving it on first use
+    ///
+    /// Failed resolutions are not cached, the lookup is retried by the next call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`LookupError`] of the class, [`LookupError::FieldNotFound`],
+    /// or [`LookupError::Lookup`] with the error of the runtime if the lookup fails otherwise
+    pub fn try_get(&'static self) -> Result<FieldInfo, LookupError> {
+        if let Some(&field) = self.field.get() {
+            return Ok(field);
+        }
+
+        let field = self
+            .class
+            .try_get()?
+            .find_field(self.name)
+            .map_err(|err| match err {
+                Il2CppError::FieldNotFound { .. } => LookupError::FieldNotFound(self),
+                err => LookupError::lookup(format!("field {self}"), self.class.assembly, err),
+            })?;
+        Ok(*self.field.get_or_init(|| field))
+    }
+}
+
+impl fmt::Display for LazyField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.class, self.name.to_string_lossy())
+    }
+}
+
+impl fmt::Debug for LazyField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyField")
+            .field("class", &self.class)
+            .field("name", &self.name)
+            .field("field", &self.field.get())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppError, Il2CppImage, LazyClass, LazyField, LazyMethod, LookupError, Ref};
+    use std::error::Error;
+    use std::panic;
+
+    static THING: LazyClass = LazyClass::new(c"LazyTests", c"Lazy", c"Thing");
+    static INNER: LazyClass = LazyClass::new(c"LazyTests", c"Lazy", c"Thing/Inner");
+    static OTHER: LazyClass = LazyClass::new(c"LazyTests", c"Lazy", c"Other");
+    static UNLOADED: LazyClass = LazyClass::new(c"LazyTestsUnloaded", c"Lazy", c"Thing");
+    static HEALTH: LazyField = LazyField::new(&THING, c"health");
+    static MANA: LazyField = LazyField::new(&THING, c"mana");
+    static MOVE: LazyMethod = LazyMethod::new(&THING, c"Move", 1);
+    static MOVE_EXACT: LazyMethod =
+        LazyMethod::exact(&THING, c"Move", &["System.Int32", "System.Int32"]);
+    static MOVE_3: LazyMethod = LazyMethod::new(&THING, c"Move", 3);
+    static OTHER_MOVE: LazyMethod = LazyMethod::exact(&OTHER, c"Move", &[]);
+    static DUP: LazyMethod = LazyMethod::exact(&THING, c"Dup", &["System.Int32"]);
+
+    #[test]
+    fn lookups() {
+        let image = mock::image(c"LazyTests.dll");
+        let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+        let thing = ClassBuilder::new(image, c"Lazy", c"Thing")
+            .field(c"health", int32)
+            .method(c"Move", &[(c"x", int32)], int32, |_| Ok(Ref::null()))
+            .method(c"Move", &[(c"x", int32), (c"y", int32)], int32, |_| Ok(Ref::null()))
+            .method(c"Dup", &[(c"x", int32)], int32, |_| Ok(Ref::null()))
+            .method(c"Dup", &[(c"y", int32)], int32, |_| Ok(Ref::null()))
+            .build();
+        let inner = ClassBuilder::new(image, c"", c"Inner").nested_in(thing).build();
+
+        assert_eq!(THING.get().as_ptr(), thing.as_ptr());
+        assert_eq!(INNER.get().as_ptr(), inner.as_ptr());
+        assert_eq!(HEALTH.get().name(), c"health");
+        assert_eq!(MOVE.get().parameters().len(), 1);
+        assert_eq!(MOVE_EXACT.get().parameters().len(), 2);
+        // Cached
+        assert_eq!(MOVE_EXACT.get().as_ptr(), MOVE_EXACT.try_get().unwrap().as_ptr());
+
+        assert!(matches!(UNLOADED.try_get(), Err(LookupError::AssemblyNotFound(_))));
+        assert_eq!(
+            UNLOADED.try_get().unwrap_err().to_string(),
+            "assembly LazyTestsUnloaded not loaded"
+        );
+        assert_eq!(
+            OTHER.try_get().unwrap_err().to_string(),
+            "class Lazy.Other not found in assembly LazyTests"
+        );
+        assert_eq!(
+            MANA.try_get().unwrap_err().to_string(),
+            "field Lazy.Thing.mana not found in assembly LazyTests"
+        );
+        assert_eq!(
+            MOVE_3.try_get().unwrap_err().to_string(),
+            "method Lazy.Thing.Move with 3 parameters not found in assembly LazyTests"
+        );
+        // The error of the class is reported
+        assert!(matches!(OTHER_MOVE.try_get(), Err(LookupError::ClassNotFound(_))));
+        // Not reported as missing
+        assert!(matches!(
+            DUP.try_get(),
+            Err(LookupError::AmbiguousMethod(_, candidates)) if candidates.len() == 2
+        ));
+        assert_eq!(
+            DUP.try_get().unwrap_err().to_string(),
+            "method Lazy.Thing.Dup(System.Int32) is ambiguous in assembly LazyTests, \
+             2 overloads match"
+        );
+
+        let err = LookupError::Lookup {
+            path: format!("method {DUP}"),
+            assembly: c"LazyTests",
+            source: Box::new(Il2CppError::MissingExport("il2cpp_class_from_name")),
+        };
+        assert_eq!(
+            err.to_string(),
+            "lookup of method Lazy.Thing.Dup(System.Int32) in assembly LazyTests failed: \
+             the IL2CPP runtime does not export il2cpp_class_from_name"
+        );
+        assert!(matches!(
+            err.source().unwrap().downcast_ref(),
+            Some(Il2CppError::MissingExport(_))
+        ));
+
+        let message = panic::catch_unwind(|| MANA.get()).unwrap_err();
+        assert!(message.downcast_ref::<String>().unwrap().contains("Lazy.Thing.mana"));
+    }
+}
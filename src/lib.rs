@@ -2,14 +2,40 @@
 
 mod array;
 mod assembly;
+#[cfg(feature = "serde")]
+pub mod bindgen;
 mod class;
+mod custom_attribute;
+mod delegate;
+mod domain;
+pub mod dump;
+mod error;
+mod event_info;
 mod exception;
 mod field_info;
 mod flags;
+mod gc_handle;
+mod generic;
+#[cfg(all(feature = "hook", target_arch = "x86_64"))]
+mod hook;
+pub mod icall;
 mod image;
+mod invoke;
+mod layout;
+mod lazy;
+pub mod metadata;
 mod method_info;
+mod native;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod object;
 mod property_info;
+mod runtime;
+#[cfg(feature = "serde")]
+mod snapshot;
 mod string;
+mod thread;
+mod type_handle;
 
 /// Raw IL2CPP bindings
 pub mod sys {
@@ -19,11 +45,30 @@ pub mod sys {
 pub use array::*;
 pub use assembly::*;
 pub use class::*;
+pub use custom_attribute::*;
+pub use delegate::*;
+pub use domain::*;
+pub use error::*;
+pub use event_info::*;
 pub use exception::*;
 pub use field_info::*;
+pub use gc_handle::*;
+pub use generic::*;
+#[cfg(all(feature = "hook", target_arch = "x86_64"))]
+pub use hook::*;
+#[cfg(feature = "derive")]
+pub use il2cpp_rs_derive::Il2CppLayout;
 pub use image::*;
+pub use invoke::*;
+pub use layout::*;
+pub use lazy::*;
 pub use method_info::*;
+pub use native::*;
+pub use object::*;
 pub use property_info::*;
+pub use runtime::{Il2CppExports, Il2CppRuntime, RuntimeError};
+#[cfg(feature = "serde")]
+pub use snapshot::*;
 use std::any::type_name;
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
@@ -31,6 +76,8 @@ use std::marker::PhantomData;
 use std::ptr::NonNull;
 use std::{fmt, ptr};
 pub use string::*;
+pub use thread::*;
+pub use type_handle::*;
 
 /// Nullable managed IL2CPP reference
 #[repr(transparent)]
@@ -0,0 +1,1073 @@
+//! Offline `global-metadata.dat` parser
+//!
+//! Reads the definitions of a game without running it, to diff builds or generate bindings
+//! ahead of time. Metadata versions 24.2 to 31 are supported, from Unity 2019.3 onwards.
+//!
+//! Types are described by the runtime binary rather than the metadata file: fields,
+//! parameters and return types only expose the index of their type in the binary.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use il2cpp_rs::metadata::GlobalMetadata;
+//!
+//! let metadata = GlobalMetadata::open("Game_Data/il2cpp_data/Metadata/global-metadata.dat")?;
+//! for image in metadata.images() {
+//!     for type_ in image.type_definitions() {
+//!         println!("{}: {}", image.name().to_string_lossy(), type_.full_name());
+//!     }
+//! }
+//! # Ok::<(), il2cpp_rs::metadata::MetadataError>(())
+//! ```
+
+use crate::flags::TYPE_ATTRIBUTE_INTERFACE;
+use std::borrow::Cow;
+use std::error::Error;
+use std::ffi::CStr;
+use std::path::Path;
+use std::{fmt, fs, io, str};
+
+/// Magic number starting every metadata file
+const SANITY: u32 = 0xFAB1_1BAF;
+/// Header size of versions 24.2 to 24.5, earlier 24 revisions have a larger header
+const HEADER_SIZE_24_2: usize = 264;
+
+/// Header sections, in order, up to the image definitions
+const SECTIONS: [&str; 21] = [
+    "string literals",
+    "string literal data",
+    "strings",
+    "events",
+    "properties",
+    "methods",
+    "parameter default values",
+    "field default values",
+    "default value data",
+    "field marshaled sizes",
+    "parameters",
+    "fields",
+    "generic parameters",
+    "generic parameter constraints",
+    "generic containers",
+    "nested types",
+    "interfaces",
+    "vtable methods",
+    "interface offsets",
+    "type definitions",
+    "images",
+];
+
+const STRING_LITERALS: usize = 0;
+const STRING_LITERAL_DATA: usize = 1;
+const STRINGS: usize = 2;
+const METHODS: usize = 5;
+const PARAMETER_DEFAULT_VALUES: usize = 6;
+const FIELD_DEFAULT_VALUES: usize = 7;
+const DEFAULT_VALUE_DATA: usize = 8;
+const PARAMETERS: usize = 10;
+const FIELDS: usize = 11;
+const NESTED_TYPES: usize = 15;
+const TYPE_DEFINITIONS: usize = 19;
+const IMAGES: usize = 20;
+
+/// `Il2CppStringLiteral` size
+const STRING_LITERAL_SIZE: usize = 8;
+/// `Il2CppFieldDefaultValue` and `Il2CppParameterDefaultValue` size
+const DEFAULT_VALUE_SIZE: usize = 12;
+/// `Il2CppParameterDefinition` size
+const PARAMETER_SIZE: usize = 12;
+/// `Il2CppFieldDefinition` size
+const FIELD_SIZE: usize = 12;
+/// `Il2CppImageDefinition` size
+const IMAGE_SIZE: usize = 40;
+
+/// Error raised while loading a metadata file
+#[derive(Debug)]
+pub enum MetadataError {
+    /// The file could not be read
+    Io(io::Error),
+    /// The file does not start with the metadata magic number
+    InvalidSanity(u32),
+    /// The metadata version is not supported
+    UnsupportedVersion(i32),
+    /// A section extends past the end of the file
+    Truncated(&'static str),
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read the metadata: {err}"),
+            Self::InvalidSanity(sanity) => write!(f, "invalid metadata sanity {sanity:#x}"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported metadata version {version}, versions 24.2 to 31 are supported"
+            ),
+            Self::Truncated(section) => write!(f, "truncated metadata {section}"),
+        }
+    }
+}
+
+impl Error for MetadataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MetadataError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Parsed `global-metadata.dat` file
+///
+/// Definitions are read on access. Indices read from the file are checked, an out of range
+/// name reads as empty and an out of range definition is skipped.
+pub struct GlobalMetadata {
+    data: Vec<u8>,
+    version: i32,
+    /// Offset and size of each section in [`SECTIONS`]
+    sections: [(usize, usize); SECTIONS.len()],
+}
+
+impl GlobalMetadata {
+    /// Reads a metadata file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to `global-metadata.dat`, usually in `<Game>_Data/il2cpp_data/Metadata`
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MetadataError`] if the file cannot be read or is not a supported metadata file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MetadataError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// Parses the content of a metadata file
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - File content, decrypted if the game encrypts it
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MetadataError`] if the content is not a supported metadata file
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, MetadataError> {
+        let header_size = 8 + SECTIONS.len() * 8;
+        if data.len() < header_size {
+            return Err(MetadataError::Truncated("header"));
+        }
+
+        let mut metadata = Self {
+            data,
+            version: 0,
+            sections: [(0, 0); SECTIONS.len()],
+        };
+        let sanity = metadata.u32(0);
+        if sanity != SANITY {
+            return Err(MetadataError::InvalidSanity(sanity));
+        }
+
+        metadata.version = metadata.u32(4) as i32;
+        for (i, &name) in SECTIONS.iter().enumerate() {
+            let offset = metadata.u32(8 + i * 8) as usize;
+            let size = metadata.u32(12 + i * 8) as usize;
+            if offset.checked_add(size).is_none_or(|end| end > metadata.data.len()) {
+                return Err(MetadataError::Truncated(name));
+            }
+            metadata.sections[i] = (offset, size);
+        }
+
+        // The first section starts right after the header
+        let supported = match metadata.version {
+            24 => metadata.sections[STRING_LITERALS].0 == HEADER_SIZE_24_2,
+            27 | 29 | 31 => true,
+            _ => false,
+        };
+        if !supported {
+            return Err(MetadataError::UnsupportedVersion(metadata.version));
+        }
+        Ok(metadata)
+    }
+
+    /// Returns the metadata version, 24 for every revision from 24.2 to 24.5
+    #[inline]
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Returns the image definitions
+    pub fn images(&self) -> impl Iterator<Item = ImageDefinition<'_>> {
+        (0..self.count(IMAGES, IMAGE_SIZE)).map(|index| ImageDefinition {
+            metadata: self,
+            index,
+            offset: self.record(IMAGES, IMAGE_SIZE, index),
+        })
+    }
+
+    /// Returns the type definitions of every image
+    pub fn type_definitions(&self) -> impl Iterator<Item = TypeDefinition<'_>> {
+        (0..self.count(TYPE_DEFINITIONS, self.type_definition_size()))
+            .filter_map(|index| self.type_definition(index))
+    }
+
+    /// Returns the type definition at `index`, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Type definition index
+    pub fn type_definition(&self, index: usize) -> Option<TypeDefinition<'_>> {
+        let size = self.type_definition_size();
+        (index < self.count(TYPE_DEFINITIONS, size)).then(|| TypeDefinition {
+            metadata: self,
+            index,
+            offset: self.record(TYPE_DEFINITIONS, size, index),
+        })
+    }
+
+    /// Finds a type definition by namespace and name
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace of the type, empty for global and nested types
+    /// * `name` - Simple type name, with the arity suffix for generic definitions
+    ///
+    /// # Returns
+    ///
+    /// First type definition found, otherwise `None`
+    pub fn find_type(&self, namespace: &CStr, name: &CStr) -> Option<TypeDefinition<'_>> {
+        self.type_definitions()
+            .find(|type_| type_.name() == name && type_.namespace() == namespace)
+    }
+
+    /// Returns the string literals used by the managed code
+    pub fn string_literals(&self) -> impl Iterator<Item = StringLiteral<'_>> {
+        (0..self.count(STRING_LITERALS, STRING_LITERAL_SIZE)).map(|index| StringLiteral {
+            metadata: self,
+            index,
+            offset: self.record(STRING_LITERALS, STRING_LITERAL_SIZE, index),
+        })
+    }
+
+    /// Returns the method definition at `index`, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Method definition index
+    pub fn method(&self, index: usize) -> Option<MethodDefinition<'_>> {
+        let size = self.method_definition_size();
+        (index < self.count(METHODS, size)).then(|| MethodDefinition {
+            metadata: self,
+            index,
+            offset: self.record(METHODS, size, index),
+        })
+    }
+
+    /// Returns the field definition at `index`, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Field definition index
+    pub fn field(&self, index: usize) -> Option<FieldDefinition<'_>> {
+        (index < self.count(FIELDS, FIELD_SIZE)).then(|| FieldDefinition {
+            metadata: self,
+            index,
+            offset: self.record(FIELDS, FIELD_SIZE, index),
+        })
+    }
+
+    /// Returns the parameter definition at `index`, if any
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Parameter definition index
+    pub fn parameter(&self, index: usize) -> Option<ParameterDefinition<'_>> {
+        (index < self.count(PARAMETERS, PARAMETER_SIZE)).then(|| ParameterDefinition {
+            metadata: self,
+            index,
+            offset: self.record(PARAMETERS, PARAMETER_SIZE, index),
+        })
+    }
+
+    /// `Il2CppTypeDefinition` size, `byrefTypeIndex` was removed in version 27
+    fn type_definition_size(&self) -> usize {
+        if self.version < 27 { 92 } else { 88 }
+    }
+
+    /// `Il2CppMethodDefinition` size, `returnParameterToken` was added in version 31
+    fn method_definition_size(&self) -> usize {
+        if self.version < 31 { 32 } else { 36 }
+    }
+
+    /// Returns the content of a section
+    fn section(&self, section: usize) -> &[u8] {
+        let (offset, size) = self.sections[section];
+        &self.data[offset..offset + size]
+    }
+
+    /// Returns the number of records in a section
+    fn count(&self, section: usize, size: usize) -> usize {
+        self.sections[section].1 / size
+    }
+
+    /// Returns the file offset of a record
+    fn record(&self, section: usize, size: usize, index: usize) -> usize {
+        self.sections[section].0 + index * size
+    }
+
+    /// Returns the null-terminated string at `index` in the string section
+    fn string(&self, index: u32) -> &CStr {
+        let strings = self.section(STRINGS).get(index as usize..).unwrap_or_default();
+        CStr::from_bytes_until_nul(strings).unwrap_or_default()
+    }
+
+    /// Returns `count` definitions starting at `start`, skipping out of range indices
+    fn range<'a, T>(
+        &'a self,
+        start: u32,
+        count: u16,
+        get: impl Fn(&'a Self, usize) -> Option<T>,
+    ) -> impl Iterator<Item = T> {
+        // A start of -1 comes with a count of 0
+        let start = start as usize;
+        (0..count as usize).filter_map(move |i| get(self, start.checked_add(i)?))
+    }
+
+    /// Finds the default value of a field or parameter
+    fn default_value(&self, section: usize, index: usize) -> Option<DefaultValue<'_>> {
+        (0..self.count(section, DEFAULT_VALUE_SIZE))
+            .map(|i| self.record(section, DEFAULT_VALUE_SIZE, i))
+            .find(|&offset| self.u32(offset) as usize == index)
+            .map(|offset| DefaultValue {
+                metadata: self,
+                offset,
+            })
+    }
+
+    fn u16(&self, offset: usize) -> u16 {
+        u16::from_le_bytes([self.data[offset], self.data[offset + 1]])
+    }
+
+    fn u32(&self, offset: usize) -> u32 {
+        let bytes = &self.data[offset..offset + 4];
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+    }
+
+    /// Reads an index, `-1` meaning none
+    fn index(&self, offset: usize) -> Option<u32> {
+        Some(self.u32(offset)).filter(|&index| index != u32::MAX)
+    }
+}
+
+impl fmt::Debug for GlobalMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalMetadata")
+            .field("version", &self.version)
+            .field("images", &self.count(IMAGES, IMAGE_SIZE))
+            .field(
+                "typeDefinitions",
+                &self.count(TYPE_DEFINITIONS, self.type_definition_size()),
+            )
+            .finish()
+    }
+}
+
+/// Image definition, an assembly module
+#[derive(Clone, Copy)]
+pub struct ImageDefinition<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> ImageDefinition<'a> {
+    /// Returns the image index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the image name, such as `mscorlib.dll`
+    #[inline]
+    pub fn name(self) -> &'a CStr {
+        self.metadata.string(self.metadata.u32(self.offset))
+    }
+
+    /// Returns the image token
+    #[inline]
+    pub fn token(self) -> u32 {
+        self.metadata.u32(self.offset + 28)
+    }
+
+    /// Returns the type definitions of the image, nested types included
+    pub fn type_definitions(self) -> impl Iterator<Item = TypeDefinition<'a>> {
+        let start = self.metadata.u32(self.offset + 8) as usize;
+        let count = self.metadata.u32(self.offset + 12) as usize;
+        (start..start.saturating_add(count))
+            .map_while(|index| self.metadata.type_definition(index))
+    }
+
+    /// Returns the entry point of the image, if any
+    #[inline]
+    pub fn entry_point(self) -> Option<MethodDefinition<'a>> {
+        let index = self.metadata.index(self.offset + 24)?;
+        self.metadata.method(index as usize)
+    }
+}
+
+/// Type definition, a class, struct, enum, interface or delegate
+#[derive(Clone, Copy)]
+pub struct TypeDefinition<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> TypeDefinition<'a> {
+    /// Returns the type definition index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the type name
+    #[inline]
+    pub fn name(self) -> &'a CStr {
+        self.metadata.string(self.u32(0))
+    }
+
+    /// Returns the type namespace, empty for nested types
+    #[inline]
+    pub fn namespace(self) -> &'a CStr {
+        self.metadata.string(self.u32(4))
+    }
+
+    /// Returns a human-readable fully qualified name, such as `System.Environment+SpecialFolder`
+    ///
+    /// Nested types are separated from their declaring type by `+`. Looking up the declaring
+    /// types scans the type definitions.
+    pub fn full_name(self) -> Cow<'a, str> {
+        let name = self.name().to_string_lossy();
+        let Some(declaring_type) = self.declaring_type() else {
+            let ns = self.namespace().to_string_lossy();
+            return if ns.is_empty() {
+                name
+            } else {
+                format!("{ns}.{name}").into()
+            };
+        };
+        format!("{}+{name}", declaring_type.full_name()).into()
+    }
+
+    /// Returns the index of the type in the runtime binary
+    #[inline]
+    pub fn type_index(self) -> u32 {
+        self.u32(8)
+    }
+
+    /// Returns the index of the declaring type in the runtime binary, if the type is nested
+    #[inline]
+    pub fn declaring_type_index(self) -> Option<u32> {
+        self.metadata.index(self.offset + 12 + self.byref_size())
+    }
+
+    /// Returns the type declaring this nested type
+    ///
+    /// Scans the type definitions.
+    pub fn declaring_type(self) -> Option<Self> {
+        self.declaring_type_index()?;
+        self.metadata
+            .type_definitions()
+            .find(|type_| type_.nested_types().any(|nested| nested.index == self.index))
+    }
+
+    /// Returns the index of the base type in the runtime binary, if any
+    #[inline]
+    pub fn parent_index(self) -> Option<u32> {
+        self.metadata.index(self.offset + 16 + self.byref_size())
+    }
+
+    /// Returns `true` if the type is a generic definition
+    #[inline]
+    pub fn is_generic(self) -> bool {
+        self.metadata.index(self.offset + 24 + self.byref_size()).is_some()
+    }
+
+    /// Returns the type flags, see `TYPE_ATTRIBUTE_*`
+    #[inline]
+    pub fn flags(self) -> u32 {
+        self.u32(28 + self.byref_size())
+    }
+
+    /// Returns `true` if the type is an interface
+    #[inline]
+    pub fn is_interface(self) -> bool {
+        self.flags() & TYPE_ATTRIBUTE_INTERFACE != 0
+    }
+
+    /// Returns `true` if the type is a value type
+    #[inline]
+    pub fn is_value_type(self) -> bool {
+        self.bitfield() & 1 != 0
+    }
+
+    /// Returns `true` if the type is an enum
+    #[inline]
+    pub fn is_enum(self) -> bool {
+        self.bitfield() & 2 != 0
+    }
+
+    /// Returns the type token
+    #[inline]
+    pub fn token(self) -> u32 {
+        self.u32(84 + self.byref_size())
+    }
+
+    /// Returns the fields declared by the type
+    pub fn fields(self) -> impl Iterator<Item = FieldDefinition<'a>> {
+        let (start, count) = (self.u32(32 + self.byref_size()), self.count(68));
+        self.metadata.range(start, count, GlobalMetadata::field)
+    }
+
+    /// Returns the methods declared by the type
+    pub fn methods(self) -> impl Iterator<Item = MethodDefinition<'a>> {
+        let (start, count) = (self.u32(36 + self.byref_size()), self.count(64));
+        self.metadata.range(start, count, GlobalMetadata::method)
+    }
+
+    /// Returns the types nested in the type
+    pub fn nested_types(self) -> impl Iterator<Item = TypeDefinition<'a>> {
+        let (start, count) = (self.u32(48 + self.byref_size()), self.count(72));
+        let metadata = self.metadata;
+        metadata.range(start, count, |metadata, i| {
+            let index = metadata.section(NESTED_TYPES).get(i * 4..i * 4 + 4)?;
+            let index = u32::from_le_bytes([index[0], index[1], index[2], index[3]]);
+            metadata.type_definition(index as usize)
+        })
+    }
+
+    /// `byrefTypeIndex` size, removed in version 27
+    fn byref_size(self) -> usize {
+        if self.metadata.version < 27 { 4 } else { 0 }
+    }
+
+    fn bitfield(self) -> u32 {
+        self.u32(80 + self.byref_size())
+    }
+
+    /// Reads a `uint16_t` count, `offset` is the offset in version 27
+    fn count(self, offset: usize) -> u16 {
+        self.metadata.u16(self.offset + offset + self.byref_size())
+    }
+
+    fn u32(self, offset: usize) -> u32 {
+        self.metadata.u32(self.offset + offset)
+    }
+}
+
+/// Field definition
+#[derive(Clone, Copy)]
+pub struct FieldDefinition<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> FieldDefinition<'a> {
+    /// Returns the field definition index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the field name
+    #[inline]
+    pub fn name(self) -> &'a CStr {
+        self.metadata.string(self.metadata.u32(self.offset))
+    }
+
+    /// Returns the index of the field type in the runtime binary
+    ///
+    /// The field flags are part of the type.
+    #[inline]
+    pub fn type_index(self) -> u32 {
+        self.metadata.u32(self.offset + 4)
+    }
+
+    /// Returns the field token
+    #[inline]
+    pub fn token(self) -> u32 {
+        self.metadata.u32(self.offset + 8)
+    }
+
+    /// Returns the value of a constant field, if any
+    #[inline]
+    pub fn default_value(self) -> Option<DefaultValue<'a>> {
+        self.metadata.default_value(FIELD_DEFAULT_VALUES, self.index)
+    }
+}
+
+/// Method definition
+#[derive(Clone, Copy)]
+pub struct MethodDefinition<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> MethodDefinition<'a> {
+    /// Returns the method definition index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the method name
+    #[inline]
+    pub fn name(self) -> &'a CStr {
+        self.metadata.string(self.metadata.u32(self.offset))
+    }
+
+    /// Returns the type declaring the method
+    #[inline]
+    pub fn declaring_type(self) -> Option<TypeDefinition<'a>> {
+        let index = self.metadata.index(self.offset + 4)?;
+        self.metadata.type_definition(index as usize)
+    }
+
+    /// Returns the index of the return type in the runtime binary
+    #[inline]
+    pub fn return_type_index(self) -> u32 {
+        self.metadata.u32(self.offset + 8)
+    }
+
+    /// Returns the parameters of the method
+    pub fn parameters(self) -> impl Iterator<Item = ParameterDefinition<'a>> {
+        let start = self.metadata.u32(self.offset + 12 + self.return_parameter_size());
+        self.metadata
+            .range(start, self.parameters_count(), GlobalMetadata::parameter)
+    }
+
+    /// Returns the number of parameters
+    #[inline]
+    pub fn parameters_count(self) -> u16 {
+        self.u16(30)
+    }
+
+    /// Returns the method token
+    #[inline]
+    pub fn token(self) -> u32 {
+        self.metadata.u32(self.offset + 20 + self.return_parameter_size())
+    }
+
+    /// Returns the method flags, see `METHOD_ATTRIBUTE_*`
+    #[inline]
+    pub fn flags(self) -> u32 {
+        self.u16(24) as u32
+    }
+
+    /// Returns the method implementation flags, see `METHOD_IMPL_ATTRIBUTE_*`
+    #[inline]
+    pub fn impl_flags(self) -> u32 {
+        self.u16(26) as u32
+    }
+
+    /// Returns the vtable slot of a virtual method
+    #[inline]
+    pub fn slot(self) -> Option<u16> {
+        Some(self.u16(28)).filter(|&slot| slot != u16::MAX)
+    }
+
+    /// `returnParameterToken` size, added in version 31
+    fn return_parameter_size(self) -> usize {
+        if self.metadata.version < 31 { 0 } else { 4 }
+    }
+
+    /// Reads a `uint16_t` following the token, `offset` is the offset in version 27
+    fn u16(self, offset: usize) -> u16 {
+        self.metadata
+            .u16(self.offset + offset + self.return_parameter_size())
+    }
+}
+
+/// Parameter definition
+#[derive(Clone, Copy)]
+pub struct ParameterDefinition<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> ParameterDefinition<'a> {
+    /// Returns the parameter definition index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the parameter name
+    #[inline]
+    pub fn name(self) -> &'a CStr {
+        self.metadata.string(self.metadata.u32(self.offset))
+    }
+
+    /// Returns the parameter token
+    #[inline]
+    pub fn token(self) -> u32 {
+        self.metadata.u32(self.offset + 4)
+    }
+
+    /// Returns the index of the parameter type in the runtime binary
+    ///
+    /// The parameter flags are part of the type.
+    #[inline]
+    pub fn type_index(self) -> u32 {
+        self.metadata.u32(self.offset + 8)
+    }
+
+    /// Returns the default value of an optional parameter, if any
+    #[inline]
+    pub fn default_value(self) -> Option<DefaultValue<'a>> {
+        self.metadata
+            .default_value(PARAMETER_DEFAULT_VALUES, self.index)
+    }
+}
+
+/// Default value of a constant field or an optional parameter
+#[derive(Clone, Copy)]
+pub struct DefaultValue<'a> {
+    metadata: &'a GlobalMetadata,
+    offset: usize,
+}
+
+impl<'a> DefaultValue<'a> {
+    /// Returns the index of the value type in the runtime binary
+    #[inline]
+    pub fn type_index(self) -> u32 {
+        self.metadata.u32(self.offset + 4)
+    }
+
+    /// Returns the encoded value and the data following it, `None` for a null reference
+    ///
+    /// The encoding depends on the value type: primitives are stored little-endian and
+    /// strings are prefixed by their length.
+    #[inline]
+    pub fn data(self) -> Option<&'a [u8]> {
+        let index = self.metadata.index(self.offset + 8)?;
+        self.metadata
+            .section(DEFAULT_VALUE_DATA)
+            .get(index as usize..)
+    }
+}
+
+/// String literal used by the managed code
+#[derive(Clone, Copy)]
+pub struct StringLiteral<'a> {
+    metadata: &'a GlobalMetadata,
+    index: usize,
+    offset: usize,
+}
+
+impl<'a> StringLiteral<'a> {
+    /// Returns the string literal index
+    #[inline]
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Returns the UTF-8 encoded literal
+    #[inline]
+    pub fn as_bytes(self) -> &'a [u8] {
+        let len = self.metadata.u32(self.offset) as usize;
+        let start = self.metadata.u32(self.offset + 4) as usize;
+        self.metadata
+            .section(STRING_LITERAL_DATA)
+            .get(start..start.saturating_add(len))
+            .unwrap_or_default()
+    }
+
+    /// Returns the literal
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`str::Utf8Error`] if the literal is not valid UTF-8
+    #[inline]
+    pub fn to_str(self) -> Result<&'a str, str::Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// Returns the literal, replacing invalid UTF-8 sequences
+    #[inline]
+    pub fn to_string_lossy(self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.as_bytes())
+    }
+}
+
+impl fmt::Display for ImageDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name().to_string_lossy(), f)
+    }
+}
+
+impl fmt::Debug for ImageDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageDefinition")
+            .field("name", &self.name().to_string_lossy())
+            .field("token", &self.token())
+            .finish()
+    }
+}
+
+impl fmt::Display for TypeDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.full_name(), f)
+    }
+}
+
+impl fmt::Debug for TypeDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeDefinition")
+            .field("name", &self.name().to_string_lossy())
+            .field("namespace", &self.namespace().to_string_lossy())
+            .field("flags", &format_args!("{:#x}", self.flags()))
+            .field("token", &format_args!("{:#x}", self.token()))
+            .finish()
+    }
+}
+
+impl fmt::Display for FieldDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name().to_string_lossy(), f)
+    }
+}
+
+impl fmt::Debug for FieldDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldDefinition")
+            .field("name", &self.name().to_string_lossy())
+            .field("token", &format_args!("{:#x}", self.token()))
+            .finish()
+    }
+}
+
+impl fmt::Display for MethodDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name().to_string_lossy(), f)
+    }
+}
+
+impl fmt::Debug for MethodDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MethodDefinition")
+            .field("name", &self.name().to_string_lossy())
+            .field(
+                "parameters",
+                &self
+                    .parameters()
+                    .map(|parameter| parameter.name().to_string_lossy())
+                    .collect::<Vec<_>>(),
+            )
+            .field("flags", &format_args!("{:#x}", self.flags()))
+            .field("token", &format_args!("{:#x}", self.token()))
+            .finish()
+    }
+}
+
+impl fmt::Display for ParameterDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.name().to_string_lossy(), f)
+    }
+}
+
+impl fmt::Debug for ParameterDefinition<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParameterDefinition")
+            .field("name", &self.name().to_string_lossy())
+            .field("token", &format_args!("{:#x}", self.token()))
+            .finish()
+    }
+}
+
+impl fmt::Debug for DefaultValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultValue")
+            .field("typeIndex", &self.type_index())
+            .finish()
+    }
+}
+
+impl fmt::Debug for StringLiteral<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_string_lossy(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GlobalMetadata, MetadataError, METHODS, TYPE_DEFINITIONS};
+
+    /// Hand-built fixtures, see `tests/fixtures/README.md`
+    const FIXTURES: [(&str, i32, &[u8]); 4] = [
+        ("24.2", 24, include_bytes!("../tests/fixtures/metadata-v24.2.dat")),
+        ("27", 27, include_bytes!("../tests/fixtures/metadata-v27.dat")),
+        ("29", 29, include_bytes!("../tests/fixtures/metadata-v29.dat")),
+        ("31", 31, include_bytes!("../tests/fixtures/metadata-v31.dat")),
+    ];
+
+    fn fixture(version: &str) -> Vec<u8> {
+        let (_, _, data) = FIXTURES.iter().find(|(name, ..)| *name == version).unwrap();
+        data.to_vec()
+    }
+
+    #[test]
+    fn record_sizes() {
+        for (name, version, data) in FIXTURES {
+            let metadata = GlobalMetadata::from_bytes(data.to_vec()).unwrap();
+            assert_eq!(metadata.version(), version);
+
+            let (type_size, method_size) = match version {
+                24 => (92, 32),
+                27 | 29 => (88, 32),
+                _ => (88, 36),
+            };
+            assert_eq!(metadata.type_definition_size(), type_size, "{name}");
+            assert_eq!(metadata.method_definition_size(), method_size, "{name}");
+            assert_eq!(metadata.sections[TYPE_DEFINITIONS].1, 2 * type_size, "{name}");
+            assert_eq!(metadata.sections[METHODS].1, 2 * method_size, "{name}");
+        }
+    }
+
+    #[test]
+    fn lookups() {
+        for (name, _, data) in FIXTURES {
+            let metadata = GlobalMetadata::from_bytes(data.to_vec()).unwrap();
+            let image = metadata.images().next().unwrap();
+            assert_eq!(image.name(), c"Game.dll", "{name}");
+            assert_eq!(image.token(), 1);
+            assert!(image.entry_point().is_none());
+            assert_eq!(image.type_definitions().count(), 2);
+
+            let player = metadata.find_type(c"Game", c"Player").unwrap();
+            assert_eq!(player.token(), 0x0200_0001, "{name}");
+            assert_eq!(player.flags(), 0x0010_0001);
+            assert_eq!(player.type_index(), 10);
+            assert_eq!(player.parent_index(), Some(2));
+            assert!(player.declaring_type_index().is_none());
+            assert!(!player.is_value_type() && !player.is_interface());
+            assert_eq!(player.full_name(), "Game.Player");
+            assert!(metadata.find_type(c"Game", c"Enemy").is_none());
+
+            // The second record is read at the version record size
+            let inner = player.nested_types().next().unwrap();
+            assert_eq!(inner.name(), c"Inner", "{name}");
+            assert_eq!(inner.token(), 0x0200_0002);
+            assert!(inner.is_value_type());
+            assert_eq!(inner.full_name(), "Game.Player+Inner");
+            assert_eq!(inner.declaring_type().unwrap().index(), player.index());
+            assert_eq!(inner.methods().count(), 0);
+
+            let fields: Vec<_> = player.fields().collect();
+            assert_eq!(fields.len(), 1);
+            assert_eq!(fields[0].name(), c"health");
+            assert_eq!(fields[0].type_index(), 3);
+            assert_eq!(fields[0].token(), 0x0400_0001);
+            let speed = inner.fields().next().unwrap();
+            assert_eq!(speed.name(), c"speed");
+            assert_eq!(speed.token(), 0x0400_0002);
+
+            let methods: Vec<_> = player.methods().collect();
+            assert_eq!(methods.len(), 2, "{name}");
+            let (move_, jump) = (methods[0], methods[1]);
+            assert_eq!(move_.name(), c"Move", "{name}");
+            assert_eq!(move_.declaring_type().unwrap().index(), player.index());
+            assert_eq!(move_.return_type_index(), 9);
+            assert_eq!(move_.token(), 0x0600_0001);
+            assert_eq!(move_.flags(), 0x86);
+            assert_eq!(move_.impl_flags(), 3);
+            assert_eq!(move_.slot(), None);
+            assert_eq!(jump.name(), c"Jump", "{name}");
+            assert_eq!(jump.token(), 0x0600_0002);
+            assert_eq!(jump.flags(), 0x1c6);
+            assert_eq!(jump.slot(), Some(4));
+            assert_eq!(jump.parameters().count(), 0);
+
+            assert_eq!(move_.parameters_count(), 2);
+            let parameters: Vec<_> = move_.parameters().collect();
+            assert_eq!(parameters[0].name(), c"x");
+            assert_eq!(parameters[1].name(), c"y");
+            assert_eq!(parameters[1].token(), 0x0800_0002);
+            assert_eq!(parameters[1].type_index(), 6);
+
+            let literals: Vec<_> = metadata.string_literals().collect();
+            assert_eq!(literals.len(), 2);
+            assert_eq!(literals[0].to_str().unwrap(), "hello");
+            assert_eq!(literals[1].to_string_lossy(), "world");
+        }
+    }
+
+    #[test]
+    fn default_values() {
+        let metadata = GlobalMetadata::from_bytes(fixture("27")).unwrap();
+        let player = metadata.find_type(c"Game", c"Player").unwrap();
+
+        let health = player.fields().next().unwrap().default_value().unwrap();
+        assert_eq!(health.type_index(), 7);
+        assert_eq!(&health.data().unwrap()[..4], &42_i32.to_le_bytes());
+        let speed = player.nested_types().next().unwrap().fields().next().unwrap();
+        assert!(speed.default_value().is_none());
+
+        let parameters: Vec<_> = player.methods().next().unwrap().parameters().collect();
+        assert!(parameters[0].default_value().is_none());
+        let y = parameters[1].default_value().unwrap();
+        assert_eq!(y.type_index(), 8);
+        assert!(y.data().is_none());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            GlobalMetadata::from_bytes(vec![0; 10]),
+            Err(MetadataError::Truncated("header"))
+        ));
+
+        let mut data = fixture("27");
+        data[0] = 0;
+        assert!(matches!(
+            GlobalMetadata::from_bytes(data),
+            Err(MetadataError::InvalidSanity(0xfab1_1b00))
+        ));
+
+        let mut data = fixture("27");
+        data.truncate(data.len() - 1);
+        let err = GlobalMetadata::from_bytes(data).unwrap_err();
+        assert!(matches!(err, MetadataError::Truncated("images")));
+        assert_eq!(err.to_string(), "truncated metadata images");
+
+        for version in [23, 28, 35] {
+            let mut data = fixture("29");
+            data[4..8].copy_from_slice(&i32::to_le_bytes(version));
+            assert!(matches!(
+                GlobalMetadata::from_bytes(data),
+                Err(MetadataError::UnsupportedVersion(found)) if found == version
+            ));
+        }
+        // Version 24 revisions before 24.2 have a larger header
+        let mut data = fixture("27");
+        data[4..8].copy_from_slice(&24_i32.to_le_bytes());
+        assert!(matches!(
+            GlobalMetadata::from_bytes(data),
+            Err(MetadataError::UnsupportedVersion(24))
+        ));
+
+        assert!(matches!(
+            GlobalMetadata::open("tests/fixtures/missing.dat"),
+            Err(MetadataError::Io(_))
+        ));
+    }
+}
@@ -2,12 +2,13 @@
 
 use crate::flags::{
     METHOD_ATTRIBUTE_ABSTRACT, METHOD_ATTRIBUTE_FINAL, METHOD_ATTRIBUTE_MEMBER_ACCESS_MASK,
-    METHOD_ATTRIBUTE_STATIC, METHOD_ATTRIBUTE_VIRTUAL,
+    METHOD_ATTRIBUTE_STATIC, METHOD_ATTRIBUTE_VIRTUAL, METHOD_IMPL_ATTRIBUTE_INTERNAL_CALL,
 };
-use crate::{ExceptionRef, Il2CppClass, NonNullRef, Ref};
-use il2cpp_sys_rs::{
-    il2cpp_class_get_method_from_name, il2cpp_method_get_param_name, il2cpp_runtime_invoke, il2cpp_type_get_name,
-    Il2CppObject, Il2CppType,
+use crate::runtime::{
+    il2cpp_class_get_method_from_name, il2cpp_method_get_param_name, il2cpp_runtime_invoke,
+};
+use crate::{
+    Exception, Il2CppClass, Il2CppError, Il2CppObjectRef, Il2CppTypeHandle, NonNullRef, Ref,
 };
 use std::ffi::{c_void, CStr};
 use std::{fmt, ptr, slice};
@@ -43,7 +44,7 @@ impl MethodInfo {
     /// Panics if the return type pointer is null
     #[track_caller]
     #[inline]
-    pub const fn return_type(self) -> NonNullRef<Il2CppType, ()> {
+    pub const fn return_type(self) -> Il2CppTypeHandle {
         Ref::new(self.as_ref().return_type as _).unwrap_non_null()
     }
 
@@ -55,12 +56,12 @@ impl MethodInfo {
 
     /// Returns the parameters
     #[inline]
-    pub const fn parameters<'a>(self) -> &'a [Ref<Il2CppType, ()>] {
+    pub const fn parameters<'a>(self) -> &'a [Il2CppTypeHandle] {
         if self.as_ref().parameters.is_null() {
             &[]
         } else {
             unsafe {
-                slice::from_raw_parts(*self.as_ref().parameters as _, self.parameters_count() as _)
+                slice::from_raw_parts(self.as_ref().parameters as _, self.parameters_count() as _)
             }
         }
     }
@@ -87,13 +88,32 @@ impl MethodInfo {
         }
     }
 
+    /// Returns the address of the native code of the method, if it has been compiled
+    ///
+    /// Generic methods shared or instantiated at runtime may have none.
+    #[inline]
+    pub fn address(self) -> Option<usize> {
+        self.as_ref().methodPointer.map(|pointer| pointer as usize)
+    }
+
     /// Returns the raw method flags
     #[inline]
     pub const fn flags(self) -> u32 {
-        // todo: there is also `iflags` (ImplementationFlags), need to research the purpose of each
         self.as_ref().flags as u32
     }
 
+    /// Returns the raw method implementation flags, `METHOD_IMPL_ATTRIBUTE_*`
+    #[inline]
+    pub const fn impl_flags(self) -> u32 {
+        self.as_ref().iflags as u32
+    }
+
+    /// Returns the method token
+    #[inline]
+    pub const fn token(self) -> u32 {
+        self.as_ref().token
+    }
+
     /// Field accessibility
     ///
     /// - 0x0000 - `[CompilerGenerated]`
@@ -132,26 +152,41 @@ impl MethodInfo {
         self.flags() & METHOD_ATTRIBUTE_ABSTRACT != 0
     }
 
+    /// Returns `true` the method is implemented by the runtime, `[MethodImpl(InternalCall)]`
+    #[inline]
+    pub const fn is_internal_call(self) -> bool {
+        self.impl_flags() & METHOD_IMPL_ATTRIBUTE_INTERNAL_CALL != 0
+    }
+
     /// Human-readable method signature
     ///
+    /// By-reference parameters are prefixed with `ref`, `in` or `out`.
+    ///
     /// # Warning
     ///
-    /// The result is indicative only. It does not include details such as
-    /// `ref`, `out`, or generic parameters.
+    /// The result is indicative only. It does not include details such as generic parameters.
     pub fn signature(self) -> String {
         let name = self.name().to_string_lossy();
-        let ret = unsafe {
-            let return_type = self.return_type();
-            CStr::from_ptr(il2cpp_type_get_name(return_type.as_ptr())).to_string_lossy()
-        };
+        let ret = self.return_type().name();
 
         let mut params = Vec::new();
         for (i, ptype) in self.parameters().iter().enumerate() {
-            let type_name =
-                unsafe { CStr::from_ptr(il2cpp_type_get_name(ptype.as_ptr())).to_string_lossy() };
+            let type_name = ptype.name();
             let name = self.param_name(i as u8).to_string_lossy();
 
-            params.push(format!("{type_name} {name}"));
+            if ptype.is_byref() {
+                let modifier = if ptype.is_out() {
+                    "out"
+                } else if ptype.is_in() {
+                    "in"
+                } else {
+                    "ref"
+                };
+                let type_name = type_name.strip_suffix('&').unwrap_or(&type_name);
+                params.push(format!("{modifier} {type_name} {name}"));
+            } else {
+                params.push(format!("{type_name} {name}"));
+            }
         }
 
         format!("{ret} {name}({})", params.join(", "))
@@ -163,12 +198,26 @@ impl MethodInfo {
     ///
     /// * `object` - Target instance, null for a static method
     /// * `arguments` - Mutable slice of argument pointers
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::ManagedException`] if the method throws
     #[inline]
     pub fn invoke<T>(
         self,
         object: Ref<T, ()>,
         arguments: &mut [*mut c_void],
-    ) -> Result<Ref<Il2CppObject, ()>, ExceptionRef> {
+    ) -> Result<Il2CppObjectRef, Il2CppError> {
+        self.invoke_raw(object, arguments).map_err(Il2CppError::from)
+    }
+
+    /// Invokes the method with arguments on a target object, returning the thrown exception
+    #[inline]
+    pub(crate) fn invoke_raw<T>(
+        self,
+        object: Ref<T, ()>,
+        arguments: &mut [*mut c_void],
+    ) -> Result<Il2CppObjectRef, Exception> {
         unsafe {
             let mut err = ptr::null_mut();
             let result = il2cpp_runtime_invoke(
@@ -177,10 +226,9 @@ impl MethodInfo {
                 arguments.as_mut_ptr(),
                 &mut err,
             );
-            if err.is_null() {
-                Ok(Ref::new(result))
-            } else {
-                Err(Ref::new(err))
+            match Exception::from_ptr(err) {
+                None => Ok(Ref::new(result)),
+                Some(exception) => Err(exception),
             }
         }
     }
@@ -209,6 +257,32 @@ impl MethodInfo {
     }
 }
 
+/// Method parameter type, matched by [`Il2CppClass::find_method_exact`]
+pub trait ParameterType {
+    /// Returns `true` if `type_` is this parameter type
+    fn matches(&self, type_: Il2CppTypeHandle) -> bool;
+}
+
+/// Matches the fully qualified type name, such as `System.Single` or `System.Int32&`
+impl ParameterType for &str {
+    #[inline]
+    fn matches(&self, type_: Il2CppTypeHandle) -> bool {
+        type_.name() == *self
+    }
+}
+
+impl ParameterType for Il2CppTypeHandle {
+    #[inline]
+    fn matches(&self, type_: Il2CppTypeHandle) -> bool {
+        *self == type_
+    }
+}
+
+/// Returns `true` if both methods have the same name and parameter types
+pub(crate) fn same_signature(method: MethodInfo, other: MethodInfo) -> bool {
+    method.name() == other.name() && method.parameters() == other.parameters()
+}
+
 impl fmt::Display for MethodInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.signature(), f)
@@ -220,3 +294,56 @@ impl fmt::Debug for MethodInfo {
         fmt::Debug::fmt(&self.signature(), f)
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Il2CppObject, Ref};
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn invoke() {
+        let image = mock::image(c"MethodTests.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Math")
+            .static_method(c"Twice", &[(c"value", corlib(c"Int32"))], corlib(c"Int32"), |call| {
+                let value = unsafe { call.arg::<i32>(0) };
+                Ok(Il2CppObject::box_value(corlib(c"Int32"), &(value * 2)).unwrap().into())
+            })
+            .build();
+
+        let method = class.find_method(c"Twice", 1).unwrap();
+        let mut args = [&mut 21_i32 as *mut i32 as _];
+        let result = method.invoke::<()>(Ref::null(), &mut args).unwrap();
+        assert_eq!(unsafe { result.unwrap_non_null().unbox::<i32>() }, 42);
+    }
+
+    #[test]
+    fn invoke_exceptions() {
+        let image = mock::image(c"MethodTestsThrow.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Thrower")
+            .static_method(c"Throw", &[], corlib(c"Void"), |_| Err(mock::exception("boom")))
+            .static_method(c"Panic", &[], corlib(c"Void"), |_| panic!("oops"))
+            .build();
+
+        let err = class
+            .find_method(c"Throw", 0)
+            .unwrap()
+            .invoke::<()>(Ref::null(), &mut [])
+            .unwrap_err();
+        let Il2CppError::ManagedException(exception) = err else {
+            panic!("expected a managed exception, found {err:?}");
+        };
+        assert_eq!(exception.target().to_string(), "System.Exception: boom");
+
+        // Panics of mock bodies are thrown as `System.Exception`
+        let err = class
+            .find_method(c"Panic", 0)
+            .unwrap()
+            .invoke::<()>(Ref::null(), &mut [])
+            .unwrap_err();
+        assert_eq!(err.to_string(), "managed exception: System.Exception: oops");
+    }
+}
@@ -0,0 +1,2580 @@
+//! In-process mock IL2CPP runtime
+//!
+//! Implements the `il2cpp_*` exports used by the safe wrappers on top of in-memory
+//! [`il2cpp_sys_rs`] structures, so they can be exercised without a Unity player.
+//!
+//! Classes are declared with [`ClassBuilder`] and method bodies are Rust closures.
+//! The mock core library provides the `System` primitives, `System.String` and `System.Exception`.
+//!
+//! The mock never frees memory: every image, class, object and string lives until the process exits.
+//!
+//! # Example
+//!
+//! ```
+//! use il2cpp_rs::mock::{self, ClassBuilder};
+//! use il2cpp_rs::{Il2CppClass, Il2CppImage, Il2CppObject, Ref};
+//!
+//! fn int32() -> Il2CppClass {
+//!     Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap()
+//! }
+//!
+//! let image = mock::image(c"Game.dll");
+//! ClassBuilder::new(image, c"Game", c"Math")
+//!     .static_method(c"Twice", &[(c"value", int32())], int32(), |call| {
+//!         let value = unsafe { call.arg::<i32>(0) };
+//!         Ok(Il2CppObject::box_value(int32(), &(value * 2)).unwrap().into())
+//!     })
+//!     .build();
+//!
+//! let method = image
+//!     .find_class(c"Game", c"Math")
+//!     .and_then(|class| class.find_method(c"Twice", 1))
+//!     .unwrap();
+//!
+//! let mut args = [&mut 21_i32 as *mut i32 as _];
+//! let result = method.invoke::<()>(Ref::null(), &mut args).unwrap();
+//! assert_eq!(unsafe { result.unwrap_non_null().unbox::<i32>() }, 42);
+//! ```
+
+use crate::flags::{
+    FIELD_ATTRIBUTE_PUBLIC, FIELD_ATTRIBUTE_STATIC, METHOD_ATTRIBUTE_PUBLIC,
+    METHOD_ATTRIBUTE_STATIC, METHOD_IMPL_ATTRIBUTE_INTERNAL_CALL, TYPE_ATTRIBUTE_ABSTRACT,
+    TYPE_ATTRIBUTE_INTERFACE, TYPE_ATTRIBUTE_PUBLIC, TYPE_ATTRIBUTE_SEALED,
+};
+use crate::{Exception, Il2CppClass, Il2CppImage, Il2CppRuntime, Ref};
+use il2cpp_sys_rs::{
+    il2cpp_array_size_t, EventInfo, FieldInfo, Il2CppArray, Il2CppArrayBounds, Il2CppArrayType,
+    Il2CppAssembly, Il2CppChar, Il2CppCustomAttrInfo, Il2CppDelegate, Il2CppDomain,
+    Il2CppException, Il2CppGenericClass, Il2CppGenericContainer, Il2CppGenericContext,
+    Il2CppGenericInst, Il2CppGenericMethod, Il2CppImage as RawImage, Il2CppMethodPointer,
+    Il2CppObject, Il2CppReflectionMethod, Il2CppReflectionType, Il2CppString, Il2CppType,
+    Il2CppTypeEnum, MethodInfo, PropertyInfo,
+    Il2CppTypeEnum_IL2CPP_TYPE_ARRAY, Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN,
+    Il2CppTypeEnum_IL2CPP_TYPE_CHAR, Il2CppTypeEnum_IL2CPP_TYPE_CLASS,
+    Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST,
+    Il2CppTypeEnum_IL2CPP_TYPE_I, Il2CppTypeEnum_IL2CPP_TYPE_I1, Il2CppTypeEnum_IL2CPP_TYPE_I2,
+    Il2CppTypeEnum_IL2CPP_TYPE_I4, Il2CppTypeEnum_IL2CPP_TYPE_I8,
+    Il2CppTypeEnum_IL2CPP_TYPE_OBJECT,
+    Il2CppTypeEnum_IL2CPP_TYPE_R4, Il2CppTypeEnum_IL2CPP_TYPE_R8,
+    Il2CppTypeEnum_IL2CPP_TYPE_STRING, Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY,
+    Il2CppTypeEnum_IL2CPP_TYPE_U, Il2CppTypeEnum_IL2CPP_TYPE_U1, Il2CppTypeEnum_IL2CPP_TYPE_U2,
+    Il2CppTypeEnum_IL2CPP_TYPE_U4, Il2CppTypeEnum_IL2CPP_TYPE_U8,
+    Il2CppTypeEnum_IL2CPP_TYPE_VALUETYPE, Il2CppTypeEnum_IL2CPP_TYPE_VOID,
+};
+use std::alloc::{self, Layout};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex, Once, OnceLock};
+use std::{mem, ptr, slice};
+
+/// Method body of a mock method
+pub type MethodBody =
+    dyn Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> + Send + Sync;
+
+/// Arguments of a mock method call
+#[derive(Clone, Copy)]
+pub struct Invocation<'a> {
+    /// Target instance, null for static methods
+    this: Ref<Il2CppObject, ()>,
+    /// Argument pointers, as passed to `il2cpp_runtime_invoke`
+    args: &'a [*mut c_void],
+}
+
+impl Invocation<'_> {
+    /// Returns the target instance, null for static methods
+    #[inline]
+    pub const fn this(&self) -> Ref<Il2CppObject, ()> {
+        self.this
+    }
+
+    /// Returns the raw argument pointers
+    #[inline]
+    pub const fn args(&self) -> &[*mut c_void] {
+        self.args
+    }
+
+    /// Reads a value type argument
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must be a value of type `T`
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index >= args.len()`
+    #[track_caller]
+    #[inline]
+    pub unsafe fn arg<T: Copy>(&self, index: usize) -> T {
+        unsafe { (self.args[index] as *const T).read_unaligned() }
+    }
+
+    /// Reads a reference type argument
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index >= args.len()`
+    #[track_caller]
+    #[inline]
+    pub fn arg_object<T>(&self, index: usize) -> Ref<T, ()> {
+        Ref::new(self.args[index] as _)
+    }
+}
+
+/// Pointer to leaked mock memory, shared across threads
+struct Leaked<T>(*mut T);
+
+impl<T> Clone for Leaked<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Leaked<T> {}
+
+// Safety: leaked mock memory is never freed
+unsafe impl<T> Send for Leaked<T> {}
+unsafe impl<T> Sync for Leaked<T> {}
+
+/// Mock image, the raw image is the first field so both pointers are interchangeable
+#[repr(C)]
+struct MockImage {
+    image: RawImage,
+    classes: Mutex<Vec<Leaked<MockClass>>>,
+}
+
+/// Mock class, the raw class is the first field so both pointers are interchangeable
+#[repr(C)]
+struct MockClass {
+    class: il2cpp_sys_rs::Il2CppClass,
+    /// Size of the unboxed value for value types
+    value_size: usize,
+    /// Number of type parameters of generic definitions
+    generic_arity: usize,
+}
+
+/// Mock method, the raw method is the first field so both pointers are interchangeable
+#[repr(C)]
+struct MockMethod {
+    method: MethodInfo,
+    param_names: Vec<CString>,
+    /// Shared with the methods inflated from a generic definition
+    body: Arc<MethodBody>,
+}
+
+/// Custom attributes returned by `il2cpp_custom_attrs_from_*`
+struct MockAttrInfo {
+    attributes: Vec<Leaked<MockClass>>,
+}
+
+/// Mock `System.Reflection.FieldInfo` and `System.Reflection.PropertyInfo` objects
+#[repr(C)]
+struct ReflectionMember {
+    object: Il2CppObject,
+    /// Raw `FieldInfo` or `PropertyInfo`
+    member: usize,
+}
+
+/// Mutable mock domain state
+#[derive(Default)]
+struct State {
+    /// Registered assemblies
+    assemblies: Vec<Leaked<Il2CppAssembly>>,
+    /// Array classes by element class, rank and boundedness
+    array_classes: HashMap<(usize, u32, bool), Leaked<MockClass>>,
+    /// Intern pool
+    interned: HashMap<Vec<Il2CppChar>, Leaked<Il2CppString>>,
+    /// Inflated classes by generic definition and type argument classes
+    inflated_classes: HashMap<(usize, Vec<usize>), Leaked<MockClass>>,
+    /// `System.Type` objects by class
+    type_objects: HashMap<usize, Leaked<Il2CppReflectionType>>,
+    /// Inflated methods by generic definition and type argument classes
+    inflated_methods: HashMap<(usize, Vec<usize>), Leaked<MockMethod>>,
+    /// `System.Reflection.MethodInfo` objects by method
+    method_objects: HashMap<usize, Leaked<Il2CppReflectionMethod>>,
+    /// Internal call implementations by name
+    icalls: HashMap<CString, usize>,
+    /// Custom attribute classes by class, method, field or property
+    attributes: HashMap<usize, Vec<Leaked<MockClass>>>,
+    /// GC handle targets by handle, weak targets are cleared by [`collect`]
+    gc_handles: HashMap<u32, GcHandleEntry>,
+    /// Last allocated GC handle
+    last_gc_handle: u32,
+}
+
+/// Target of a mock GC handle
+struct GcHandleEntry {
+    target: Leaked<Il2CppObject>,
+    weak: bool,
+}
+
+/// Mock domain state
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(Mutex::default);
+
+/// Mock core library classes
+struct Corlib {
+    image: Leaked<MockImage>,
+    object: Il2CppClass,
+    value_type: Il2CppClass,
+    string: Il2CppClass,
+    exception: Il2CppClass,
+}
+
+// Safety: mock classes are leaked and never mutated after creation
+unsafe impl Send for Corlib {}
+unsafe impl Sync for Corlib {}
+
+/// Mock core library, created by [`install`]
+static CORLIB: OnceLock<Corlib> = OnceLock::new();
+
+/// Mock reflection classes
+struct Reflection {
+    /// `System.RuntimeType`
+    runtime_type: Leaked<MockClass>,
+    /// `System.Reflection.RuntimeMethodInfo`
+    runtime_method: Leaked<MockClass>,
+    /// `System.Reflection.RuntimeFieldInfo`
+    runtime_field: Leaked<MockClass>,
+    /// `System.Reflection.RuntimePropertyInfo`
+    runtime_property: Leaked<MockClass>,
+}
+
+/// Mock reflection classes, created by [`install`]
+static REFLECTION: OnceLock<Reflection> = OnceLock::new();
+
+/// Next metadata token
+static NEXT_TOKEN: AtomicU32 = AtomicU32::new(1);
+
+/// Installs the mock runtime
+///
+/// Can be called any number of times, the runtime is installed once.
+///
+/// # Panics
+///
+/// Panics if another runtime is already installed
+#[track_caller]
+pub fn install() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        CORLIB.get_or_init(build_corlib);
+        // Safety: every resolved export is implemented below with the matching signature
+        unsafe { Il2CppRuntime::from_resolver(exports::resolve) }
+            .and_then(Il2CppRuntime::install)
+            .expect("failed to install the mock runtime");
+        REFLECTION.get_or_init(build_reflection);
+    });
+}
+
+/// Registers a new empty image and its assembly
+///
+/// Installs the mock runtime if needed.
+///
+/// # Arguments
+///
+/// * `name` - Image file name, such as `Game.dll`
+pub fn image(name: &CStr) -> Il2CppImage {
+    install();
+    new_image(name)
+}
+
+/// Simulates a garbage collection
+///
+/// The mock never frees objects, but weak GC handles to objects that are not held by a strong
+/// GC handle are cleared, as if their target was collected.
+pub fn collect() {
+    let mut state = STATE.lock().unwrap();
+    let alive: Vec<_> = state
+        .gc_handles
+        .values()
+        .filter(|entry| !entry.weak)
+        .map(|entry| entry.target.0)
+        .collect();
+
+    for entry in state.gc_handles.values_mut() {
+        if entry.weak && !alive.contains(&entry.target.0) {
+            entry.target = Leaked(ptr::null_mut());
+        }
+    }
+}
+
+/// Creates a `System.Exception` with a message
+///
+/// Installs the mock runtime if needed.
+///
+/// # Arguments
+///
+/// * `message` - Exception message
+pub fn exception(message: &str) -> Exception {
+    install();
+    let corlib = CORLIB.get().unwrap();
+
+    unsafe {
+        let exception = new_object(corlib.exception.as_ptr()) as *mut Il2CppException;
+        (*exception).message = new_string(&message.encode_utf16().collect::<Vec<_>>());
+        Exception::from_ptr(exception).unwrap()
+    }
+}
+
+/// Method declared by a [`ClassBuilder`]
+struct MethodDefinition {
+    name: CString,
+    params: Vec<(CString, Il2CppClass)>,
+    return_type: Il2CppClass,
+    flags: u32,
+    impl_flags: u32,
+    generic_arity: usize,
+    body: Arc<MethodBody>,
+}
+
+/// Mock class builder
+///
+/// Instance fields are laid out after the object header in declaration order.
+pub struct ClassBuilder {
+    image: Il2CppImage,
+    namespace: CString,
+    name: CString,
+    flags: u32,
+    parent: Option<Il2CppClass>,
+    declaring_type: Option<Il2CppClass>,
+    interfaces: Vec<Il2CppClass>,
+    /// Type enum and value size of value types
+    value_type: Option<(Il2CppTypeEnum, usize)>,
+    /// Type enum of reference types
+    type_enum: Il2CppTypeEnum,
+    /// Whether the class has no parent, only for `System.Object`
+    root: bool,
+    /// Number of type parameters of generic definitions
+    generic_arity: usize,
+    fields: Vec<(CString, Il2CppClass, u32)>,
+    methods: Vec<MethodDefinition>,
+    properties: Vec<(CString, Option<CString>, Option<CString>)>,
+    events: Vec<(CString, Il2CppClass, CString, CString)>,
+    /// Custom attributes of the class, or of its members by name
+    attributes: Vec<(Option<CString>, Il2CppClass)>,
+}
+
+impl ClassBuilder {
+    /// Starts a public reference type declaration
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Image containing the class, see [`image`]
+    /// * `namespace` - Namespace of the class, empty for global
+    /// * `name` - Class name
+    pub fn new(image: Il2CppImage, namespace: &CStr, name: &CStr) -> Self {
+        Self {
+            image,
+            namespace: namespace.to_owned(),
+            name: name.to_owned(),
+            flags: TYPE_ATTRIBUTE_PUBLIC,
+            parent: None,
+            declaring_type: None,
+            interfaces: Vec::new(),
+            value_type: None,
+            type_enum: Il2CppTypeEnum_IL2CPP_TYPE_CLASS,
+            root: false,
+            generic_arity: 0,
+            fields: Vec::new(),
+            methods: Vec::new(),
+            properties: Vec::new(),
+            events: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Sets the base class, `System.Object` or `System.ValueType` by default
+    pub fn parent(mut self, parent: Il2CppClass) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Declares the class as nested in `declaring_type`
+    pub fn nested_in(mut self, declaring_type: Il2CppClass) -> Self {
+        self.declaring_type = Some(declaring_type);
+        self
+    }
+
+    /// Adds an implemented interface
+    pub fn interface(mut self, interface: Il2CppClass) -> Self {
+        self.interfaces.push(interface);
+        self
+    }
+
+    /// Declares the class as a sealed value type
+    pub fn value_type(mut self) -> Self {
+        self.value_type = Some((Il2CppTypeEnum_IL2CPP_TYPE_VALUETYPE, 0));
+        self.flags |= TYPE_ATTRIBUTE_SEALED;
+        self
+    }
+
+    /// Declares the class as an interface
+    pub fn interface_type(mut self) -> Self {
+        self.flags |= TYPE_ATTRIBUTE_INTERFACE | TYPE_ATTRIBUTE_ABSTRACT;
+        self
+    }
+
+    /// Declares the class as abstract
+    pub fn abstract_type(mut self) -> Self {
+        self.flags |= TYPE_ATTRIBUTE_ABSTRACT;
+        self
+    }
+
+    /// Declares the class as a generic definition with `arity` type parameters
+    ///
+    /// The class name should end with the arity suffix, such as ``List`1``.
+    /// Inflated classes share the fields and methods of their definition.
+    pub fn generic(mut self, arity: usize) -> Self {
+        self.generic_arity = arity;
+        self
+    }
+
+    /// Adds a public instance field
+    pub fn field(mut self, name: &CStr, type_: Il2CppClass) -> Self {
+        self.fields
+            .push((name.to_owned(), type_, FIELD_ATTRIBUTE_PUBLIC));
+        self
+    }
+
+    /// Adds a public static field
+    pub fn static_field(mut self, name: &CStr, type_: Il2CppClass) -> Self {
+        self.fields.push((
+            name.to_owned(),
+            type_,
+            FIELD_ATTRIBUTE_PUBLIC | FIELD_ATTRIBUTE_STATIC,
+        ));
+        self
+    }
+
+    /// Adds a public instance method
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    /// * `body` - Method implementation
+    pub fn method(
+        self,
+        name: &CStr,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        body: impl Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.method_with_flags(name, params, return_type, METHOD_ATTRIBUTE_PUBLIC, body)
+    }
+
+    /// Adds a public static method
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    /// * `body` - Method implementation
+    pub fn static_method(
+        self,
+        name: &CStr,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        body: impl Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.method_with_flags(
+            name,
+            params,
+            return_type,
+            METHOD_ATTRIBUTE_PUBLIC | METHOD_ATTRIBUTE_STATIC,
+            body,
+        )
+    }
+
+    /// Adds a method with explicit `METHOD_ATTRIBUTE_*` flags
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    /// * `flags` - Method flags
+    /// * `body` - Method implementation
+    pub fn method_with_flags(
+        self,
+        name: &CStr,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        flags: u32,
+        body: impl Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.declare_method(name, params, return_type, flags, 0, Arc::new(body))
+    }
+
+    /// Adds a public generic instance method definition
+    ///
+    /// Parameter and return types are not substituted when the method is inflated, so
+    /// type parameters should be declared as `System.Object`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `arity` - Number of type parameters
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    /// * `body` - Method implementation, shared by every instantiation
+    pub fn generic_method(
+        self,
+        name: &CStr,
+        arity: usize,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        body: impl Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        let flags = METHOD_ATTRIBUTE_PUBLIC;
+        self.declare_method(name, params, return_type, flags, arity, Arc::new(body))
+    }
+
+    /// Adds a public generic static method definition
+    ///
+    /// See [`ClassBuilder::generic_method`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `arity` - Number of type parameters
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    /// * `body` - Method implementation, shared by every instantiation
+    pub fn generic_static_method(
+        self,
+        name: &CStr,
+        arity: usize,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        body: impl Fn(Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        let flags = METHOD_ATTRIBUTE_PUBLIC | METHOD_ATTRIBUTE_STATIC;
+        self.declare_method(name, params, return_type, flags, arity, Arc::new(body))
+    }
+
+    /// Adds a public static method implemented by an internal call
+    ///
+    /// The mock cannot invoke native functions, invoking the method raises an exception.
+    /// Its implementation is registered and resolved through [`crate::icall`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Method name
+    /// * `params` - Parameter names and types
+    /// * `return_type` - Return type, `System.Void` for none
+    pub fn internal_call(
+        mut self,
+        name: &CStr,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+    ) -> Self {
+        self = self.declare_method(
+            name,
+            params,
+            return_type,
+            METHOD_ATTRIBUTE_PUBLIC | METHOD_ATTRIBUTE_STATIC,
+            0,
+            Arc::new(|_| Err(exception("NotSupportedException: mock internal call"))),
+        );
+        self.methods.last_mut().unwrap().impl_flags = METHOD_IMPL_ATTRIBUTE_INTERNAL_CALL;
+        self
+    }
+
+    fn declare_method(
+        mut self,
+        name: &CStr,
+        params: &[(&CStr, Il2CppClass)],
+        return_type: Il2CppClass,
+        flags: u32,
+        generic_arity: usize,
+        body: Arc<MethodBody>,
+    ) -> Self {
+        let params = params
+            .iter()
+            .map(|&(name, type_)| (name.to_owned(), type_))
+            .collect();
+        self.methods.push(MethodDefinition {
+            name: name.to_owned(),
+            params,
+            return_type,
+            flags,
+            impl_flags: 0,
+            generic_arity,
+            body,
+        });
+        self
+    }
+
+    /// Makes the class a delegate type
+    ///
+    /// Derives from `System.MulticastDelegate` and declares an `Invoke` method calling the
+    /// delegate method through its invoker.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameter names and types of `Invoke`
+    /// * `return_type` - Return type of `Invoke`, `System.Void` for none
+    pub fn delegate(self, params: &[(&CStr, Il2CppClass)], return_type: Il2CppClass) -> Self {
+        let multicast_delegate = Il2CppImage::corlib()
+            .find_class(c"System", c"MulticastDelegate")
+            .unwrap();
+        self.parent(multicast_delegate)
+            .method(c"Invoke", params, return_type, invoke_delegate)
+    }
+
+    /// Adds a property backed by methods of this class
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Property name
+    /// * `getter` - Getter method name, such as `get_Value`
+    /// * `setter` - Setter method name, such as `set_Value`
+    pub fn property(mut self, name: &CStr, getter: Option<&CStr>, setter: Option<&CStr>) -> Self {
+        self.properties.push((
+            name.to_owned(),
+            getter.map(CStr::to_owned),
+            setter.map(CStr::to_owned),
+        ));
+        self
+    }
+
+    /// Adds an event backed by methods of this class
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Event name
+    /// * `type_` - Delegate type of the handlers
+    /// * `add` - Add method name, such as `add_Changed`
+    /// * `remove` - Remove method name, such as `remove_Changed`
+    pub fn event(mut self, name: &CStr, type_: Il2CppClass, add: &CStr, remove: &CStr) -> Self {
+        self.events
+            .push((name.to_owned(), type_, add.to_owned(), remove.to_owned()));
+        self
+    }
+
+    /// Adds a custom attribute to the class
+    pub fn attribute(mut self, attribute: Il2CppClass) -> Self {
+        self.attributes.push((None, attribute));
+        self
+    }
+
+    /// Adds a custom attribute to the fields, properties and methods named `member`
+    ///
+    /// Reading the attributes of a field or property throws if `attribute` is abstract.
+    pub fn member_attribute(mut self, member: &CStr, attribute: Il2CppClass) -> Self {
+        self.attributes.push((Some(member.to_owned()), attribute));
+        self
+    }
+
+    /// Declares a primitive value type of `size` bytes
+    fn primitive(mut self, type_enum: Il2CppTypeEnum, size: usize) -> Self {
+        self.value_type = Some((type_enum, size));
+        self.flags |= TYPE_ATTRIBUTE_SEALED;
+        self
+    }
+
+    /// Sets the type enum of a reference type
+    fn type_enum(mut self, type_enum: Il2CppTypeEnum) -> Self {
+        self.type_enum = type_enum;
+        self
+    }
+
+    /// Allocates the class and registers it in its image
+    pub fn build(self) -> Il2CppClass {
+        let header = mem::size_of::<Il2CppObject>();
+        let parent = if self.root {
+            None
+        } else {
+            self.parent.or_else(|| {
+                let corlib = CORLIB.get().expect("mock corlib is not initialized");
+                Some(if self.value_type.is_some() {
+                    corlib.value_type
+                } else {
+                    corlib.object
+                })
+            })
+        };
+
+        unsafe {
+            let mock = leak(MockClass {
+                class: mem::zeroed(),
+                value_size: 0,
+                generic_arity: self.generic_arity,
+            });
+            let class = &mut (*mock).class;
+
+            class.image = self.image.as_ptr();
+            class.name = self.name.into_raw();
+            class.namespaze = self.namespace.into_raw();
+            class.flags = self.flags;
+            class.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+            class.parent = parent.map_or(ptr::null_mut(), Il2CppClass::as_ptr);
+            class.declaringType = self.declaring_type.map_or(ptr::null_mut(), Il2CppClass::as_ptr);
+
+            // Type
+            let type_enum = self.value_type.map_or(self.type_enum, |(type_enum, _)| type_enum);
+            class.byval_arg.data.dummy = mock as _;
+            class.byval_arg.set_type_(type_enum);
+            class.byval_arg.set_valuetype(self.value_type.is_some() as _);
+            class.this_arg = class.byval_arg;
+            class.this_arg.set_byref(1);
+
+            // Interfaces
+            if !self.interfaces.is_empty() {
+                class.interfaces_count = self.interfaces.len() as u16;
+                class.implementedInterfaces = leak_slice(
+                    self.interfaces.iter().map(|interface| interface.as_ptr()).collect(),
+                );
+            }
+
+            // Fields
+            let mut instance_size = parent.map_or(header, |parent| {
+                if self.value_type.is_some() {
+                    header
+                } else {
+                    parent.as_ref().instance_size as usize
+                }
+            });
+            let mut static_size = 0_usize;
+            let mut fields = Vec::with_capacity(self.fields.len());
+            for (name, type_, flags) in self.fields {
+                let size = type_size(&type_.as_ref().byval_arg);
+                let field_type = leak(type_.as_ref().byval_arg);
+                (*field_type).set_attrs(flags);
+
+                let offset = if flags & FIELD_ATTRIBUTE_STATIC != 0 {
+                    static_size = static_size.next_multiple_of(size.clamp(1, 8));
+                    static_size += size;
+                    static_size - size
+                } else {
+                    instance_size = instance_size.next_multiple_of(size.clamp(1, 8));
+                    instance_size += size;
+                    instance_size - size
+                };
+
+                let mut field: FieldInfo = mem::zeroed();
+                field.name = name.into_raw();
+                field.type_ = field_type;
+                field.parent = mock as _;
+                field.offset = offset as i32;
+                field.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+                fields.push(field);
+            }
+            class.field_count = fields.len() as u16;
+            class.fields = leak_slice(fields);
+            class.static_fields = alloc_zeroed(static_size) as _;
+            class.static_fields_size = static_size as u32;
+
+            if let Some((_, size)) = self.value_type {
+                (*mock).value_size = size.max(instance_size - header);
+                instance_size = header + (*mock).value_size;
+            }
+            class.instance_size = instance_size as u32;
+            class.actualSize = instance_size as u32;
+
+            // Methods
+            let mut methods = Vec::with_capacity(self.methods.len());
+            for MethodDefinition {
+                name,
+                params,
+                return_type,
+                flags,
+                impl_flags,
+                generic_arity,
+                body,
+            } in self.methods
+            {
+                let mut method: MethodInfo = mem::zeroed();
+                method.name = name.into_raw();
+                method.klass = mock as _;
+                method.return_type = &return_type.as_ref().byval_arg;
+                method.parameters_count = params.len() as u8;
+                method.parameters = leak_slice(
+                    params
+                        .iter()
+                        .map(|(_, type_)| &type_.as_ref().byval_arg as *const Il2CppType)
+                        .collect(),
+                );
+                method.flags = flags as u16;
+                method.iflags = impl_flags as u16;
+                method.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+                if generic_arity > 0 {
+                    method.set_is_generic(1);
+                    method.__bindgen_anon_2.genericContainerHandle =
+                        leak(Il2CppGenericContainer {
+                            ownerIndex: 0,
+                            type_argc: generic_arity as i32,
+                            is_method: 1,
+                            genericParameterStart: 0,
+                        }) as _;
+                }
+
+                let method = leak(MockMethod {
+                    method,
+                    param_names: params.into_iter().map(|(name, _)| name).collect(),
+                    body,
+                });
+                methods.push(method as *const MethodInfo);
+            }
+            class.method_count = methods.len() as u16;
+            class.methods = leak_slice(methods);
+
+            // Properties
+            let find_method = |name: &CStr| {
+                (0..class.method_count as usize)
+                    .map(|i| *class.methods.add(i))
+                    .find(|&method| CStr::from_ptr((*method).name) == name)
+                    .expect("accessor is not a method of the class")
+            };
+            let mut properties = Vec::with_capacity(self.properties.len());
+            for (name, getter, setter) in self.properties {
+                let mut property: PropertyInfo = mem::zeroed();
+                property.parent = mock as _;
+                property.name = name.into_raw();
+                property.get = getter.map_or(ptr::null(), |name| find_method(&name));
+                property.set = setter.map_or(ptr::null(), |name| find_method(&name));
+                property.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+                properties.push(property);
+            }
+            class.property_count = properties.len() as u16;
+            class.properties = leak_slice(properties);
+
+            // Events
+            let mut events = Vec::with_capacity(self.events.len());
+            for (name, type_, add, remove) in self.events {
+                let mut event: EventInfo = mem::zeroed();
+                event.parent = mock as _;
+                event.name = name.into_raw();
+                event.eventType = &type_.as_ref().byval_arg;
+                event.add = find_method(&add);
+                event.remove = find_method(&remove);
+                event.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+                events.push(event);
+            }
+            class.event_count = events.len() as u16;
+            class.events = leak_slice(events);
+
+            // Custom attributes
+            let mut state = STATE.lock().unwrap();
+            for (member, attribute) in self.attributes {
+                let attribute = Leaked(attribute.as_ptr() as *mut MockClass);
+                let members: Vec<usize> = match member {
+                    None => vec![mock as usize],
+                    Some(member) => {
+                        let fields = (0..class.field_count as usize).map(|i| class.fields.add(i));
+                        let fields = fields
+                            .filter(|&field| CStr::from_ptr((*field).name) == member.as_c_str())
+                            .map(|field| field as usize);
+                        let properties = (0..class.property_count as usize)
+                            .map(|i| class.properties.add(i))
+                            .filter(|&property| {
+                                CStr::from_ptr((*property).name) == member.as_c_str()
+                            })
+                            .map(|property| property as usize);
+                        let methods = (0..class.method_count as usize)
+                            .map(|i| *class.methods.add(i))
+                            .filter(|&method| CStr::from_ptr((*method).name) == member.as_c_str())
+                            .map(|method| method as usize);
+                        fields.chain(properties).chain(methods).collect()
+                    }
+                };
+                assert!(!members.is_empty(), "attribute target is not a member of the class");
+                for member in members {
+                    state.attributes.entry(member).or_default().push(attribute);
+                }
+            }
+            drop(state);
+
+            register_class(self.image, mock);
+            Il2CppClass::from_ptr(mock as _).unwrap()
+        }
+    }
+}
+
+/// Leaks a value
+fn leak<T>(value: T) -> *mut T {
+    Box::into_raw(Box::new(value))
+}
+
+/// Leaks a vector, returns null for an empty vector
+fn leak_slice<T>(values: Vec<T>) -> *mut T {
+    if values.is_empty() {
+        ptr::null_mut()
+    } else {
+        Box::leak(values.into_boxed_slice()).as_mut_ptr()
+    }
+}
+
+/// Allocates zeroed memory aligned for any IL2CPP structure
+fn alloc_zeroed(size: usize) -> *mut u8 {
+    let layout = Layout::from_size_align(size.max(1), 16).unwrap();
+    let ptr = unsafe { alloc::alloc_zeroed(layout) };
+    if ptr.is_null() {
+        alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// Allocates a new object of `class`
+///
+/// # Safety
+///
+/// `class` must be a mock class
+unsafe fn new_object(class: *mut il2cpp_sys_rs::Il2CppClass) -> *mut Il2CppObject {
+    unsafe {
+        let object = alloc_zeroed((*class).instance_size as usize) as *mut Il2CppObject;
+        (*object).__bindgen_anon_1.klass = class;
+        object
+    }
+}
+
+/// Allocates a new `System.String`
+fn new_string(chars: &[Il2CppChar]) -> *mut Il2CppString {
+    let corlib = CORLIB.get().expect("mock corlib is not initialized");
+    let size = mem::offset_of!(Il2CppString, chars) + (chars.len() + 1) * size_of::<Il2CppChar>();
+
+    unsafe {
+        let string = alloc_zeroed(size) as *mut Il2CppString;
+        (*string).object.__bindgen_anon_1.klass = corlib.string.as_ptr();
+        (*string).length = chars.len() as i32;
+        ptr::copy_nonoverlapping(
+            chars.as_ptr(),
+            (*string).chars.as_mut_ptr(),
+            chars.len(),
+        );
+        string
+    }
+}
+
+/// Allocates a new image and its assembly
+fn new_image(name: &CStr) -> Il2CppImage {
+    let name_no_ext = name
+        .to_str()
+        .ok()
+        .and_then(|name| name.strip_suffix(".dll"))
+        .map_or_else(|| name.to_owned(), |name| CString::new(name).unwrap());
+
+    unsafe {
+        let image = leak(MockImage {
+            image: mem::zeroed(),
+            classes: Mutex::default(),
+        });
+        let assembly = leak(mem::zeroed::<Il2CppAssembly>());
+
+        (*image).image.name = name.to_owned().into_raw();
+        (*image).image.nameNoExt = name_no_ext.clone().into_raw();
+        (*image).image.assembly = assembly;
+        (*image).image.token = NEXT_TOKEN.fetch_add(1, Ordering::Relaxed);
+        (*assembly).image = image as _;
+        (*assembly).aname.name = name_no_ext.into_raw();
+        (*assembly).token = (*image).image.token;
+
+        STATE.lock().unwrap().assemblies.push(Leaked(assembly));
+        Il2CppImage::from_ptr(image as _).unwrap()
+    }
+}
+
+/// Adds a class to its image
+///
+/// # Safety
+///
+/// `image` must be a mock image
+unsafe fn register_class(image: Il2CppImage, class: *mut MockClass) {
+    let image = image.as_ptr() as *mut MockImage;
+    unsafe {
+        let mut classes = (*image).classes.lock().unwrap();
+        classes.push(Leaked(class));
+        (*image).image.typeCount = classes.len() as u32;
+    }
+}
+
+/// Returns the class of a mock type
+///
+/// # Safety
+///
+/// `type_` must be a mock type
+#[allow(non_upper_case_globals)]
+unsafe fn type_class(type_: *const Il2CppType) -> *mut il2cpp_sys_rs::Il2CppClass {
+    unsafe {
+        let type_ = &*type_;
+        match type_.type_() {
+            Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY => {
+                array_class(type_class(type_.data.type_), 1, false) as _
+            }
+            Il2CppTypeEnum_IL2CPP_TYPE_ARRAY => {
+                let array = &*type_.data.array;
+                array_class(type_class(array.etype), array.rank as u32, true) as _
+            }
+            Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST => (*type_.data.generic_class).cached_class,
+            _ => type_.data.dummy as _,
+        }
+    }
+}
+
+/// Returns the size of a value of a mock type, as stored in a field or array
+///
+/// # Safety
+///
+/// `type_` must be a mock type
+#[allow(non_upper_case_globals)]
+unsafe fn type_size(type_: *const Il2CppType) -> usize {
+    unsafe {
+        match (*type_).type_() {
+            Il2CppTypeEnum_IL2CPP_TYPE_VALUETYPE => {
+                (*(type_class(type_) as *mut MockClass)).value_size
+            }
+            Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST if (*type_).valuetype() != 0 => {
+                (*(type_class(type_) as *mut MockClass)).value_size
+            }
+            Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN
+            | Il2CppTypeEnum_IL2CPP_TYPE_I1
+            | Il2CppTypeEnum_IL2CPP_TYPE_U1 => 1,
+            Il2CppTypeEnum_IL2CPP_TYPE_CHAR
+            | Il2CppTypeEnum_IL2CPP_TYPE_I2
+            | Il2CppTypeEnum_IL2CPP_TYPE_U2 => 2,
+            Il2CppTypeEnum_IL2CPP_TYPE_I4
+            | Il2CppTypeEnum_IL2CPP_TYPE_U4
+            | Il2CppTypeEnum_IL2CPP_TYPE_R4 => 4,
+            Il2CppTypeEnum_IL2CPP_TYPE_I8
+            | Il2CppTypeEnum_IL2CPP_TYPE_U8
+            | Il2CppTypeEnum_IL2CPP_TYPE_R8 => 8,
+            _ => size_of::<usize>(),
+        }
+    }
+}
+
+/// Returns the element size of arrays of `class`
+///
+/// # Safety
+///
+/// `class` must be a mock class
+unsafe fn element_size(class: *const il2cpp_sys_rs::Il2CppClass) -> usize {
+    unsafe { type_size(&(*class).byval_arg) }
+}
+
+/// Returns the full name of a mock type
+///
+/// # Safety
+///
+/// `type_` must be a mock type
+#[allow(non_upper_case_globals)]
+unsafe fn type_name(type_: *const Il2CppType) -> String {
+    unsafe {
+        let type_ = &*type_;
+        if type_.byref() != 0 {
+            let mut byval = *type_;
+            byval.set_byref(0);
+            return format!("{}&", type_name(&byval));
+        }
+
+        match type_.type_() {
+            Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY => format!("{}[]", type_name(type_.data.type_)),
+            Il2CppTypeEnum_IL2CPP_TYPE_ARRAY => {
+                let array = &*type_.data.array;
+                let commas = ",".repeat(array.rank as usize - 1);
+                format!("{}[{commas}]", type_name(array.etype))
+            }
+            Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST => {
+                let generic_class = &*type_.data.generic_class;
+                let definition = type_name(generic_class.type_);
+                let definition = definition.split_once('`').map_or(&*definition, |(name, _)| name);
+                let inst = &*generic_class.context.class_inst;
+                let arguments: Vec<_> = (0..inst.type_argc as usize)
+                    .map(|i| type_name(*inst.type_argv.add(i)))
+                    .collect();
+                format!("{definition}<{}>", arguments.join(","))
+            }
+            _ => Il2CppClass::from_ptr(type_class(type_))
+                .unwrap()
+                .qualified_name('/')
+                .into_owned(),
+        }
+    }
+}
+
+/// Returns the array class of an element class, creating it on first use
+///
+/// # Safety
+///
+/// `element_class` must be a mock class
+unsafe fn array_class(
+    element_class: *mut il2cpp_sys_rs::Il2CppClass,
+    rank: u32,
+    bounded: bool,
+) -> *mut MockClass {
+    let key = (element_class as usize, rank, bounded);
+    if let Some(class) = STATE.lock().unwrap().array_classes.get(&key) {
+        return class.0;
+    }
+
+    let corlib = CORLIB.get().expect("mock corlib is not initialized");
+    let mock = unsafe {
+        let element = &*element_class;
+        let commas = ",".repeat(rank as usize - 1);
+        let name = format!("{}[{commas}]", CStr::from_ptr(element.name).to_string_lossy());
+
+        let mock = leak(MockClass {
+            class: mem::zeroed(),
+            value_size: 0,
+            generic_arity: 0,
+        });
+        let class = &mut (*mock).class;
+        class.image = element.image;
+        class.name = CString::new(name).unwrap().into_raw();
+        class.namespaze = element.namespaze;
+        class.flags = TYPE_ATTRIBUTE_PUBLIC | TYPE_ATTRIBUTE_SEALED;
+        class.parent = corlib.object.as_ptr();
+        class.element_class = element_class;
+        class.castClass = element_class;
+        class.rank = rank as u8;
+        class.element_size = element_size(element_class) as u32;
+        class.instance_size = size_of::<Il2CppArray>() as u32;
+
+        if rank == 1 && !bounded {
+            class.byval_arg.data.type_ = &element.byval_arg;
+            class.byval_arg.set_type_(Il2CppTypeEnum_IL2CPP_TYPE_SZARRAY);
+        } else {
+            let mut array: Il2CppArrayType = mem::zeroed();
+            array.etype = &element.byval_arg;
+            array.rank = rank as u8;
+            class.byval_arg.data.array = leak(array);
+            class.byval_arg.set_type_(Il2CppTypeEnum_IL2CPP_TYPE_ARRAY);
+        }
+        class.this_arg = class.byval_arg;
+        class.this_arg.set_byref(1);
+        mock
+    };
+
+    // Another thread may have created the same class in the meantime
+    STATE
+        .lock()
+        .unwrap()
+        .array_classes
+        .entry(key)
+        .or_insert(Leaked(mock))
+        .0
+}
+
+/// Allocates a new array of `array_class`
+///
+/// # Safety
+///
+/// `array_class` must be a mock array class
+unsafe fn new_array(
+    array_class: *mut il2cpp_sys_rs::Il2CppClass,
+    lengths: &[il2cpp_array_size_t],
+    lower_bounds: Option<&[il2cpp_array_size_t]>,
+) -> *mut Il2CppArray {
+    unsafe {
+        let len = lengths.iter().product::<il2cpp_array_size_t>();
+        let size = size_of::<Il2CppArray>() + len * (*array_class).element_size as usize;
+        let array = new_object_sized(array_class, size) as *mut Il2CppArray;
+        (*array).max_length = len;
+
+        if let Some(lower_bounds) = lower_bounds {
+            let bounds = lengths
+                .iter()
+                .zip(lower_bounds)
+                .map(|(&length, &lower_bound)| Il2CppArrayBounds {
+                    length,
+                    lower_bound: lower_bound as _,
+                })
+                .collect();
+            (*array).bounds = leak_slice(bounds);
+        }
+        array
+    }
+}
+
+/// Returns the class inflating a generic definition, creating it on first use
+///
+/// # Safety
+///
+/// `definition` must be a mock generic definition and `arguments` mock types
+unsafe fn inflate(definition: *mut MockClass, arguments: &[*const Il2CppType]) -> *mut MockClass {
+    let key = (
+        definition as usize,
+        arguments.iter().map(|&type_| unsafe { type_class(type_) } as usize).collect(),
+    );
+    let mut state = STATE.lock().unwrap();
+    if let Some(class) = state.inflated_classes.get(&key) {
+        return class.0;
+    }
+
+    unsafe {
+        let mock = leak(MockClass {
+            class: (*definition).class,
+            value_size: (*definition).value_size,
+            generic_arity: 0,
+        });
+        let class = &mut (*mock).class;
+
+        let inst = leak(Il2CppGenericInst {
+            type_argc: arguments.len() as u32,
+            type_argv: leak_slice(arguments.to_vec()),
+        });
+        let generic_class = leak(Il2CppGenericClass {
+            type_: &(*definition).class.byval_arg,
+            context: Il2CppGenericContext {
+                class_inst: inst,
+                method_inst: ptr::null(),
+            },
+            cached_class: mock as _,
+        });
+        class.generic_class = generic_class;
+        class.byval_arg.data.generic_class = generic_class;
+        class.byval_arg.set_type_(Il2CppTypeEnum_IL2CPP_TYPE_GENERICINST);
+        class.this_arg = class.byval_arg;
+        class.this_arg.set_byref(1);
+
+        state.inflated_classes.insert(key, Leaked(mock));
+        mock
+    }
+}
+
+/// Returns the `System.Type` object of a type, creating it on first use
+///
+/// # Safety
+///
+/// `type_` must be a mock type
+unsafe fn type_object(type_: *const Il2CppType) -> *mut Il2CppReflectionType {
+    let class = unsafe { type_class(type_) };
+    let mut state = STATE.lock().unwrap();
+    state
+        .type_objects
+        .entry(class as usize)
+        .or_insert_with(|| unsafe {
+            let runtime_type = REFLECTION.get().unwrap().runtime_type.0;
+            let object = new_object(runtime_type as _) as *mut Il2CppReflectionType;
+            (*object).type_ = &(*class).byval_arg;
+            Leaked(object)
+        })
+        .0
+}
+
+/// Mock `System.RuntimeType.MakeGenericType`
+fn make_generic_type(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let definition = call.this().as_ptr() as *mut Il2CppReflectionType;
+        let definition = type_class((*definition).type_) as *mut MockClass;
+        let arguments = call.arg_object::<Il2CppArray>(0).unwrap_non_null();
+        let arguments = slice::from_raw_parts(
+            arguments.as_ptr().add(1) as *const *mut Il2CppReflectionType,
+            (*arguments.as_ptr()).max_length,
+        );
+
+        if (*definition).generic_arity == 0 {
+            return Err(exception("InvalidOperationException: not a generic type definition"));
+        }
+        if (*definition).generic_arity != arguments.len() {
+            return Err(exception("ArgumentException: wrong number of type arguments"));
+        }
+
+        let arguments: Vec<_> = arguments.iter().map(|&argument| (*argument).type_).collect();
+        let inflated = inflate(definition, &arguments);
+        Ok(Ref::new(type_object(&(*inflated).class.byval_arg) as _))
+    }
+}
+
+/// Returns the method inflating a generic method definition, creating it on first use
+///
+/// # Safety
+///
+/// `definition` must be a mock generic method and `arguments` mock types
+unsafe fn inflate_method(
+    definition: *const MockMethod,
+    arguments: &[*const Il2CppType],
+) -> *const MockMethod {
+    /// Stands in for the compiled code of inflated methods
+    extern "C" fn compiled() {}
+
+    let key = (
+        definition as usize,
+        arguments.iter().map(|&type_| unsafe { type_class(type_) } as usize).collect(),
+    );
+    let mut state = STATE.lock().unwrap();
+    if let Some(method) = state.inflated_methods.get(&key) {
+        return method.0;
+    }
+
+    unsafe {
+        let mut method = (*definition).method;
+        let inst = leak(Il2CppGenericInst {
+            type_argc: arguments.len() as u32,
+            type_argv: leak_slice(arguments.to_vec()),
+        });
+        method.__bindgen_anon_2.genericMethod = leak(Il2CppGenericMethod {
+            methodDefinition: definition as _,
+            context: Il2CppGenericContext {
+                class_inst: ptr::null(),
+                method_inst: inst,
+            },
+        });
+        method.set_is_generic(0);
+        method.set_is_inflated(1);
+        method.methodPointer = Some(compiled);
+
+        let inflated = leak(MockMethod {
+            method,
+            param_names: (*definition).param_names.clone(),
+            body: (*definition).body.clone(),
+        });
+        state.inflated_methods.insert(key, Leaked(inflated));
+        inflated
+    }
+}
+
+/// Returns the `System.Reflection.MethodInfo` object of a method, creating it on first use
+///
+/// # Safety
+///
+/// `method` must be a mock method
+unsafe fn method_object(method: *const MethodInfo) -> *mut Il2CppReflectionMethod {
+    let mut state = STATE.lock().unwrap();
+    state
+        .method_objects
+        .entry(method as usize)
+        .or_insert_with(|| unsafe {
+            let runtime_method = REFLECTION.get().unwrap().runtime_method.0;
+            let object = new_object(runtime_method as _) as *mut Il2CppReflectionMethod;
+            (*object).method = method;
+            Leaked(object)
+        })
+        .0
+}
+
+/// Mock `System.Reflection.RuntimeMethodInfo.MakeGenericMethod`
+fn make_generic_method(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let definition = call.this().as_ptr() as *mut Il2CppReflectionMethod;
+        let definition = (*definition).method as *const MockMethod;
+        let arguments = call.arg_object::<Il2CppArray>(0).unwrap_non_null();
+        let arguments = slice::from_raw_parts(
+            arguments.as_ptr().add(1) as *const *mut Il2CppReflectionType,
+            (*arguments.as_ptr()).max_length,
+        );
+
+        let method = &(*definition).method;
+        if method.is_generic() == 0 {
+            return Err(exception("InvalidOperationException: not a generic method definition"));
+        }
+        let container = method.__bindgen_anon_2.genericContainerHandle;
+        if (*(container as *const Il2CppGenericContainer)).type_argc as usize != arguments.len() {
+            return Err(exception("ArgumentException: wrong number of type arguments"));
+        }
+
+        let arguments: Vec<_> = arguments.iter().map(|&argument| (*argument).type_).collect();
+        let inflated = inflate_method(definition, &arguments);
+        Ok(Ref::new(method_object(inflated as _) as _))
+    }
+}
+
+/// Constructs custom attributes into an `Object[]`
+///
+/// # Safety
+///
+/// `attributes` must be mock classes
+unsafe fn construct_attributes(attributes: &[Leaked<MockClass>]) -> *mut Il2CppArray {
+    unsafe {
+        let object = CORLIB.get().unwrap().object;
+        let array_class = array_class(object.as_ptr(), 1, false) as _;
+        let array = new_array(array_class, &[attributes.len()], None);
+        let data = array.add(1) as *mut *mut Il2CppObject;
+        for (index, attribute) in attributes.iter().enumerate() {
+            *data.add(index) = new_object(attribute.0 as _);
+        }
+        array
+    }
+}
+
+/// Returns the custom attribute classes of a class, method, field or property
+fn attributes_of(member: usize) -> Vec<Leaked<MockClass>> {
+    let state = STATE.lock().unwrap();
+    state.attributes.get(&member).cloned().unwrap_or_default()
+}
+
+/// Mock `System.RuntimeType.GetField`, ignores the binding flags
+fn get_field(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let runtime_field = REFLECTION.get().unwrap().runtime_field;
+        reflection_member(call, runtime_field, |class| {
+            (0..class.field_count as usize)
+                .map(|i| class.fields.add(i))
+                .map(|field| ((*field).name, field as usize))
+                .collect()
+        })
+    }
+}
+
+/// Mock `System.RuntimeType.GetProperty`, ignores the binding flags
+fn get_property(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let runtime_property = REFLECTION.get().unwrap().runtime_property;
+        reflection_member(call, runtime_property, |class| {
+            (0..class.property_count as usize)
+                .map(|i| class.properties.add(i))
+                .map(|property| ((*property).name, property as usize))
+                .collect()
+        })
+    }
+}
+
+/// Returns the reflection object of the member named by the first argument, or null
+///
+/// # Safety
+///
+/// `call` must be a call on a `System.Type` object, and `members` must return the names and
+/// raw pointers of members of a mock class
+unsafe fn reflection_member(
+    call: Invocation<'_>,
+    object_class: Leaked<MockClass>,
+    members: impl Fn(&il2cpp_sys_rs::Il2CppClass) -> Vec<(*const c_char, usize)>,
+) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let type_ = call.this().as_ptr() as *mut Il2CppReflectionType;
+        let class = type_class((*type_).type_);
+        let name = crate::Il2CppString::from_ptr(call.arg_object(0).as_ptr())
+            .map(|name| name.to_utf8_lossy())
+            .unwrap_or_default();
+
+        let member = members(&*class)
+            .into_iter()
+            .find(|&(member_name, _)| CStr::from_ptr(member_name).to_bytes() == name.as_bytes());
+        let Some((_, member)) = member else {
+            return Ok(Ref::null());
+        };
+        let object = new_object(object_class.0 as _) as *mut ReflectionMember;
+        (*object).member = member;
+        Ok(Ref::new(object as _))
+    }
+}
+
+/// Mock `System.Reflection.MemberInfo.GetCustomAttributes` of fields and properties
+fn member_attributes(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let member = (*(call.this().as_ptr() as *mut ReflectionMember)).member;
+        let attributes = attributes_of(member);
+        // Stands for a throwing attribute constructor
+        if attributes
+            .iter()
+            .any(|attribute| (*attribute.0).class.flags & TYPE_ATTRIBUTE_ABSTRACT != 0)
+        {
+            return Err(exception("cannot create an abstract attribute"));
+        }
+        Ok(Ref::new(construct_attributes(&attributes) as _))
+    }
+}
+
+/// Mock `Invoke` method of delegate classes
+fn invoke_delegate(call: Invocation<'_>) -> Result<Ref<Il2CppObject, ()>, Exception> {
+    unsafe {
+        let delegate = call.this().as_ptr() as *mut Il2CppDelegate;
+        let method = (*delegate).method;
+        let target = (*delegate).target;
+
+        // Mock methods have no invoker
+        let Some(invoker) = (*method).invoker_method else {
+            let invocation = Invocation {
+                this: Ref::new(target),
+                args: call.args(),
+            };
+            return ((*(method as *const MockMethod)).body)(invocation);
+        };
+
+        // Invokers may raise exceptions
+        type UnwindInvoker = unsafe extern "C-unwind" fn(
+            Il2CppMethodPointer,
+            *const il2cpp_sys_rs::MethodInfo,
+            *mut c_void,
+            *mut *mut c_void,
+            *mut c_void,
+        );
+        let invoker = mem::transmute::<*const (), UnwindInvoker>(invoker as *const ());
+
+        let return_type = (*method).return_type;
+        let mut ret = vec![0_u64; type_size(return_type).div_ceil(8).max(1)];
+        invoker(
+            (*method).methodPointer,
+            method,
+            target as _,
+            call.args().as_ptr() as _,
+            ret.as_mut_ptr() as _,
+        );
+
+        if (*return_type).type_() == Il2CppTypeEnum_IL2CPP_TYPE_VOID {
+            Ok(Ref::null())
+        } else if (*return_type).valuetype() != 0 {
+            let class = type_class(return_type);
+            let object = new_object(class);
+            let size = (*(class as *mut MockClass)).value_size;
+            ptr::copy_nonoverlapping(ret.as_ptr() as *const u8, object.add(1) as *mut u8, size);
+            Ok(Ref::new(object))
+        } else {
+            Ok(Ref::new(ret[0] as *mut Il2CppObject))
+        }
+    }
+}
+
+/// Allocates a new object of `class` with an explicit size
+///
+/// # Safety
+///
+/// `class` must be a mock class
+unsafe fn new_object_sized(
+    class: *mut il2cpp_sys_rs::Il2CppClass,
+    size: usize,
+) -> *mut Il2CppObject {
+    unsafe {
+        let object = alloc_zeroed(size) as *mut Il2CppObject;
+        (*object).__bindgen_anon_1.klass = class;
+        object
+    }
+}
+
+/// Returns the panic payload message
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("mock method panicked")
+}
+
+/// Panic payload carrying an exception raised by `il2cpp_raise_exception`
+struct RaisedException(Exception);
+
+// Safety: the mock runtime objects are never freed
+unsafe impl Send for RaisedException {}
+
+/// Copies `text` into a C buffer of `size` bytes, truncated and nul-terminated
+///
+/// # Safety
+///
+/// `buffer` must be valid for `size` bytes
+unsafe fn copy_truncated(text: &str, buffer: *mut c_char, size: i32) {
+    let len = text.len().min((size as usize).saturating_sub(1));
+    unsafe {
+        ptr::copy_nonoverlapping(text.as_ptr(), buffer as *mut u8, len);
+        *buffer.add(len) = 0;
+    }
+}
+
+/// Builds the mock core library
+fn build_corlib() -> Corlib {
+    let image = new_image(c"mscorlib.dll");
+    let class = |name: &CStr| ClassBuilder::new(image, c"System", name);
+
+    let mut object_builder = class(c"Object").type_enum(Il2CppTypeEnum_IL2CPP_TYPE_OBJECT);
+    object_builder.root = true;
+    let object = object_builder.build();
+
+    // Primitives and core types need `System.Object` and `System.ValueType` as parents
+    let value_type = class(c"ValueType").parent(object).abstract_type().build();
+
+    let primitive = |name: &CStr, type_enum, size| {
+        class(name)
+            .parent(value_type)
+            .primitive(type_enum, size)
+            .build()
+    };
+    primitive(c"Void", Il2CppTypeEnum_IL2CPP_TYPE_VOID, 0);
+    primitive(c"Boolean", Il2CppTypeEnum_IL2CPP_TYPE_BOOLEAN, 1);
+    primitive(c"Char", Il2CppTypeEnum_IL2CPP_TYPE_CHAR, 2);
+    primitive(c"SByte", Il2CppTypeEnum_IL2CPP_TYPE_I1, 1);
+    primitive(c"Byte", Il2CppTypeEnum_IL2CPP_TYPE_U1, 1);
+    primitive(c"Int16", Il2CppTypeEnum_IL2CPP_TYPE_I2, 2);
+    primitive(c"UInt16", Il2CppTypeEnum_IL2CPP_TYPE_U2, 2);
+    primitive(c"Int32", Il2CppTypeEnum_IL2CPP_TYPE_I4, 4);
+    primitive(c"UInt32", Il2CppTypeEnum_IL2CPP_TYPE_U4, 4);
+    primitive(c"Int64", Il2CppTypeEnum_IL2CPP_TYPE_I8, 8);
+    primitive(c"UInt64", Il2CppTypeEnum_IL2CPP_TYPE_U8, 8);
+    primitive(c"Single", Il2CppTypeEnum_IL2CPP_TYPE_R4, 4);
+    primitive(c"Double", Il2CppTypeEnum_IL2CPP_TYPE_R8, 8);
+    primitive(c"IntPtr", Il2CppTypeEnum_IL2CPP_TYPE_I, size_of::<usize>());
+    primitive(c"UIntPtr", Il2CppTypeEnum_IL2CPP_TYPE_U, size_of::<usize>());
+
+    let string = class(c"String")
+        .parent(object)
+        .type_enum(Il2CppTypeEnum_IL2CPP_TYPE_STRING)
+        .build();
+    class(c"Array").parent(object).abstract_type().build();
+
+    let exception = class(c"Exception").parent(object).build();
+    // `System.Exception` objects are laid out as `Il2CppException`
+    unsafe {
+        (*(exception.as_ptr())).instance_size = size_of::<Il2CppException>() as u32;
+    }
+    let system_exception = class(c"SystemException").parent(exception).build();
+    let argument_exception = class(c"ArgumentException").parent(system_exception).build();
+    class(c"ArgumentNullException").parent(argument_exception).build();
+    for name in [
+        c"InvalidOperationException",
+        c"NotSupportedException",
+        c"NullReferenceException",
+    ] {
+        class(name).parent(system_exception).build();
+    }
+
+    class(c"Attribute").parent(object).abstract_type().build();
+
+    let delegate = class(c"Delegate").parent(object).abstract_type().build();
+    // `System.Delegate` objects are laid out as `Il2CppDelegate`
+    unsafe {
+        (*(delegate.as_ptr())).instance_size = size_of::<Il2CppDelegate>() as u32;
+    }
+    class(c"MulticastDelegate")
+        .parent(delegate)
+        .abstract_type()
+        .build();
+
+    Corlib {
+        image: Leaked(image.as_ptr() as _),
+        object,
+        value_type,
+        string,
+        exception,
+    }
+}
+
+/// Builds the mock reflection classes, once the runtime is installed
+fn build_reflection() -> Reflection {
+    let image = Il2CppImage::from_ptr(CORLIB.get().unwrap().image.0 as _).unwrap();
+    let type_ = ClassBuilder::new(image, c"System", c"Type")
+        .abstract_type()
+        .build();
+    let type_array = unsafe { array_class(type_.as_ptr(), 1, false) };
+    let corlib = |name: &CStr| image.find_class(c"System", name).unwrap();
+    let object_array = unsafe { array_class(corlib(c"Object").as_ptr(), 1, false) };
+    let object_array = Il2CppClass::from_ptr(object_array as _).unwrap();
+
+    let binding_flags = ClassBuilder::new(image, c"System.Reflection", c"BindingFlags")
+        .value_type()
+        .field(c"value__", corlib(c"Int32"))
+        .build();
+    let member_info = ClassBuilder::new(image, c"System.Reflection", c"MemberInfo")
+        .abstract_type()
+        .build();
+    let member_class = |name: &CStr, runtime_name: &CStr| {
+        let class = ClassBuilder::new(image, c"System.Reflection", name)
+            .parent(member_info)
+            .abstract_type()
+            .build();
+        let runtime_class = ClassBuilder::new(image, c"System.Reflection", runtime_name)
+            .parent(class)
+            .method(
+                c"GetCustomAttributes",
+                &[(c"inherit", corlib(c"Boolean"))],
+                object_array,
+                member_attributes,
+            )
+            .build();
+        // Reflection objects are laid out as `ReflectionMember`
+        unsafe { (*runtime_class.as_ptr()).instance_size = size_of::<ReflectionMember>() as u32 };
+        (class, runtime_class)
+    };
+    let (field_info, runtime_field) = member_class(c"FieldInfo", c"RuntimeFieldInfo");
+    let (property_info, runtime_property) = member_class(c"PropertyInfo", c"RuntimePropertyInfo");
+
+    let member_getter = [(c"name", corlib(c"String")), (c"bindingAttr", binding_flags)];
+    let runtime_type = ClassBuilder::new(image, c"System", c"RuntimeType")
+        .parent(type_)
+        .method(
+            c"MakeGenericType",
+            &[(c"typeArguments", Il2CppClass::from_ptr(type_array as _).unwrap())],
+            type_,
+            make_generic_type,
+        )
+        .method(c"GetField", &member_getter, field_info, get_field)
+        .method(c"GetProperty", &member_getter, property_info, get_property)
+        .build();
+
+    let method_info = ClassBuilder::new(image, c"System.Reflection", c"MethodInfo")
+        .abstract_type()
+        .build();
+    let runtime_method = ClassBuilder::new(image, c"System.Reflection", c"RuntimeMethodInfo")
+        .parent(method_info)
+        .method(
+            c"MakeGenericMethod",
+            &[(c"methodInstantiation", Il2CppClass::from_ptr(type_array as _).unwrap())],
+            method_info,
+            make_generic_method,
+        )
+        .build();
+
+    // Reflection objects are laid out as `Il2CppReflectionType` and `Il2CppReflectionMethod`
+    unsafe {
+        (*(runtime_type.as_ptr())).instance_size = size_of::<Il2CppReflectionType>() as u32;
+        (*(runtime_method.as_ptr())).instance_size = size_of::<Il2CppReflectionMethod>() as u32;
+    }
+    Reflection {
+        runtime_type: Leaked(runtime_type.as_ptr() as _),
+        runtime_method: Leaked(runtime_method.as_ptr() as _),
+        runtime_field: Leaked(runtime_field.as_ptr() as _),
+        runtime_property: Leaked(runtime_property.as_ptr() as _),
+    }
+}
+
+/// Mock `il2cpp_*` exports
+pub(crate) mod exports {
+    use super::*;
+    use il2cpp_sys_rs::Il2CppThread;
+    use std::cell::Cell;
+
+    /// Maps export names to their mock implementation
+    macro_rules! resolve {
+        ($name:expr; $($export:ident),* $(,)?) => {
+            match $name.to_bytes() {
+                $(n if n == stringify!($export).as_bytes() => $export as *const c_void,)*
+                _ => ptr::null(),
+            }
+        };
+    }
+
+    /// Resolves a mock export by name
+    pub(crate) fn resolve(name: &CStr) -> *const c_void {
+        resolve!(name;
+            il2cpp_domain_get,
+            il2cpp_domain_get_assemblies,
+            il2cpp_thread_current,
+            il2cpp_thread_attach,
+            il2cpp_thread_detach,
+            il2cpp_get_corlib,
+            il2cpp_image_get_class_count,
+            il2cpp_image_get_class,
+            il2cpp_class_from_name,
+            il2cpp_class_get_type,
+            il2cpp_class_from_il2cpp_type,
+            il2cpp_class_from_system_type,
+            il2cpp_class_get_field_from_name,
+            il2cpp_class_get_property_from_name,
+            il2cpp_class_get_method_from_name,
+            il2cpp_class_get_events,
+            il2cpp_class_get_nested_types,
+            il2cpp_class_get_interfaces,
+            il2cpp_class_array_element_size,
+            il2cpp_class_num_fields,
+            il2cpp_class_is_inited,
+            il2cpp_class_is_generic,
+            il2cpp_class_is_inflated,
+            il2cpp_class_is_valuetype,
+            il2cpp_class_is_blittable,
+            il2cpp_class_is_abstract,
+            il2cpp_class_is_interface,
+            il2cpp_class_is_enum,
+            il2cpp_class_is_subclass_of,
+            il2cpp_object_new,
+            il2cpp_object_get_class,
+            il2cpp_object_get_size,
+            il2cpp_object_unbox,
+            il2cpp_value_box,
+            il2cpp_field_get_value,
+            il2cpp_field_static_get_value,
+            il2cpp_field_set_value,
+            il2cpp_field_static_set_value,
+            il2cpp_method_get_param_name,
+            il2cpp_method_is_generic,
+            il2cpp_method_is_inflated,
+            il2cpp_method_get_object,
+            il2cpp_method_get_from_reflection,
+            il2cpp_runtime_invoke,
+            il2cpp_add_internal_call,
+            il2cpp_resolve_icall,
+            il2cpp_custom_attrs_from_class,
+            il2cpp_custom_attrs_from_method,
+            il2cpp_custom_attrs_has_attr,
+            il2cpp_custom_attrs_get_attr,
+            il2cpp_custom_attrs_construct,
+            il2cpp_custom_attrs_free,
+            il2cpp_type_get_name,
+            il2cpp_type_equals,
+            il2cpp_type_get_object,
+            il2cpp_free,
+            il2cpp_format_exception,
+            il2cpp_format_stack_trace,
+            il2cpp_exception_from_name_msg,
+            il2cpp_raise_exception,
+            il2cpp_string_new,
+            il2cpp_string_new_utf16,
+            il2cpp_string_intern,
+            il2cpp_string_is_interned,
+            il2cpp_array_class_get,
+            il2cpp_bounded_array_class_get,
+            il2cpp_array_get_byte_length,
+            il2cpp_array_new,
+            il2cpp_array_new_specific,
+            il2cpp_array_new_full,
+            il2cpp_gchandle_new,
+            il2cpp_gchandle_new_weakref,
+            il2cpp_gchandle_get_target,
+            il2cpp_gchandle_free,
+            il2cpp_gc_wbarrier_set_field,
+        )
+    }
+
+    /// Walks `class` and its parents
+    unsafe fn hierarchy(
+        class: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> impl Iterator<Item = &'static il2cpp_sys_rs::Il2CppClass> {
+        let mut class = class;
+        std::iter::from_fn(move || {
+            let current = unsafe { class.as_ref()? };
+            class = current.parent;
+            Some(current)
+        })
+    }
+
+    unsafe extern "C" fn il2cpp_domain_get() -> *mut Il2CppDomain {
+        static DOMAIN: OnceLock<Leaked<Il2CppDomain>> = OnceLock::new();
+        DOMAIN
+            .get_or_init(|| Leaked(leak(unsafe { mem::zeroed() })))
+            .0
+    }
+
+    unsafe extern "C" fn il2cpp_domain_get_assemblies(
+        _domain: *const Il2CppDomain,
+        size: *mut usize,
+    ) -> *mut *const Il2CppAssembly {
+        let assemblies: Vec<_> = STATE
+            .lock()
+            .unwrap()
+            .assemblies
+            .iter()
+            .map(|assembly| assembly.0 as *const Il2CppAssembly)
+            .collect();
+        unsafe { *size = assemblies.len() };
+        // Leaked so the snapshot outlives concurrent registrations
+        Box::leak(assemblies.into_boxed_slice()).as_mut_ptr()
+    }
+
+    thread_local! {
+        /// Managed thread of the calling thread, null if not attached
+        static CURRENT_THREAD: Cell<*mut Il2CppThread> = const { Cell::new(ptr::null_mut()) };
+    }
+
+    unsafe extern "C" fn il2cpp_thread_current() -> *mut Il2CppThread {
+        CURRENT_THREAD.get()
+    }
+
+    unsafe extern "C" fn il2cpp_thread_attach(_domain: *mut Il2CppDomain) -> *mut Il2CppThread {
+        if CURRENT_THREAD.get().is_null() {
+            CURRENT_THREAD.set(leak(unsafe { mem::zeroed() }));
+        }
+        CURRENT_THREAD.get()
+    }
+
+    unsafe extern "C" fn il2cpp_thread_detach(thread: *mut Il2CppThread) {
+        if CURRENT_THREAD.get() == thread {
+            CURRENT_THREAD.set(ptr::null_mut());
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_get_corlib() -> *const RawImage {
+        CORLIB.get().unwrap().image.0 as _
+    }
+
+    unsafe extern "C" fn il2cpp_image_get_class_count(image: *const RawImage) -> usize {
+        unsafe { (*(image as *const MockImage)).classes.lock().unwrap().len() }
+    }
+
+    unsafe extern "C" fn il2cpp_image_get_class(
+        image: *const RawImage,
+        index: usize,
+    ) -> *const il2cpp_sys_rs::Il2CppClass {
+        unsafe {
+            let classes = (*(image as *const MockImage)).classes.lock().unwrap();
+            classes.get(index).map_or(ptr::null(), |class| class.0 as _)
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_from_name(
+        image: *const RawImage,
+        namespaze: *const c_char,
+        name: *const c_char,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe {
+            let namespace = CStr::from_ptr(namespaze);
+            let classes = (*(image as *const MockImage)).classes.lock().unwrap();
+
+            // Nested types are separated by `/`
+            let mut declaring_type = ptr::null_mut();
+            for segment in CStr::from_ptr(name).to_bytes().split(|&b| b == b'/') {
+                let class = classes.iter().find(|class| {
+                    let class = &(*class.0).class;
+                    class.declaringType == declaring_type
+                        && CStr::from_ptr(class.name).to_bytes() == segment
+                        && (!declaring_type.is_null()
+                            || CStr::from_ptr(class.namespaze) == namespace)
+                });
+                match class {
+                    Some(class) => declaring_type = class.0 as _,
+                    None => return ptr::null_mut(),
+                }
+            }
+            declaring_type
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_type(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> *const Il2CppType {
+        unsafe { &(*klass).byval_arg }
+    }
+
+    unsafe extern "C" fn il2cpp_class_from_il2cpp_type(
+        type_: *const Il2CppType,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe { type_class(type_) }
+    }
+
+    unsafe extern "C" fn il2cpp_class_from_system_type(
+        type_: *mut Il2CppReflectionType,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe { type_class((*type_).type_) }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_field_from_name(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        name: *const c_char,
+    ) -> *mut FieldInfo {
+        unsafe {
+            let name = CStr::from_ptr(name);
+            for class in hierarchy(klass) {
+                for i in 0..class.field_count as usize {
+                    let field = class.fields.add(i);
+                    if CStr::from_ptr((*field).name) == name {
+                        return field;
+                    }
+                }
+            }
+            ptr::null_mut()
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_property_from_name(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        name: *const c_char,
+    ) -> *const PropertyInfo {
+        unsafe {
+            let name = CStr::from_ptr(name);
+            for class in hierarchy(klass) {
+                for i in 0..class.property_count as usize {
+                    let property = class.properties.add(i);
+                    if CStr::from_ptr((*property).name) == name {
+                        return property;
+                    }
+                }
+            }
+            ptr::null()
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_method_from_name(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        name: *const c_char,
+        args_count: i32,
+    ) -> *const MethodInfo {
+        unsafe {
+            let name = CStr::from_ptr(name);
+            for class in hierarchy(klass) {
+                for i in 0..class.method_count as usize {
+                    let method = *class.methods.add(i);
+                    if CStr::from_ptr((*method).name) == name
+                        && (args_count < 0 || (*method).parameters_count as i32 == args_count)
+                    {
+                        return method;
+                    }
+                }
+            }
+            ptr::null()
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_events(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        iter: *mut *mut c_void,
+    ) -> *const EventInfo {
+        unsafe {
+            let class = &*klass;
+            let next = if (*iter).is_null() {
+                class.events
+            } else {
+                (*iter as *const EventInfo).add(1)
+            };
+            if next.is_null() || next >= class.events.add(class.event_count as usize) {
+                return ptr::null();
+            }
+            *iter = next as _;
+            next
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_nested_types(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        iter: *mut *mut c_void,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe {
+            // The iterator holds the index of the next class of the image to check
+            let classes = (*((*klass).image as *const MockImage)).classes.lock().unwrap();
+            let start = *iter as usize;
+            for (index, class) in classes.iter().enumerate().skip(start) {
+                if (*class.0).class.declaringType == klass {
+                    *iter = (index + 1) as _;
+                    return class.0 as _;
+                }
+            }
+            *iter = classes.len() as _;
+            ptr::null_mut()
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_get_interfaces(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        iter: *mut *mut c_void,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe {
+            // The iterator holds the index of the next interface
+            let class = &*klass;
+            let index = *iter as usize;
+            if index >= class.interfaces_count as usize {
+                return ptr::null_mut();
+            }
+            *iter = (index + 1) as _;
+            *class.implementedInterfaces.add(index)
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_array_element_size(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> i32 {
+        unsafe { element_size(klass) as i32 }
+    }
+
+    unsafe extern "C" fn il2cpp_class_num_fields(klass: *const il2cpp_sys_rs::Il2CppClass) -> usize {
+        unsafe {
+            let class = &*klass;
+            (0..class.field_count as usize)
+                .filter(|&i| (*(*class.fields.add(i)).type_).attrs() & FIELD_ATTRIBUTE_STATIC == 0)
+                .count()
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_inited(_klass: *const il2cpp_sys_rs::Il2CppClass) -> bool {
+        true
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_generic(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { (*(klass as *const MockClass)).generic_arity > 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_inflated(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { !(*klass).generic_class.is_null() }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_valuetype(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { (*klass).byval_arg.valuetype() != 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_blittable(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { il2cpp_class_is_valuetype(klass) }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_abstract(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { (*klass).flags & TYPE_ATTRIBUTE_ABSTRACT != 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_interface(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { (*klass).flags & TYPE_ATTRIBUTE_INTERFACE != 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_enum(klass: *const il2cpp_sys_rs::Il2CppClass) -> bool {
+        unsafe {
+            let parent = (*klass).parent;
+            !parent.is_null()
+                && CStr::from_ptr((*parent).namespaze) == c"System"
+                && CStr::from_ptr((*parent).name) == c"Enum"
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_class_is_subclass_of(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        klassc: *mut il2cpp_sys_rs::Il2CppClass,
+        check_interfaces: bool,
+    ) -> bool {
+        unsafe {
+            hierarchy(klass).any(|class| {
+                ptr::eq(class, klassc)
+                    || (check_interfaces
+                        && (0..class.interfaces_count as usize)
+                            .any(|i| *class.implementedInterfaces.add(i) == klassc))
+            })
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_object_new(
+        klass: *const il2cpp_sys_rs::Il2CppClass,
+    ) -> *mut Il2CppObject {
+        unsafe { new_object(klass as _) }
+    }
+
+    unsafe extern "C" fn il2cpp_object_get_class(
+        obj: *mut Il2CppObject,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe { (*obj).__bindgen_anon_1.klass }
+    }
+
+    unsafe extern "C" fn il2cpp_object_get_size(obj: *mut Il2CppObject) -> u32 {
+        unsafe {
+            let class = (*obj).__bindgen_anon_1.klass;
+            if (*class).rank > 0 {
+                let array = obj as *mut Il2CppArray;
+                size_of::<Il2CppArray>() as u32
+                    + (*array).max_length as u32 * element_size(class) as u32
+            } else if class == CORLIB.get().unwrap().string.as_ptr() {
+                let string = obj as *mut Il2CppString;
+                (mem::offset_of!(Il2CppString, chars)
+                    + ((*string).length as usize + 1) * size_of::<Il2CppChar>()) as u32
+            } else {
+                (*class).instance_size
+            }
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_object_unbox(obj: *mut Il2CppObject) -> *mut c_void {
+        unsafe { obj.add(1) as _ }
+    }
+
+    unsafe extern "C" fn il2cpp_value_box(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+        data: *mut c_void,
+    ) -> *mut Il2CppObject {
+        unsafe {
+            let object = new_object(klass);
+            let size = (*(klass as *mut MockClass)).value_size;
+            ptr::copy_nonoverlapping(data as *const u8, object.add(1) as *mut u8, size);
+            object
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_field_get_value(
+        obj: *mut Il2CppObject,
+        field: *mut FieldInfo,
+        value: *mut c_void,
+    ) {
+        unsafe {
+            let size = type_size((*field).type_);
+            let source = (obj as *const u8).add((*field).offset as usize);
+            ptr::copy_nonoverlapping(source, value as *mut u8, size);
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_field_static_get_value(field: *mut FieldInfo, value: *mut c_void) {
+        unsafe {
+            let size = type_size((*field).type_);
+            let source = ((*(*field).parent).static_fields as *const u8).add((*field).offset as usize);
+            ptr::copy_nonoverlapping(source, value as *mut u8, size);
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_field_set_value(
+        obj: *mut Il2CppObject,
+        field: *mut FieldInfo,
+        value: *mut c_void,
+    ) {
+        unsafe {
+            let size = type_size((*field).type_);
+            let target = (obj as *mut u8).add((*field).offset as usize);
+            ptr::copy_nonoverlapping(value as *const u8, target, size);
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_field_static_set_value(field: *mut FieldInfo, value: *mut c_void) {
+        unsafe {
+            let size = type_size((*field).type_);
+            let static_fields = (*(*field).parent).static_fields as *mut u8;
+            let target = static_fields.add((*field).offset as usize);
+            ptr::copy_nonoverlapping(value as *const u8, target, size);
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_method_get_param_name(
+        method: *const MethodInfo,
+        index: u32,
+    ) -> *const c_char {
+        unsafe {
+            let method = &*(method as *const MockMethod);
+            method
+                .param_names
+                .get(index as usize)
+                .map_or(ptr::null(), |name| name.as_ptr())
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_method_is_generic(method: *const MethodInfo) -> bool {
+        unsafe { (*method).is_generic() != 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_method_is_inflated(method: *const MethodInfo) -> bool {
+        unsafe { (*method).is_inflated() != 0 }
+    }
+
+    unsafe extern "C" fn il2cpp_method_get_object(
+        method: *const MethodInfo,
+        _refclass: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> *mut Il2CppReflectionMethod {
+        unsafe { method_object(method) }
+    }
+
+    unsafe extern "C" fn il2cpp_method_get_from_reflection(
+        method: *const Il2CppReflectionMethod,
+    ) -> *const MethodInfo {
+        unsafe { (*method).method }
+    }
+
+    unsafe extern "C" fn il2cpp_runtime_invoke(
+        method: *const MethodInfo,
+        obj: *mut c_void,
+        params: *mut *mut c_void,
+        exc: *mut *mut Il2CppException,
+    ) -> *mut Il2CppObject {
+        unsafe {
+            let method = &*(method as *const MockMethod);
+            let args = if params.is_null() {
+                &[]
+            } else {
+                slice::from_raw_parts(params, method.method.parameters_count as usize)
+            };
+            let invocation = Invocation {
+                this: Ref::new(obj as _),
+                args,
+            };
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| (method.body)(invocation)))
+                .unwrap_or_else(|payload| match payload.downcast::<RaisedException>() {
+                    Ok(raised) => Err(raised.0),
+                    Err(payload) => Err(exception(panic_message(&*payload))),
+                });
+            match result {
+                Ok(object) => object.as_ptr(),
+                Err(exception) => {
+                    if !exc.is_null() {
+                        *exc = exception.as_ptr();
+                    }
+                    ptr::null_mut()
+                }
+            }
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_add_internal_call(
+        name: *const c_char,
+        method: Il2CppMethodPointer,
+    ) {
+        let name = unsafe { CStr::from_ptr(name) }.to_owned();
+        let mut state = STATE.lock().unwrap();
+        match method {
+            Some(method) => state.icalls.insert(name, method as usize),
+            None => state.icalls.remove(&name),
+        };
+    }
+
+    /// Falls back to the name without signature, like the runtime
+    unsafe extern "C" fn il2cpp_resolve_icall(name: *const c_char) -> Il2CppMethodPointer {
+        let name = unsafe { CStr::from_ptr(name) };
+        let state = STATE.lock().unwrap();
+        let method = state.icalls.get(name).or_else(|| {
+            let bytes = name.to_bytes();
+            let end = bytes.iter().position(|&byte| byte == b'(')?;
+            state.icalls.get(&*CString::new(&bytes[..end]).unwrap())
+        })?;
+        Some(unsafe { mem::transmute::<usize, unsafe extern "C" fn()>(*method) })
+    }
+
+    /// Returns null if the member has no attributes, like the runtime
+    fn attr_info(member: usize) -> *mut Il2CppCustomAttrInfo {
+        let attributes = attributes_of(member);
+        if attributes.is_empty() {
+            ptr::null_mut()
+        } else {
+            leak(MockAttrInfo { attributes }) as _
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_from_class(
+        klass: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> *mut Il2CppCustomAttrInfo {
+        attr_info(klass as usize)
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_from_method(
+        method: *const MethodInfo,
+    ) -> *mut Il2CppCustomAttrInfo {
+        attr_info(method as usize)
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_has_attr(
+        ainfo: *mut Il2CppCustomAttrInfo,
+        attr_klass: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> bool {
+        unsafe { !il2cpp_custom_attrs_get_attr(ainfo, attr_klass).is_null() }
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_get_attr(
+        ainfo: *mut Il2CppCustomAttrInfo,
+        attr_klass: *mut il2cpp_sys_rs::Il2CppClass,
+    ) -> *mut Il2CppObject {
+        unsafe {
+            let info = &*(ainfo as *mut MockAttrInfo);
+            info.attributes
+                .iter()
+                .find(|attribute| il2cpp_class_is_subclass_of(attribute.0 as _, attr_klass, false))
+                .map_or(ptr::null_mut(), |attribute| new_object(attribute.0 as _))
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_construct(
+        cinfo: *mut Il2CppCustomAttrInfo,
+    ) -> *mut Il2CppArray {
+        unsafe { construct_attributes(&(*(cinfo as *mut MockAttrInfo)).attributes) }
+    }
+
+    unsafe extern "C" fn il2cpp_custom_attrs_free(ainfo: *mut Il2CppCustomAttrInfo) {
+        drop(unsafe { Box::from_raw(ainfo as *mut MockAttrInfo) });
+    }
+
+    unsafe extern "C" fn il2cpp_type_get_name(type_: *const Il2CppType) -> *mut c_char {
+        unsafe { CString::new(type_name(type_)).unwrap().into_raw() }
+    }
+
+    unsafe extern "C" fn il2cpp_type_equals(
+        type_: *const Il2CppType,
+        other_type: *const Il2CppType,
+    ) -> bool {
+        unsafe {
+            let (type_, other_type) = (&*type_, &*other_type);
+            type_.byref() == other_type.byref()
+                && type_.type_() == other_type.type_()
+                && type_.data.dummy == other_type.data.dummy
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_type_get_object(type_: *const Il2CppType) -> *mut Il2CppObject {
+        unsafe { type_object(type_) as _ }
+    }
+
+    /// Mock memory is never freed
+    unsafe extern "C" fn il2cpp_free(_ptr: *mut c_void) {}
+
+    unsafe extern "C" fn il2cpp_format_exception(
+        ex: *const Il2CppException,
+        message: *mut c_char,
+        message_size: i32,
+    ) {
+        unsafe {
+            let class = Il2CppClass::from_ptr((*ex).object.__bindgen_anon_1.klass).unwrap();
+            let text = match crate::Il2CppString::from_ptr((*ex).message) {
+                Some(msg) => format!("{}: {msg}", class.full_name()),
+                None => class.full_name().into_owned(),
+            };
+
+            copy_truncated(&text, message, message_size);
+        }
+    }
+
+    /// Formats the managed stack trace, the mock does not capture native frames
+    unsafe extern "C" fn il2cpp_format_stack_trace(
+        ex: *const Il2CppException,
+        output: *mut c_char,
+        output_size: i32,
+    ) {
+        unsafe {
+            let trace = crate::Il2CppString::from_ptr((*ex).stack_trace)
+                .map_or_else(String::new, |trace| trace.to_utf8_lossy());
+            copy_truncated(&trace, output, output_size);
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_exception_from_name_msg(
+        image: *const RawImage,
+        name_space: *const c_char,
+        name: *const c_char,
+        msg: *const c_char,
+    ) -> *mut Il2CppException {
+        unsafe {
+            let class = il2cpp_class_from_name(image, name_space, name);
+            if class.is_null() {
+                return ptr::null_mut();
+            }
+
+            let exception = new_object(class) as *mut Il2CppException;
+            let message = CStr::from_ptr(msg).to_string_lossy();
+            (*exception).message = new_string(&message.encode_utf16().collect::<Vec<_>>());
+            exception
+        }
+    }
+
+    /// Unwinds to the closest `il2cpp_runtime_invoke`, which reports the exception
+    unsafe extern "C-unwind" fn il2cpp_raise_exception(ex: *mut Il2CppException) -> ! {
+        let exception = Exception::from_ptr(ex).unwrap();
+        panic::resume_unwind(Box::new(RaisedException(exception)))
+    }
+
+    unsafe extern "C" fn il2cpp_string_new(str: *const c_char) -> *mut Il2CppString {
+        let text = unsafe { CStr::from_ptr(str) }.to_string_lossy();
+        new_string(&text.encode_utf16().collect::<Vec<_>>())
+    }
+
+    unsafe extern "C" fn il2cpp_string_new_utf16(
+        text: *const Il2CppChar,
+        len: i32,
+    ) -> *mut Il2CppString {
+        new_string(unsafe { slice::from_raw_parts(text, len as usize) })
+    }
+
+    unsafe extern "C" fn il2cpp_string_intern(str: *mut Il2CppString) -> *mut Il2CppString {
+        let chars = crate::Il2CppString::from_ptr(str).unwrap().as_slice().to_vec();
+        STATE
+            .lock()
+            .unwrap()
+            .interned
+            .entry(chars)
+            .or_insert(Leaked(str))
+            .0
+    }
+
+    unsafe extern "C" fn il2cpp_string_is_interned(str: *mut Il2CppString) -> *mut Il2CppString {
+        let chars = crate::Il2CppString::from_ptr(str).unwrap().as_slice();
+        STATE
+            .lock()
+            .unwrap()
+            .interned
+            .get(chars)
+            .map_or(ptr::null_mut(), |string| string.0)
+    }
+
+    unsafe extern "C" fn il2cpp_array_class_get(
+        element_class: *mut il2cpp_sys_rs::Il2CppClass,
+        rank: u32,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe { array_class(element_class, rank, false) as _ }
+    }
+
+    unsafe extern "C" fn il2cpp_bounded_array_class_get(
+        element_class: *mut il2cpp_sys_rs::Il2CppClass,
+        rank: u32,
+        bounded: bool,
+    ) -> *mut il2cpp_sys_rs::Il2CppClass {
+        unsafe { array_class(element_class, rank, bounded || rank > 1) as _ }
+    }
+
+    unsafe extern "C" fn il2cpp_array_get_byte_length(array: *mut Il2CppArray) -> u32 {
+        unsafe {
+            let class = (*array).obj.__bindgen_anon_1.klass;
+            ((*array).max_length * (*class).element_size as usize) as u32
+        }
+    }
+
+    unsafe extern "C" fn il2cpp_array_new(
+        element_class: *mut il2cpp_sys_rs::Il2CppClass,
+        length: il2cpp_array_size_t,
+    ) -> *mut Il2CppArray {
+        unsafe { new_array(array_class(element_class, 1, false) as _, &[length], None) }
+    }
+
+    unsafe extern "C" fn il2cpp_array_new_specific(
+        array_class: *mut il2cpp_sys_rs::Il2CppClass,
+        length: il2cpp_array_size_t,
+    ) -> *mut Il2CppArray {
+        unsafe { new_array(array_class, &[length], None) }
+    }
+
+    unsafe extern "C" fn il2cpp_array_new_full(
+        array_class: *mut il2cpp_sys_rs::Il2CppClass,
+        lengths: *mut il2cpp_array_size_t,
+        lower_bounds: *mut il2cpp_array_size_t,
+    ) -> *mut Il2CppArray {
+        unsafe {
+            let rank = (*array_class).rank as usize;
+            new_array(
+                array_class,
+                slice::from_raw_parts(lengths, rank),
+                Some(slice::from_raw_parts(lower_bounds, rank)),
+            )
+        }
+    }
+
+    fn new_gc_handle(obj: *mut Il2CppObject, weak: bool) -> u32 {
+        let mut state = STATE.lock().unwrap();
+        state.last_gc_handle += 1;
+        let handle = state.last_gc_handle;
+        let entry = GcHandleEntry {
+            target: Leaked(obj),
+            weak,
+        };
+        state.gc_handles.insert(handle, entry);
+        handle
+    }
+
+    unsafe extern "C" fn il2cpp_gchandle_new(obj: *mut Il2CppObject, _pinned: bool) -> u32 {
+        new_gc_handle(obj, false)
+    }
+
+    unsafe extern "C" fn il2cpp_gchandle_new_weakref(
+        obj: *mut Il2CppObject,
+        _track_resurrection: bool,
+    ) -> u32 {
+        new_gc_handle(obj, true)
+    }
+
+    unsafe extern "C" fn il2cpp_gchandle_get_target(gchandle: u32) -> *mut Il2CppObject {
+        STATE
+            .lock()
+            .unwrap()
+            .gc_handles
+            .get(&gchandle)
+            .map_or(ptr::null_mut(), |entry| entry.target.0)
+    }
+
+    unsafe extern "C" fn il2cpp_gchandle_free(gchandle: u32) {
+        STATE.lock().unwrap().gc_handles.remove(&gchandle);
+    }
+
+    unsafe extern "C" fn il2cpp_gc_wbarrier_set_field(
+        _obj: *mut Il2CppObject,
+        target_address: *mut *mut c_void,
+        object: *mut c_void,
+    ) {
+        unsafe { *target_address = object };
+    }
+}
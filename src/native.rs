@@ -0,0 +1,88 @@
+//! Native function pointers
+
+/// Native function pointer type, used by hooks and internal calls
+///
+/// Implemented for `extern "C"` and `extern "C-unwind"` function pointers of up to 12 arguments.
+/// Functions raising managed exceptions, see [`Exception::raise`](crate::Exception::raise),
+/// must use the `C-unwind` ABI.
+///
+/// # Safety
+///
+/// Implementors must be plain function pointers
+pub unsafe trait NativeFunction: Copy {
+    /// Returns the function address
+    fn address(self) -> *const ();
+
+    /// Creates a function pointer from an address
+    ///
+    /// # Safety
+    ///
+    /// `address` must be a function of this signature
+    unsafe fn from_address(address: *const ()) -> Self;
+}
+
+macro_rules! impl_native_function {
+    ($($arg:ident),*) => {
+        unsafe impl<R, $($arg),*> NativeFunction for extern "C" fn($($arg),*) -> R {
+            #[inline]
+            fn address(self) -> *const () {
+                self as *const ()
+            }
+
+            #[inline]
+            unsafe fn from_address(address: *const ()) -> Self {
+                unsafe { std::mem::transmute::<*const (), Self>(address) }
+            }
+        }
+
+        unsafe impl<R, $($arg),*> NativeFunction for unsafe extern "C" fn($($arg),*) -> R {
+            #[inline]
+            fn address(self) -> *const () {
+                self as *const ()
+            }
+
+            #[inline]
+            unsafe fn from_address(address: *const ()) -> Self {
+                unsafe { std::mem::transmute::<*const (), Self>(address) }
+            }
+        }
+
+        unsafe impl<R, $($arg),*> NativeFunction for extern "C-unwind" fn($($arg),*) -> R {
+            #[inline]
+            fn address(self) -> *const () {
+                self as *const ()
+            }
+
+            #[inline]
+            unsafe fn from_address(address: *const ()) -> Self {
+                unsafe { std::mem::transmute::<*const (), Self>(address) }
+            }
+        }
+
+        unsafe impl<R, $($arg),*> NativeFunction for unsafe extern "C-unwind" fn($($arg),*) -> R {
+            #[inline]
+            fn address(self) -> *const () {
+                self as *const ()
+            }
+
+            #[inline]
+            unsafe fn from_address(address: *const ()) -> Self {
+                unsafe { std::mem::transmute::<*const (), Self>(address) }
+            }
+        }
+    };
+}
+
+impl_native_function!();
+impl_native_function!(A1);
+impl_native_function!(A1, A2);
+impl_native_function!(A1, A2, A3);
+impl_native_function!(A1, A2, A3, A4);
+impl_native_function!(A1, A2, A3, A4, A5);
+impl_native_function!(A1, A2, A3, A4, A5, A6);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7, A8);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+impl_native_function!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
@@ -0,0 +1,228 @@
+//! Il2CppObject
+
+use crate::runtime::{
+    il2cpp_object_get_class, il2cpp_object_get_size, il2cpp_object_new, il2cpp_object_unbox,
+    il2cpp_value_box,
+};
+use crate::{Il2CppClass, Il2CppError, NonNullRef, Ref};
+use std::fmt;
+use std::mem::size_of;
+
+/// Object handle
+pub type Il2CppObject = NonNullRef<il2cpp_sys_rs::Il2CppObject, ()>;
+/// Nullable Object handle
+pub type Il2CppObjectRef = Ref<il2cpp_sys_rs::Il2CppObject, ()>;
+
+impl Il2CppObject {
+    /// Allocates a new object of `class`
+    ///
+    /// The constructor is not called.
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Class of the object
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime fails to allocate the object
+    #[inline]
+    pub fn new(class: Il2CppClass) -> Result<Self, Il2CppError> {
+        Self::from_ptr(unsafe { il2cpp_object_new(class.as_ptr()) })
+            .ok_or(Il2CppError::AllocationFailed("object"))
+    }
+
+    /// Boxes a value into a new object of a value type class
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - Value type class
+    /// * `value` - Value to box
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::TypeMismatch`] if `class` is not a value type or if its size does
+    /// not match `T`, or [`Il2CppError::AllocationFailed`] if the runtime fails to box the value
+    pub fn box_value<T: Copy>(class: Il2CppClass, value: &T) -> Result<Self, Il2CppError> {
+        check_value_type::<T>(class)?;
+        Self::from_ptr(unsafe { il2cpp_value_box(class.as_ptr(), value as *const T as _) })
+            .ok_or(Il2CppError::AllocationFailed("boxed value"))
+    }
+
+    /// Returns the object class
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class pointer is null
+    #[track_caller]
+    #[inline]
+    pub fn class(self) -> Il2CppClass {
+        Il2CppClass::from_ptr(unsafe { il2cpp_object_get_class(self.as_ptr()) }).unwrap()
+    }
+
+    /// Returns the object size in bytes, including the object header
+    #[inline]
+    pub fn size(self) -> usize {
+        unsafe { il2cpp_object_get_size(self.as_ptr()) as usize }
+    }
+
+    /// Reads the value of a boxed value type
+    ///
+    /// # Safety
+    ///
+    /// `T` must be a valid representation of the boxed value type
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object class is not a value type, or if its size does not match `T`
+    #[track_caller]
+    #[inline]
+    pub unsafe fn unbox<T: Copy>(self) -> T {
+        check_value_type::<T>(self.class()).unwrap_or_else(|err| panic!("{err}"));
+        unsafe { self.unbox_ptr::<T>().read_unaligned() }
+    }
+
+    /// Returns a pointer to the value of a boxed value type
+    ///
+    /// # Safety
+    ///
+    /// The object must be a boxed value type
+    #[inline]
+    pub unsafe fn unbox_ptr<T>(self) -> *mut T {
+        unsafe { il2cpp_object_unbox(self.as_ptr()) as _ }
+    }
+
+    /// Reinterprets the object as a more specific managed type, such as a string or an array
+    ///
+    /// # Safety
+    ///
+    /// The object must be an instance of `T`
+    #[inline]
+    pub const unsafe fn cast<T>(self) -> NonNullRef<T, ()> {
+        NonNullRef::from_ptr(self.as_ptr() as _).unwrap()
+    }
+
+    /// Reinterprets the object as an instance of `class`, after checking its runtime class
+    ///
+    /// # Safety
+    ///
+    /// `T` must be the layout of the instances of `class`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::TypeMismatch`] if the object is not an instance of `class`
+    #[inline]
+    pub unsafe fn try_cast<T>(self, class: Il2CppClass) -> Result<NonNullRef<T, ()>, Il2CppError> {
+        let object_class = self.class();
+        if object_class.is_subclass_of(class, true) {
+            Ok(unsafe { self.cast() })
+        } else {
+            Err(Il2CppError::TypeMismatch {
+                expected: class.full_name(),
+                found: object_class.full_name(),
+            })
+        }
+    }
+}
+
+/// Checks that `class` is a value type holding exactly a `T`
+///
+/// # Errors
+///
+/// Returns [`Il2CppError::TypeMismatch`] with the value type and size otherwise
+fn check_value_type<T>(class: Il2CppClass) -> Result<(), Il2CppError> {
+    if !class.is_value_type() {
+        return Err(Il2CppError::TypeMismatch {
+            expected: "value type".into(),
+            found: class.full_name(),
+        });
+    }
+    if class.array_element_size() != size_of::<T>() {
+        return Err(Il2CppError::TypeMismatch {
+            expected: format!("{}-byte value", size_of::<T>()).into(),
+            found: format!("{} ({} bytes)", class.full_name(), class.array_element_size()).into(),
+        });
+    }
+    Ok(())
+}
+
+impl fmt::Debug for Il2CppObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Il2CppObject")
+            .field("ptr", &self.as_ptr())
+            .field("class", &self.class().full_name())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppError, Il2CppImage, Il2CppObject, Il2CppString};
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn box_and_unbox() {
+        mock::install();
+        let boxed = Il2CppObject::box_value(corlib(c"Int32"), &-7_i32).unwrap();
+        assert_eq!(boxed.class().full_name(), "System.Int32");
+        assert_eq!(unsafe { boxed.unbox::<i32>() }, -7);
+
+        let boxed = Il2CppObject::box_value(corlib(c"Double"), &2.5_f64).unwrap();
+        assert_eq!(unsafe { boxed.unbox::<f64>() }, 2.5);
+        let boxed = Il2CppObject::box_value(corlib(c"Boolean"), &true).unwrap();
+        assert!(unsafe { boxed.unbox::<bool>() });
+
+        let err = Il2CppObject::box_value(corlib(c"Int64"), &1_i32).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected `4-byte value`, found `System.Int64 (8 bytes)`"
+        );
+        assert!(matches!(
+            Il2CppObject::box_value(corlib(c"String"), &0_usize),
+            Err(Il2CppError::TypeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `8-byte value`, found `System.Int32 (4 bytes)`")]
+    fn unbox_wrong_size() {
+        mock::install();
+        let boxed = Il2CppObject::box_value(corlib(c"Int32"), &1_i32).unwrap();
+        unsafe { boxed.unbox::<i64>() };
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `value type`, found `System.Object`")]
+    fn unbox_reference_type() {
+        mock::install();
+        let object = Il2CppObject::new(corlib(c"Object")).unwrap();
+        unsafe { object.unbox::<usize>() };
+    }
+
+    #[test]
+    fn cast() {
+        let image = mock::image(c"ObjectTests.dll");
+        let unit = ClassBuilder::new(image, c"Game", c"Unit").build();
+        let player = ClassBuilder::new(image, c"Game", c"Player").parent(unit).build();
+
+        let string = Il2CppString::new(c"text").unwrap();
+        let string = Il2CppObject::from_ptr(string.as_ptr() as _).unwrap();
+        let cast = unsafe { string.cast::<il2cpp_sys_rs::Il2CppString>() };
+        assert_eq!(cast.to_utf8_lossy(), "text");
+        let cast = unsafe { string.try_cast::<il2cpp_sys_rs::Il2CppString>(corlib(c"String")) };
+        assert_eq!(cast.unwrap().to_utf8_lossy(), "text");
+
+        let object = Il2CppObject::new(player).unwrap();
+        let cast = unsafe { object.try_cast::<il2cpp_sys_rs::Il2CppObject>(unit) };
+        assert_eq!(cast.unwrap().as_ptr(), object.as_ptr());
+
+        let err = unsafe { object.try_cast::<il2cpp_sys_rs::Il2CppString>(corlib(c"String")) };
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "expected `System.String`, found `Game.Player`"
+        );
+    }
+}
@@ -1,8 +1,8 @@
 //! PropertyInfo
 
-use crate::{ExceptionRef, Il2CppClass, MethodInfo, NonNullRef, Ref};
-use il2cpp_sys_rs::{
-    il2cpp_class_get_property_from_name, il2cpp_type_get_name, Il2CppObject, Il2CppType,
+use crate::runtime::il2cpp_class_get_property_from_name;
+use crate::{
+    Il2CppClass, Il2CppError, Il2CppObjectRef, Il2CppTypeHandle, MethodInfo, NonNullRef, Ref,
 };
 use std::ffi::{c_void, CStr};
 use std::fmt;
@@ -26,7 +26,7 @@ impl PropertyInfo {
     ///
     /// Panics if neither getter nor setter is defined
     #[inline]
-    pub const fn type_(self) -> NonNullRef<Il2CppType, ()> {
+    pub const fn type_(self) -> Il2CppTypeHandle {
         if let Some(getter) = self.getter() {
             getter.return_type()
         } else {
@@ -35,10 +35,7 @@ impl PropertyInfo {
                 .expect("Property must have either a getter or a setter")
                 .parameters();
 
-            parameters
-                .last()
-                .expect("Setters always have one parameter")
-                .unwrap_non_null()
+            *parameters.last().expect("Setters always have one parameter")
         }
     }
 
@@ -69,15 +66,15 @@ impl PropertyInfo {
     /// # Arguments
     ///
     /// * `object` - Instance that declares this property
-    pub unsafe fn get<T>(
-        self,
-        object: NonNullRef<T, ()>,
-    ) -> Result<Ref<Il2CppObject, ()>, ExceptionRef> {
-        if let Some(getter) = self.getter() {
-            getter.invoke(object.into(), &mut [])
-        } else {
-            Err(Ref::null())
-        }
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::NoGetter`] if the property is write-only,
+    /// or [`Il2CppError::ManagedException`] if the getter throws
+    pub unsafe fn get<T>(self, object: NonNullRef<T, ()>) -> Result<Il2CppObjectRef, Il2CppError> {
+        self.getter()
+            .ok_or(Il2CppError::NoGetter(self))?
+            .invoke(object.into(), &mut [])
     }
 
     /// Returns the setter method, if any
@@ -97,16 +94,19 @@ impl PropertyInfo {
     ///
     /// * `object` - Instance that declares this property
     /// * `value` - Pointer to the new value
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::NoSetter`] if the property is read-only,
+    /// or [`Il2CppError::ManagedException`] if the setter throws
     pub unsafe fn set<T>(
         self,
         object: NonNullRef<T, ()>,
         value: *mut c_void,
-    ) -> Result<Ref<Il2CppObject, ()>, ExceptionRef> {
-        if let Some(setter) = self.setter() {
-            setter.invoke(object.into(), &mut [value])
-        } else {
-            Err(Ref::null())
-        }
+    ) -> Result<Il2CppObjectRef, Il2CppError> {
+        self.setter()
+            .ok_or(Il2CppError::NoSetter(self))?
+            .invoke(object.into(), &mut [value])
     }
 
     /// Returns the raw property flags
@@ -152,9 +152,7 @@ impl fmt::Debug for PropertyInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PropertyInfo")
             .field("name", &self.name().to_string_lossy())
-            .field("type", &unsafe {
-                CStr::from_ptr(il2cpp_type_get_name(self.type_().as_ptr())).to_string_lossy()
-            })
+            .field("type", &self.type_().name())
             .finish()
     }
 }
@@ -0,0 +1,509 @@
+//! Il2CppRuntime
+
+use crate::Il2CppError;
+use il2cpp_sys_rs::{
+    il2cpp_array_size_t, EventInfo, FieldInfo, Il2CppArray, Il2CppAssembly, Il2CppChar,
+    Il2CppClass, Il2CppCustomAttrInfo, Il2CppDomain, Il2CppException, Il2CppImage,
+    Il2CppMethodPointer, Il2CppObject, Il2CppReflectionMethod, Il2CppReflectionType,
+    Il2CppString, Il2CppThread, Il2CppType, MethodInfo, PropertyInfo,
+};
+use libloading::Library;
+use std::error::Error;
+use std::ffi::{c_char, c_void, CStr, OsStr};
+use std::sync::OnceLock;
+use std::{fmt, mem};
+
+/// Installed runtime, see [`Il2CppRuntime::install`]
+static RUNTIME: OnceLock<Il2CppRuntime> = OnceLock::new();
+
+/// Declares the `il2cpp_*` export table.
+///
+/// Generates the [`Il2CppExports`] struct, its resolver and one crate-private
+/// forwarding function per export, named after the export itself.
+///
+/// Required exports are needed by the core wrappers, a runtime missing one of them is rejected.
+/// Optional exports back feature-specific wrappers and resolve to `None` when missing, their
+/// forwarding functions return [`Il2CppError::MissingExport`] instead.
+///
+/// Exports are called with the `C-unwind` ABI, the runtime throws managed exceptions as C++
+/// exceptions.
+macro_rules! exports {
+    (@name $name:ident) => {
+        // Safety: the literal is null-terminated and has no interior null
+        unsafe {
+            CStr::from_bytes_with_nul_unchecked(concat!(stringify!($name), "\0").as_bytes())
+        }
+    };
+    (@ret) => { () };
+    (@ret $ret:ty) => { $ret };
+    (
+        required {
+            $(fn $name:ident($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)?;)*
+        }
+        optional {
+            $(fn $opt:ident($($opt_arg:ident: $opt_ty:ty),* $(,)?) $(-> $opt_ret:ty)?;)*
+        }
+    ) => {
+        /// Resolved `il2cpp_*` export table
+        #[allow(non_snake_case)]
+        pub struct Il2CppExports {
+            $(
+                #[doc = concat!("`", stringify!($name), "`")]
+                pub $name: unsafe extern "C-unwind" fn($($ty),*) $(-> $ret)?,
+            )*
+            $(
+                #[doc = concat!("`", stringify!($opt), "`, `None` if not exported")]
+                pub $opt: Option<unsafe extern "C-unwind" fn($($opt_ty),*) $(-> $opt_ret)?>,
+            )*
+        }
+
+        impl Il2CppExports {
+            /// Resolves every export using `resolve`
+            ///
+            /// # Safety
+            ///
+            /// Each non-null address returned by `resolve` must be the export of the same name
+            ///
+            /// # Errors
+            ///
+            /// Returns [`RuntimeError::MissingExports`] listing every unresolved required export
+            unsafe fn resolve(
+                mut resolve: impl FnMut(&CStr) -> *const c_void,
+            ) -> Result<Self, RuntimeError> {
+                let mut missing = Vec::new();
+                $(
+                    let $name = resolve(exports!(@name $name));
+                    if $name.is_null() {
+                        missing.push(stringify!($name));
+                    }
+                )*
+
+                if !missing.is_empty() {
+                    return Err(RuntimeError::MissingExports(missing));
+                }
+
+                $(
+                    let $opt = resolve(exports!(@name $opt));
+                )*
+
+                unsafe {
+                    Ok(Self {
+                        $(
+                            $name: mem::transmute::<
+                                *const c_void,
+                                unsafe extern "C-unwind" fn($($ty),*) $(-> $ret)?,
+                            >($name),
+                        )*
+                        // A null address transmutes to `None`
+                        $(
+                            $opt: mem::transmute::<
+                                *const c_void,
+                                Option<unsafe extern "C-unwind" fn($($opt_ty),*) $(-> $opt_ret)?>,
+                            >($opt),
+                        )*
+                    })
+                }
+            }
+        }
+
+        $(
+            #[doc = concat!("Calls `", stringify!($name), "` of the installed runtime")]
+            #[allow(clippy::too_many_arguments)]
+            #[inline]
+            pub(crate) unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                unsafe { (Il2CppRuntime::get().exports.$name)($($arg),*) }
+            }
+        )*
+
+        $(
+            #[doc = concat!("Calls `", stringify!($opt), "` of the installed runtime")]
+            ///
+            /// Returns [`Il2CppError::MissingExport`] if the runtime does not export it
+            #[allow(clippy::too_many_arguments)]
+            #[inline]
+            pub(crate) unsafe fn $opt(
+                $($opt_arg: $opt_ty),*
+            ) -> Result<exports!(@ret $($opt_ret)?), Il2CppError> {
+                let export = Il2CppRuntime::get()
+                    .exports
+                    .$opt
+                    .ok_or(Il2CppError::MissingExport(stringify!($opt)))?;
+                Ok(unsafe { export($($opt_arg),*) })
+            }
+        )*
+    };
+}
+
+exports! {
+    required {
+        // Domain
+        fn il2cpp_domain_get() -> *mut Il2CppDomain;
+        fn il2cpp_domain_get_assemblies(domain: *const Il2CppDomain, size: *mut usize) -> *mut *const Il2CppAssembly;
+
+        // Thread
+        fn il2cpp_thread_current() -> *mut Il2CppThread;
+        fn il2cpp_thread_attach(domain: *mut Il2CppDomain) -> *mut Il2CppThread;
+        fn il2cpp_thread_detach(thread: *mut Il2CppThread);
+
+        // Image
+        fn il2cpp_get_corlib() -> *const Il2CppImage;
+        fn il2cpp_image_get_class_count(image: *const Il2CppImage) -> usize;
+        fn il2cpp_image_get_class(image: *const Il2CppImage, index: usize) -> *const Il2CppClass;
+
+        // Class
+        fn il2cpp_class_from_name(image: *const Il2CppImage, namespaze: *const c_char, name: *const c_char) -> *mut Il2CppClass;
+        fn il2cpp_class_get_type(klass: *mut Il2CppClass) -> *const Il2CppType;
+        fn il2cpp_class_from_il2cpp_type(type_: *const Il2CppType) -> *mut Il2CppClass;
+        fn il2cpp_class_get_field_from_name(klass: *mut Il2CppClass, name: *const c_char) -> *mut FieldInfo;
+        fn il2cpp_class_get_property_from_name(klass: *mut Il2CppClass, name: *const c_char) -> *const PropertyInfo;
+        fn il2cpp_class_get_method_from_name(klass: *mut Il2CppClass, name: *const c_char, args_count: i32) -> *const MethodInfo;
+        fn il2cpp_class_get_events(klass: *mut Il2CppClass, iter: *mut *mut c_void) -> *const EventInfo;
+        fn il2cpp_class_get_nested_types(klass: *mut Il2CppClass, iter: *mut *mut c_void) -> *mut Il2CppClass;
+        fn il2cpp_class_get_interfaces(klass: *mut Il2CppClass, iter: *mut *mut c_void) -> *mut Il2CppClass;
+        fn il2cpp_class_array_element_size(klass: *const Il2CppClass) -> i32;
+        fn il2cpp_class_num_fields(klass: *const Il2CppClass) -> usize;
+        fn il2cpp_class_is_inited(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_generic(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_inflated(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_valuetype(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_blittable(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_abstract(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_interface(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_enum(klass: *const Il2CppClass) -> bool;
+        fn il2cpp_class_is_subclass_of(klass: *mut Il2CppClass, klassc: *mut Il2CppClass, check_interfaces: bool) -> bool;
+
+        // Object
+        fn il2cpp_object_new(klass: *const Il2CppClass) -> *mut Il2CppObject;
+        fn il2cpp_object_get_class(obj: *mut Il2CppObject) -> *mut Il2CppClass;
+        fn il2cpp_object_get_size(obj: *mut Il2CppObject) -> u32;
+        fn il2cpp_object_unbox(obj: *mut Il2CppObject) -> *mut c_void;
+        fn il2cpp_value_box(klass: *mut Il2CppClass, data: *mut c_void) -> *mut Il2CppObject;
+
+        // Field
+        fn il2cpp_field_get_value(obj: *mut Il2CppObject, field: *mut FieldInfo, value: *mut c_void);
+        fn il2cpp_field_static_get_value(field: *mut FieldInfo, value: *mut c_void);
+        fn il2cpp_field_set_value(obj: *mut Il2CppObject, field: *mut FieldInfo, value: *mut c_void);
+        fn il2cpp_field_static_set_value(field: *mut FieldInfo, value: *mut c_void);
+
+        // Method
+        fn il2cpp_method_get_param_name(method: *const MethodInfo, index: u32) -> *const c_char;
+        fn il2cpp_method_is_generic(method: *const MethodInfo) -> bool;
+        fn il2cpp_method_is_inflated(method: *const MethodInfo) -> bool;
+        fn il2cpp_runtime_invoke(method: *const MethodInfo, obj: *mut c_void, params: *mut *mut c_void, exc: *mut *mut Il2CppException) -> *mut Il2CppObject;
+
+        // Type
+        fn il2cpp_type_get_name(type_: *const Il2CppType) -> *mut c_char;
+        fn il2cpp_type_equals(type_: *const Il2CppType, other_type: *const Il2CppType) -> bool;
+
+        // Memory
+        fn il2cpp_free(ptr: *mut c_void);
+
+        // Exception
+        fn il2cpp_format_exception(ex: *const Il2CppException, message: *mut c_char, message_size: i32);
+        fn il2cpp_exception_from_name_msg(image: *const Il2CppImage, name_space: *const c_char, name: *const c_char, msg: *const c_char) -> *mut Il2CppException;
+        fn il2cpp_raise_exception(ex: *mut Il2CppException) -> !;
+
+        // String
+        fn il2cpp_string_new(str: *const c_char) -> *mut Il2CppString;
+        fn il2cpp_string_new_utf16(text: *const Il2CppChar, len: i32) -> *mut Il2CppString;
+        fn il2cpp_string_intern(str: *mut Il2CppString) -> *mut Il2CppString;
+        fn il2cpp_string_is_interned(str: *mut Il2CppString) -> *mut Il2CppString;
+
+        // Array
+        fn il2cpp_array_class_get(element_class: *mut Il2CppClass, rank: u32) -> *mut Il2CppClass;
+        fn il2cpp_bounded_array_class_get(element_class: *mut Il2CppClass, rank: u32, bounded: bool) -> *mut Il2CppClass;
+        fn il2cpp_array_get_byte_length(array: *mut Il2CppArray) -> u32;
+        fn il2cpp_array_new(element_class: *mut Il2CppClass, length: il2cpp_array_size_t) -> *mut Il2CppArray;
+        fn il2cpp_array_new_specific(array_class: *mut Il2CppClass, length: il2cpp_array_size_t) -> *mut Il2CppArray;
+        fn il2cpp_array_new_full(array_class: *mut Il2CppClass, lengths: *mut il2cpp_array_size_t, lower_bounds: *mut il2cpp_array_size_t) -> *mut Il2CppArray;
+
+        // GC
+        fn il2cpp_gchandle_new(obj: *mut Il2CppObject, pinned: bool) -> u32;
+        fn il2cpp_gchandle_new_weakref(obj: *mut Il2CppObject, track_resurrection: bool) -> u32;
+        fn il2cpp_gchandle_get_target(gchandle: u32) -> *mut Il2CppObject;
+        fn il2cpp_gchandle_free(gchandle: u32);
+        fn il2cpp_gc_wbarrier_set_field(obj: *mut Il2CppObject, target_address: *mut *mut c_void, object: *mut c_void);
+    }
+
+    optional {
+        // Class
+        fn il2cpp_class_from_system_type(type_: *mut Il2CppReflectionType) -> *mut Il2CppClass;
+
+        // Method
+        fn il2cpp_method_get_object(method: *const MethodInfo, refclass: *mut Il2CppClass) -> *mut Il2CppReflectionMethod;
+        fn il2cpp_method_get_from_reflection(method: *const Il2CppReflectionMethod) -> *const MethodInfo;
+
+        // Internal call
+        fn il2cpp_add_internal_call(name: *const c_char, method: Il2CppMethodPointer);
+        fn il2cpp_resolve_icall(name: *const c_char) -> Il2CppMethodPointer;
+
+        // Custom attribute
+        fn il2cpp_custom_attrs_from_class(klass: *mut Il2CppClass) -> *mut Il2CppCustomAttrInfo;
+        fn il2cpp_custom_attrs_from_method(method: *const MethodInfo) -> *mut Il2CppCustomAttrInfo;
+        fn il2cpp_custom_attrs_has_attr(ainfo: *mut Il2CppCustomAttrInfo, attr_klass: *mut Il2CppClass) -> bool;
+        fn il2cpp_custom_attrs_get_attr(ainfo: *mut Il2CppCustomAttrInfo, attr_klass: *mut Il2CppClass) -> *mut Il2CppObject;
+        fn il2cpp_custom_attrs_construct(cinfo: *mut Il2CppCustomAttrInfo) -> *mut Il2CppArray;
+        fn il2cpp_custom_attrs_free(ainfo: *mut Il2CppCustomAttrInfo);
+
+        // Type
+        fn il2cpp_type_get_object(type_: *const Il2CppType) -> *mut Il2CppObject;
+
+        // Exception
+        fn il2cpp_format_stack_trace(ex: *const Il2CppException, output: *mut c_char, output_size: i32);
+    }
+}
+
+/// Error raised while loading the IL2CPP runtime
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// The runtime module could not be loaded
+    Load(libloading::Error),
+    /// The runtime module does not export every required `il2cpp_*` function
+    MissingExports(Vec<&'static str>),
+    /// A runtime has already been installed
+    AlreadyInstalled,
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Load(err) => write!(f, "failed to load the IL2CPP runtime: {err}"),
+            Self::MissingExports(names) => {
+                write!(f, "missing IL2CPP exports: {}", names.join(", "))
+            }
+            Self::AlreadyInstalled => f.write_str("an IL2CPP runtime is already installed"),
+        }
+    }
+}
+
+impl Error for RuntimeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Load(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// IL2CPP runtime resolved from a loaded module
+///
+/// Every safe wrapper of this crate calls into the runtime installed with [`Il2CppRuntime::install`].
+///
+/// # Example
+///
+/// ```no_run
+/// use il2cpp_rs::Il2CppRuntime;
+///
+/// Il2CppRuntime::already_loaded("GameAssembly.dll")?.install()?;
+/// # Ok::<(), il2cpp_rs::RuntimeError>(())
+/// ```
+pub struct Il2CppRuntime {
+    /// Keeps the module loaded while the exports are in use
+    _library: Option<Library>,
+    /// Resolved exports
+    exports: Il2CppExports,
+}
+
+impl Il2CppRuntime {
+    /// Loads the runtime module at `path` and resolves its exports
+    ///
+    /// If the module is already loaded in the process, its reference count is incremented.
+    ///
+    /// # Safety
+    ///
+    /// Loading a module runs its initialization routines.
+    /// `path` must point to a Unity IL2CPP runtime module, usually `GameAssembly`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Module name or path
+    pub unsafe fn open(path: impl AsRef<OsStr>) -> Result<Self, RuntimeError> {
+        let library = unsafe { Library::new(path) }.map_err(RuntimeError::Load)?;
+        unsafe { Self::from_library(library) }
+    }
+
+    /// Resolves the exports of a module that is already loaded in the process
+    ///
+    /// Never loads the module.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Module name, such as `GameAssembly.dll` or `GameAssembly.so`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::Load`] if the module is not loaded
+    pub fn already_loaded(name: impl AsRef<OsStr>) -> Result<Self, RuntimeError> {
+        #[cfg(unix)]
+        let library = unsafe {
+            use libloading::os::unix::{Library, RTLD_NOW};
+
+            /// `RTLD_NOLOAD` is not exposed by `libloading`
+            #[cfg(target_os = "linux")]
+            const RTLD_NOLOAD: i32 = 0x4;
+            #[cfg(not(target_os = "linux"))]
+            const RTLD_NOLOAD: i32 = 0x10;
+
+            Library::open(Some(name), RTLD_NOW | RTLD_NOLOAD)
+        };
+        #[cfg(windows)]
+        let library = libloading::os::windows::Library::open_already_loaded(name);
+
+        let library = library.map_err(RuntimeError::Load)?;
+        // Safety: an already loaded module has no initialization routine left to run
+        unsafe { Self::from_library(library.into()) }
+    }
+
+    /// Resolves the exports of a module from its raw handle
+    ///
+    /// The runtime takes ownership of the handle and releases it on drop.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be a valid module handle, as returned by `dlopen` or `LoadLibrary`,
+    /// of a Unity IL2CPP runtime module
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - Module handle
+    pub unsafe fn from_raw_handle(handle: *mut c_void) -> Result<Self, RuntimeError> {
+        #[cfg(unix)]
+        let library = unsafe { libloading::os::unix::Library::from_raw(handle) };
+        #[cfg(windows)]
+        let library = unsafe { libloading::os::windows::Library::from_raw(handle as _) };
+
+        unsafe { Self::from_library(library.into()) }
+    }
+
+    /// Resolves the exports with a custom symbol lookup
+    ///
+    /// # Safety
+    ///
+    /// Each non-null address returned by `resolve` must be the `il2cpp_*` function of
+    /// the requested name, and must stay valid for the rest of the program
+    ///
+    /// # Arguments
+    ///
+    /// * `resolve` - Returns the address of an export by name, or null if it does not exist
+    pub unsafe fn from_resolver(
+        resolve: impl FnMut(&CStr) -> *const c_void,
+    ) -> Result<Self, RuntimeError> {
+        Ok(Self {
+            _library: None,
+            exports: unsafe { Il2CppExports::resolve(resolve)? },
+        })
+    }
+
+    /// Resolves the exports of a loaded library
+    ///
+    /// # Safety
+    ///
+    /// `library` must be a Unity IL2CPP runtime module
+    unsafe fn from_library(library: Library) -> Result<Self, RuntimeError> {
+        let exports = unsafe {
+            Il2CppExports::resolve(|name| {
+                library
+                    .get::<*const c_void>(name.to_bytes_with_nul())
+                    .map_or(std::ptr::null(), |symbol| *symbol)
+            })?
+        };
+
+        Ok(Self {
+            _library: Some(library),
+            exports,
+        })
+    }
+
+    /// Returns the resolved export table
+    #[inline]
+    pub const fn exports(&self) -> &Il2CppExports {
+        &self.exports
+    }
+}
+
+impl Il2CppRuntime {
+    /// Installs the runtime used by every wrapper of this crate
+    ///
+    /// A runtime can only be installed once per process.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::AlreadyInstalled`] if a runtime is already installed
+    pub fn install(self) -> Result<&'static Self, RuntimeError> {
+        RUNTIME
+            .set(self)
+            .map_err(|_| RuntimeError::AlreadyInstalled)?;
+        Ok(Self::get())
+    }
+
+    /// Returns the installed runtime, if any
+    #[inline]
+    pub fn try_get() -> Option<&'static Self> {
+        RUNTIME.get()
+    }
+
+    /// Returns the installed runtime
+    ///
+    /// # Panics
+    ///
+    /// Panics if no runtime is installed
+    #[track_caller]
+    #[inline]
+    pub fn get() -> &'static Self {
+        Self::try_get().expect("IL2CPP runtime is not installed, see `Il2CppRuntime::install`")
+    }
+}
+
+impl fmt::Debug for Il2CppRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Il2CppRuntime")
+            .field("library", &self._library)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock::exports;
+    use crate::{Il2CppError, Il2CppRuntime, RuntimeError};
+    use std::ffi::CStr;
+    use std::ptr;
+
+    #[test]
+    fn optional_exports() {
+        let stripped = |name: &CStr| {
+            if name.to_bytes().starts_with(b"il2cpp_custom_attrs_")
+                || name == c"il2cpp_format_stack_trace"
+            {
+                ptr::null()
+            } else {
+                exports::resolve(name)
+            }
+        };
+        let runtime = unsafe { Il2CppRuntime::from_resolver(stripped) }.unwrap();
+        assert!(runtime.exports().il2cpp_format_stack_trace.is_none());
+        assert!(runtime.exports().il2cpp_custom_attrs_construct.is_none());
+        assert!(runtime.exports().il2cpp_method_get_object.is_some());
+
+        let err = Il2CppError::MissingExport("il2cpp_format_stack_trace");
+        assert_eq!(err.to_string(), "the IL2CPP runtime does not export il2cpp_format_stack_trace");
+    }
+
+    #[test]
+    fn required_exports() {
+        let stripped = |name: &CStr| {
+            if name == c"il2cpp_domain_get" || name.to_bytes().starts_with(b"il2cpp_custom_attrs_")
+            {
+                ptr::null()
+            } else {
+                exports::resolve(name)
+            }
+        };
+        let err = unsafe { Il2CppRuntime::from_resolver(stripped) }.unwrap_err();
+        // Optional exports are not reported
+        assert!(
+            matches!(&err, RuntimeError::MissingExports(names) if names == &["il2cpp_domain_get"])
+        );
+        assert_eq!(err.to_string(), "missing IL2CPP exports: il2cpp_domain_get");
+    }
+}
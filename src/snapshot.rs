@@ -0,0 +1,383 @@
+//! Serializable snapshots of the loaded assemblies
+
+use crate::{
+    FieldInfo, Il2CppAssembly, Il2CppClass, Il2CppDomain, Il2CppImage, MethodInfo, PropertyInfo,
+};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Snapshot of the loaded assemblies
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomainDescriptor {
+    /// Loaded assemblies
+    pub assemblies: Vec<AssemblyDescriptor>,
+}
+
+/// Snapshot of an assembly
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssemblyDescriptor {
+    /// Assembly name, such as `Assembly-CSharp`
+    pub name: String,
+    /// Assembly version, such as `1.0.0.0`
+    pub version: String,
+    /// Image of the assembly
+    pub image: ImageDescriptor,
+}
+
+/// Snapshot of an image
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImageDescriptor {
+    /// Image name, such as `Assembly-CSharp.dll`
+    pub name: String,
+    /// Image token
+    pub token: u32,
+    /// Classes of the image, nested classes included
+    pub classes: Vec<ClassDescriptor>,
+}
+
+/// Snapshot of a class
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassDescriptor {
+    /// Class name
+    pub name: String,
+    /// Class namespace, empty for nested classes
+    pub namespace: String,
+    /// Fully qualified name, nested classes separated by `+`, see
+    /// [`Il2CppClass::qualified_name`]
+    pub full_name: String,
+    /// Class token
+    pub token: u32,
+    /// Raw class flags, `TYPE_ATTRIBUTE_*`
+    pub flags: u32,
+    /// Full name of the base class, if any
+    pub parent: Option<String>,
+    /// Full name of the declaring class of a nested class
+    pub declaring_type: Option<String>,
+    /// Full names of the interfaces declared by the class
+    pub interfaces: Vec<String>,
+    /// Whether the class is a value type
+    pub is_value_type: bool,
+    /// Whether the class is an enum
+    pub is_enum: bool,
+    /// Whether the class is an interface
+    pub is_interface: bool,
+    /// Whether the class is a generic definition
+    pub is_generic: bool,
+    /// Fields declared by the class
+    pub fields: Vec<FieldDescriptor>,
+    /// Properties declared by the class
+    pub properties: Vec<PropertyDescriptor>,
+    /// Methods declared by the class
+    pub methods: Vec<MethodDescriptor>,
+}
+
+/// Snapshot of a field
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldDescriptor {
+    /// Field name
+    pub name: String,
+    /// Field type name
+    pub type_name: String,
+    /// Field token
+    pub token: u32,
+    /// Raw field flags, `FIELD_ATTRIBUTE_*`
+    pub flags: u32,
+    /// Byte offset in the instance, or in the static data of the class for a static field
+    pub offset: usize,
+}
+
+/// Snapshot of a property
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PropertyDescriptor {
+    /// Property name
+    pub name: String,
+    /// Property type name
+    pub type_name: String,
+    /// Property token
+    pub token: u32,
+    /// Raw property flags
+    pub flags: u32,
+    /// Name of the `get` accessor, if any
+    pub getter: Option<String>,
+    /// Name of the `set` accessor, if any
+    pub setter: Option<String>,
+}
+
+/// Snapshot of a method
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodDescriptor {
+    /// Method name
+    pub name: String,
+    /// Method token
+    pub token: u32,
+    /// Raw method flags, `METHOD_ATTRIBUTE_*`
+    pub flags: u32,
+    /// Raw method implementation flags, `METHOD_IMPL_ATTRIBUTE_*`
+    pub impl_flags: u32,
+    /// Return type name
+    pub return_type: String,
+    /// Method parameters
+    pub parameters: Vec<ParameterDescriptor>,
+    /// Address of the native code, if compiled
+    pub address: Option<usize>,
+}
+
+/// Snapshot of a method parameter
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterDescriptor {
+    /// Parameter name
+    pub name: String,
+    /// Parameter type name, with a trailing `&` for by-reference parameters
+    pub type_name: String,
+}
+
+impl DomainDescriptor {
+    /// Deserializes a snapshot from JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if the JSON is not a snapshot
+    #[inline]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Serializes the snapshot to JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if serialization fails
+    #[inline]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    /// Serializes the snapshot to JSON into `writer`
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Output, buffered by the caller if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`serde_json::Error`] if serialization or writing fails
+    #[inline]
+    pub fn write_json(&self, writer: impl io::Write) -> serde_json::Result<()> {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+impl Il2CppDomain {
+    /// Snapshots every loaded assembly
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use il2cpp_rs::Il2CppDomain;
+    ///
+    /// let json = Il2CppDomain::get().snapshot().to_json()?;
+    /// std::fs::write("domain.json", json)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn snapshot(self) -> DomainDescriptor {
+        DomainDescriptor {
+            assemblies: self
+                .assemblies()
+                .iter()
+                .map(|assembly| assembly.snapshot())
+                .collect(),
+        }
+    }
+}
+
+impl Il2CppAssembly {
+    /// Snapshots the assembly and its image
+    pub fn snapshot(self) -> AssemblyDescriptor {
+        let name = &self.as_ref().aname;
+        AssemblyDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            version: format!(
+                "{}.{}.{}.{}",
+                name.major, name.minor, name.build, name.revision
+            ),
+            image: self.image().snapshot(),
+        }
+    }
+}
+
+impl Il2CppImage {
+    /// Snapshots the image and its classes
+    pub fn snapshot(self) -> ImageDescriptor {
+        ImageDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            token: self.token(),
+            classes: self.classes().map(Il2CppClass::snapshot).collect(),
+        }
+    }
+}
+
+impl Il2CppClass {
+    /// Snapshots the class and its members
+    pub fn snapshot(self) -> ClassDescriptor {
+        ClassDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            namespace: self.namespace().to_string_lossy().into_owned(),
+            full_name: self.qualified_name('+').into_owned(),
+            token: self.token(),
+            flags: self.flags(),
+            parent: self.parent().map(|parent| parent.qualified_name('+').into_owned()),
+            declaring_type: self
+                .declaring_type()
+                .map(|declaring_type| declaring_type.qualified_name('+').into_owned()),
+            interfaces: self
+                .interfaces()
+                .map(|interface| interface.qualified_name('+').into_owned())
+                .collect(),
+            is_value_type: self.is_value_type(),
+            is_enum: self.is_enum(),
+            is_interface: self.is_interface(),
+            is_generic: self.is_generic(),
+            fields: self.fields().map(FieldInfo::snapshot).collect(),
+            properties: self.properties().map(PropertyInfo::snapshot).collect(),
+            methods: self.methods().iter().copied().map(MethodInfo::snapshot).collect(),
+        }
+    }
+}
+
+impl FieldInfo {
+    /// Snapshots the field
+    pub fn snapshot(self) -> FieldDescriptor {
+        FieldDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            type_name: self.type_().name(),
+            token: self.token(),
+            flags: self.flags(),
+            offset: self.offset(),
+        }
+    }
+}
+
+impl PropertyInfo {
+    /// Snapshots the property
+    pub fn snapshot(self) -> PropertyDescriptor {
+        let accessor = |method: MethodInfo| method.name().to_string_lossy().into_owned();
+        PropertyDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            type_name: self.type_().name(),
+            token: self.token(),
+            flags: self.flags(),
+            getter: self.getter().map(accessor),
+            setter: self.setter().map(accessor),
+        }
+    }
+}
+
+impl MethodInfo {
+    /// Snapshots the method
+    pub fn snapshot(self) -> MethodDescriptor {
+        MethodDescriptor {
+            name: self.name().to_string_lossy().into_owned(),
+            token: self.token(),
+            flags: self.flags(),
+            impl_flags: self.impl_flags(),
+            return_type: self.return_type().name(),
+            parameters: self
+                .parameters()
+                .iter()
+                .zip(0..self.parameters_count())
+                .map(|(type_, i)| ParameterDescriptor {
+                    name: self.param_name(i).to_string_lossy().into_owned(),
+                    type_name: type_.name(),
+                })
+                .collect(),
+            address: self.address(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::{DomainDescriptor, ParameterDescriptor};
+    use crate::mock::{self, ClassBuilder};
+    use crate::{Il2CppClass, Il2CppDomain, Il2CppImage, Ref};
+
+    fn corlib(name: &std::ffi::CStr) -> Il2CppClass {
+        Il2CppImage::corlib().find_class(c"System", name).unwrap()
+    }
+
+    #[test]
+    fn snapshot() {
+        let image = mock::image(c"SnapshotTests.dll");
+        let class = ClassBuilder::new(image, c"Game", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .method(c"get_Health", &[], corlib(c"Int32"), |_| Ok(Ref::null()))
+            .method(
+                c"Move",
+                &[(c"x", corlib(c"Single")), (c"y", corlib(c"Single"))],
+                corlib(c"Void"),
+                |_| Ok(Ref::null()),
+            )
+            .property(c"Health", Some(c"get_Health"), None)
+            .build();
+        ClassBuilder::new(image, c"", c"Inner").nested_in(class).build();
+
+        let snapshot = image.snapshot();
+        assert_eq!(snapshot.name, "SnapshotTests.dll");
+        assert_eq!(snapshot.classes.len(), 2);
+
+        let player = &snapshot.classes[0];
+        assert_eq!(player.full_name, "Game.Player");
+        assert_eq!(player.token, class.token());
+        assert_eq!(player.parent.as_deref(), Some("System.Object"));
+        assert_eq!(player.fields[0].name, "health");
+        assert_eq!(player.fields[0].type_name, "System.Int32");
+        assert_eq!(player.fields[0].offset, 0x10);
+        assert_eq!(player.properties[0].getter.as_deref(), Some("get_Health"));
+        assert_eq!(player.properties[0].setter, None);
+
+        let move_ = player.methods.iter().find(|method| method.name == "Move").unwrap();
+        assert_eq!(move_.return_type, "System.Void");
+        assert_eq!(
+            move_.parameters,
+            [("x", "System.Single"), ("y", "System.Single")].map(|(name, type_name)| {
+                ParameterDescriptor {
+                    name: name.to_owned(),
+                    type_name: type_name.to_owned(),
+                }
+            })
+        );
+
+        let inner = &snapshot.classes[1];
+        assert_eq!(inner.full_name, "Game.Player+Inner");
+        assert_eq!(inner.declaring_type.as_deref(), Some("Game.Player"));
+
+        let domain = Il2CppDomain::get().snapshot();
+        let assembly = domain
+            .assemblies
+            .iter()
+            .find(|assembly| assembly.image.name == "SnapshotTests.dll")
+            .unwrap();
+        assert_eq!(assembly.image, snapshot);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let image = mock::image(c"SnapshotTestsJson.dll");
+        ClassBuilder::new(image, c"Game", c"Enemy")
+            .field(c"speed", corlib(c"Single"))
+            .static_method(c"Spawn", &[(c"count", corlib(c"Int32"))], corlib(c"Void"), |_| {
+                Ok(Ref::null())
+            })
+            .build();
+
+        let snapshot = Il2CppDomain::get().snapshot();
+        let json = snapshot.to_json().unwrap();
+        assert_eq!(DomainDescriptor::from_json(&json).unwrap(), snapshot);
+
+        let mut written = Vec::new();
+        snapshot.write_json(&mut written).unwrap();
+        assert_eq!(String::from_utf8(written).unwrap(), json);
+
+        assert!(DomainDescriptor::from_json("{\"assemblies\": 1}").is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Il2CppString
 
-use crate::{NonNullRef, Ref};
-use il2cpp_sys_rs::{il2cpp_string_new, il2cpp_string_new_utf16, Il2CppChar};
+use crate::runtime::{
+    il2cpp_string_intern, il2cpp_string_is_interned, il2cpp_string_new, il2cpp_string_new_utf16,
+};
+use crate::{Il2CppError, NonNullRef, Ref};
+use il2cpp_sys_rs::Il2CppChar;
 use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::fmt;
@@ -21,12 +24,13 @@ impl Il2CppString {
     ///
     /// * `s` - Null-terminated C string
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// [`Some(Il2CppString)`] if allocation succeeds, otherwise `None`.
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the string
     #[inline]
-    pub fn new(s: &CStr) -> Option<Self> {
+    pub fn new(s: &CStr) -> Result<Self, Il2CppError> {
         Self::from_ptr(unsafe { il2cpp_string_new(s.as_ptr()) })
+            .ok_or(Il2CppError::AllocationFailed("string"))
     }
 
     /// Allocate a new string from a UTF-16 null-terminated slice
@@ -39,25 +43,27 @@ impl Il2CppString {
     ///
     /// * `s` - Null-terminated UTF-16 slice
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// [`Some(Il2CppString)`] if allocation succeeds, otherwise `None`.
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot allocate the string
     #[allow(unsafe_op_in_unsafe_fn)]
     #[inline]
-    pub unsafe fn new_utf16(s: &[Il2CppChar], len: i32) -> Option<Self> {
+    pub unsafe fn new_utf16(s: &[Il2CppChar], len: i32) -> Result<Self, Il2CppError> {
         Self::from_ptr(il2cpp_string_new_utf16(s.as_ptr(), len))
+            .ok_or(Il2CppError::AllocationFailed("string"))
     }
 
     /// Interns this string and returns the canonical interned instance.
     ///
     /// Uses the runtime's intern pool. Equal strings share the same instance.
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// [`Some(Il2CppString)`] representing the interned string, or `None` if interning fails.
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime cannot intern the string
     #[inline]
-    pub fn intern(self) -> Option<Self> {
-        Self::from_ptr(unsafe { il2cpp_sys_rs::il2cpp_string_intern(self.as_ptr()) })
+    pub fn intern(self) -> Result<Self, Il2CppError> {
+        Self::from_ptr(unsafe { il2cpp_string_intern(self.as_ptr()) })
+            .ok_or(Il2CppError::AllocationFailed("interned string"))
     }
 
     /// Returns the interned instance if one already exists.
@@ -65,7 +71,7 @@ impl Il2CppString {
     /// Does not modify the intern pool. Only queries it.
     #[inline]
     pub fn is_interned(self) -> Option<Self> {
-        let ptr = unsafe { il2cpp_sys_rs::il2cpp_string_is_interned(self.as_ptr()) };
+        let ptr = unsafe { il2cpp_string_is_interned(self.as_ptr()) };
         Self::from_ptr(ptr)
     }
 
@@ -182,3 +188,37 @@ impl fmt::Debug for Il2CppString {
         fmt::Debug::fmt(&self.to_utf8_lossy(), f)
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock;
+    use crate::Il2CppString;
+
+    #[test]
+    fn round_trip() {
+        mock::install();
+        let string = Il2CppString::new(c"héllo").unwrap();
+        assert_eq!(string.to_utf8_lossy(), "héllo");
+        assert_eq!(string.len(), 5);
+        assert_eq!(string.to_string(), "héllo");
+
+        let chars: Vec<u16> = "wörld\0".encode_utf16().collect();
+        let string = unsafe { Il2CppString::new_utf16(&chars, 5) }.unwrap();
+        assert_eq!(string.to_utf8().unwrap(), "wörld");
+        assert_eq!(&*string, &chars[..5]);
+
+        assert!(Il2CppString::new(c"").unwrap().is_empty());
+    }
+
+    #[test]
+    fn intern() {
+        mock::install();
+        let string = Il2CppString::new(c"StringTests.intern").unwrap();
+        assert!(string.is_interned().is_none());
+
+        let interned = string.intern().unwrap();
+        let other = Il2CppString::new(c"StringTests.intern").unwrap();
+        assert_eq!(other.is_interned().unwrap().as_ptr(), interned.as_ptr());
+        assert_eq!(other.intern().unwrap().as_ptr(), interned.as_ptr());
+    }
+}
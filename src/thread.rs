@@ -0,0 +1,171 @@
+//! Il2CppThread
+
+use crate::runtime::{
+    il2cpp_domain_get, il2cpp_thread_attach, il2cpp_thread_current, il2cpp_thread_detach,
+};
+use crate::{NonNullRef, Ref};
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::{fmt, ptr};
+
+/// Thread handle
+pub type Il2CppThread = NonNullRef<il2cpp_sys_rs::Il2CppThread, ()>;
+/// Nullable Thread handle
+pub type Il2CppThreadRef = Ref<il2cpp_sys_rs::Il2CppThread, ()>;
+
+thread_local! {
+    /// Number of live guards on this thread
+    static GUARD_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// Thread attached by the outermost guard, null if it was already attached
+    static ATTACHED: Cell<*mut il2cpp_sys_rs::Il2CppThread> = const { Cell::new(ptr::null_mut()) };
+}
+
+impl Il2CppThread {
+    /// Returns the managed thread of the calling thread, if it is attached
+    #[inline]
+    pub fn current() -> Option<Self> {
+        Self::from_ptr(unsafe { il2cpp_thread_current() })
+    }
+
+    /// Returns `true` if the calling thread is attached to the IL2CPP domain
+    #[inline]
+    pub fn is_attached() -> bool {
+        Self::current().is_some()
+    }
+
+    /// Runs `f` with the calling thread attached to the IL2CPP domain
+    ///
+    /// The thread is detached afterward, unless it was attached before the call.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Closure calling into the runtime
+    #[inline]
+    pub fn with_attached<R>(f: impl FnOnce() -> R) -> R {
+        let _guard = ThreadGuard::attach();
+        f()
+    }
+}
+
+/// Keeps the calling thread attached to the IL2CPP domain
+///
+/// Calling into the runtime from a thread that is not attached crashes the process.
+///
+/// Guards are reentrant: only the outermost guard attaches the thread, and detaches it on drop.
+/// A thread that was already attached, such as the Unity main thread, is never detached.
+pub struct ThreadGuard {
+    /// Guards are bound to the thread that created them
+    _marker: PhantomData<*mut ()>,
+}
+
+impl ThreadGuard {
+    /// Attaches the calling thread to the IL2CPP domain if needed
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime fails to attach the thread
+    #[track_caller]
+    pub fn attach() -> Self {
+        if GUARD_DEPTH.get() == 0 && !Il2CppThread::is_attached() {
+            let thread = unsafe { il2cpp_thread_attach(il2cpp_domain_get()) };
+            assert!(!thread.is_null(), "failed to attach the thread");
+            ATTACHED.set(thread);
+        }
+        GUARD_DEPTH.set(GUARD_DEPTH.get() + 1);
+
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the managed thread of the calling thread
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread was detached by the runtime
+    #[track_caller]
+    #[inline]
+    pub fn thread(&self) -> Il2CppThread {
+        Il2CppThread::current().expect("thread is attached")
+    }
+}
+
+impl Drop for ThreadGuard {
+    fn drop(&mut self) {
+        let depth = GUARD_DEPTH.get() - 1;
+        GUARD_DEPTH.set(depth);
+
+        if depth == 0 {
+            let thread = ATTACHED.replace(ptr::null_mut());
+            if !thread.is_null() {
+                unsafe { il2cpp_thread_detach(thread) };
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ThreadGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThreadGuard")
+            .field("depth", &GUARD_DEPTH.get())
+            .field("attached", &!ATTACHED.get().is_null())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::{Il2CppThread, ThreadGuard};
+    use crate::mock;
+    use crate::runtime::{il2cpp_domain_get, il2cpp_thread_attach, il2cpp_thread_detach};
+    use std::thread;
+
+    #[test]
+    fn nested_guards() {
+        mock::install();
+        // Each scenario runs on a fresh thread, which starts detached
+        thread::spawn(|| {
+            assert!(!Il2CppThread::is_attached());
+            let outer = ThreadGuard::attach();
+            assert!(Il2CppThread::is_attached());
+            let thread = outer.thread();
+
+            let inner = ThreadGuard::attach();
+            assert_eq!(inner.thread().as_ptr(), thread.as_ptr());
+            drop(inner);
+            assert!(Il2CppThread::is_attached());
+
+            Il2CppThread::with_attached(|| assert!(Il2CppThread::is_attached()));
+            assert!(Il2CppThread::is_attached());
+
+            drop(outer);
+            assert!(!Il2CppThread::is_attached());
+            assert!(Il2CppThread::current().is_none());
+
+            assert!(Il2CppThread::with_attached(Il2CppThread::is_attached));
+            assert!(!Il2CppThread::is_attached());
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn already_attached() {
+        mock::install();
+        thread::spawn(|| {
+            let thread = unsafe { il2cpp_thread_attach(il2cpp_domain_get()) };
+            assert!(Il2CppThread::is_attached());
+
+            let guard = ThreadGuard::attach();
+            Il2CppThread::with_attached(|| assert!(Il2CppThread::is_attached()));
+            drop(guard);
+            assert!(Il2CppThread::is_attached());
+            assert_eq!(Il2CppThread::current().unwrap().as_ptr(), thread);
+
+            unsafe { il2cpp_thread_detach(thread) };
+            assert!(!Il2CppThread::is_attached());
+        })
+        .join()
+        .unwrap();
+    }
+}
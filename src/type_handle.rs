@@ -0,0 +1,359 @@
+//! Il2CppType
+
+use crate::flags::{PARAM_ATTRIBUTE_IN, PARAM_ATTRIBUTE_OUT};
+use crate::runtime::{
+    il2cpp_class_from_il2cpp_type, il2cpp_free, il2cpp_type_equals, il2cpp_type_get_name,
+    il2cpp_type_get_object,
+};
+use crate::{Il2CppClass, Il2CppError, Il2CppObject, NonNullRef, Ref};
+use std::ffi::CStr;
+use std::{fmt, slice};
+
+/// Type handle
+pub type Il2CppTypeHandle = NonNullRef<il2cpp_sys_rs::Il2CppType, ()>;
+/// Nullable Type handle
+pub type Il2CppTypeHandleRef = Ref<il2cpp_sys_rs::Il2CppType, ()>;
+
+/// Kind of a type, mirrors `Il2CppTypeEnum` (ECMA-335 II.23.1.16)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum TypeKind {
+    End = 0x00,
+    Void = 0x01,
+    Boolean = 0x02,
+    Char = 0x03,
+    I1 = 0x04,
+    U1 = 0x05,
+    I2 = 0x06,
+    U2 = 0x07,
+    I4 = 0x08,
+    U4 = 0x09,
+    I8 = 0x0a,
+    U8 = 0x0b,
+    R4 = 0x0c,
+    R8 = 0x0d,
+    String = 0x0e,
+    /// Unmanaged pointer, `T*`
+    Ptr = 0x0f,
+    /// Managed reference, `ref T`
+    ByRef = 0x10,
+    ValueType = 0x11,
+    Class = 0x12,
+    /// Generic parameter of a type
+    Var = 0x13,
+    /// Multi-dimensional array
+    Array = 0x14,
+    /// Generic instance, such as `List<int>`
+    GenericInst = 0x15,
+    TypedByRef = 0x16,
+    I = 0x18,
+    U = 0x19,
+    FnPtr = 0x1b,
+    Object = 0x1c,
+    /// Single-dimensional zero-based array
+    SzArray = 0x1d,
+    /// Generic parameter of a method
+    MVar = 0x1e,
+    CModReqd = 0x1f,
+    CModOpt = 0x20,
+    Internal = 0x21,
+    Modifier = 0x40,
+    Sentinel = 0x41,
+    Pinned = 0x45,
+    Enum = 0x55,
+    /// Index into the IL2CPP type table, used in custom attribute blobs
+    Il2CppTypeIndex = 0xff,
+}
+
+impl TypeKind {
+    /// Converts a raw `Il2CppTypeEnum` value
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - Raw type enum value
+    ///
+    /// # Returns
+    ///
+    /// Type kind if `raw` is a known value, otherwise `None`
+    pub const fn from_raw(raw: u32) -> Option<Self> {
+        Some(match raw {
+            0x00 => Self::End,
+            0x01 => Self::Void,
+            0x02 => Self::Boolean,
+            0x03 => Self::Char,
+            0x04 => Self::I1,
+            0x05 => Self::U1,
+            0x06 => Self::I2,
+            0x07 => Self::U2,
+            0x08 => Self::I4,
+            0x09 => Self::U4,
+            0x0a => Self::I8,
+            0x0b => Self::U8,
+            0x0c => Self::R4,
+            0x0d => Self::R8,
+            0x0e => Self::String,
+            0x0f => Self::Ptr,
+            0x10 => Self::ByRef,
+            0x11 => Self::ValueType,
+            0x12 => Self::Class,
+            0x13 => Self::Var,
+            0x14 => Self::Array,
+            0x15 => Self::GenericInst,
+            0x16 => Self::TypedByRef,
+            0x18 => Self::I,
+            0x19 => Self::U,
+            0x1b => Self::FnPtr,
+            0x1c => Self::Object,
+            0x1d => Self::SzArray,
+            0x1e => Self::MVar,
+            0x1f => Self::CModReqd,
+            0x20 => Self::CModOpt,
+            0x21 => Self::Internal,
+            0x40 => Self::Modifier,
+            0x41 => Self::Sentinel,
+            0x45 => Self::Pinned,
+            0x55 => Self::Enum,
+            0xff => Self::Il2CppTypeIndex,
+            _ => return None,
+        })
+    }
+
+    /// Returns `true` for the built-in numeric, boolean and char types
+    #[inline]
+    pub const fn is_primitive(self) -> bool {
+        matches!(
+            self,
+            Self::Boolean
+                | Self::Char
+                | Self::I1
+                | Self::U1
+                | Self::I2
+                | Self::U2
+                | Self::I4
+                | Self::U4
+                | Self::I8
+                | Self::U8
+                | Self::R4
+                | Self::R8
+                | Self::I
+                | Self::U
+        )
+    }
+
+    /// Returns `true` for generic parameters
+    #[inline]
+    pub const fn is_generic_parameter(self) -> bool {
+        matches!(self, Self::Var | Self::MVar)
+    }
+}
+
+impl Il2CppTypeHandle {
+    /// Returns the raw `Il2CppTypeEnum` value
+    #[inline]
+    pub fn raw_kind(self) -> u32 {
+        self.as_ref().type_()
+    }
+
+    /// Returns the type kind
+    ///
+    /// # Returns
+    ///
+    /// Type kind, or `None` if the runtime uses a type enum value unknown to this crate,
+    /// see [`Self::raw_kind`]
+    #[inline]
+    pub fn kind(self) -> Option<TypeKind> {
+        TypeKind::from_raw(self.raw_kind())
+    }
+
+    /// Returns the raw type attributes
+    ///
+    /// Parameter attributes for parameter types, field attributes for field types.
+    #[inline]
+    pub fn attrs(self) -> u32 {
+        self.as_ref().attrs()
+    }
+
+    /// Returns `true` if the type is passed by reference, such as `ref`, `in` or `out` parameters
+    #[inline]
+    pub fn is_byref(self) -> bool {
+        self.as_ref().byref() != 0
+    }
+
+    /// Returns `true` if the type is an `out` parameter type
+    #[inline]
+    pub fn is_out(self) -> bool {
+        self.is_byref()
+            && self.attrs() & PARAM_ATTRIBUTE_OUT != 0
+            && self.attrs() & PARAM_ATTRIBUTE_IN == 0
+    }
+
+    /// Returns `true` if the type is an `in` parameter type
+    #[inline]
+    pub fn is_in(self) -> bool {
+        self.is_byref()
+            && self.attrs() & PARAM_ATTRIBUTE_IN != 0
+            && self.attrs() & PARAM_ATTRIBUTE_OUT == 0
+    }
+
+    /// Returns `true` if the type is an unmanaged pointer
+    #[inline]
+    pub fn is_pointer(self) -> bool {
+        self.raw_kind() == TypeKind::Ptr as u32
+    }
+
+    /// Returns `true` if the type is a value type
+    #[inline]
+    pub fn is_value_type(self) -> bool {
+        self.as_ref().valuetype() != 0
+    }
+
+    /// Returns the fully qualified type name, such as `System.Collections.Generic.List<System.Int32>`
+    ///
+    /// By-reference types end with `&`.
+    pub fn name(self) -> String {
+        unsafe {
+            let name = il2cpp_type_get_name(self.as_ptr());
+            let owned = CStr::from_ptr(name).to_string_lossy().into_owned();
+            il2cpp_free(name as _);
+            owned
+        }
+    }
+
+    /// Returns the class of the type
+    ///
+    /// # Returns
+    ///
+    /// Class handle if the runtime resolves one, otherwise `None`
+    #[inline]
+    pub fn class(self) -> Option<Il2CppClass> {
+        Il2CppClass::from_ptr(unsafe { il2cpp_class_from_il2cpp_type(self.as_ptr()) })
+    }
+
+    /// Returns the `System.Type` object of the type
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Il2CppError::AllocationFailed`] if the runtime fails to create the type object,
+    /// or [`Il2CppError::MissingExport`] if the runtime does not export `il2cpp_type_get_object`
+    #[inline]
+    pub fn system_type(self) -> Result<Il2CppObject, Il2CppError> {
+        Il2CppObject::from_ptr(unsafe { il2cpp_type_get_object(self.as_ptr()) }?)
+            .ok_or(Il2CppError::AllocationFailed("System.Type object"))
+    }
+
+    /// Returns the type arguments of a generic instance
+    ///
+    /// Empty if the type is not [`TypeKind::GenericInst`].
+    pub fn generic_arguments(self) -> &'static [Self] {
+        if self.raw_kind() != TypeKind::GenericInst as u32 {
+            return &[];
+        }
+
+        unsafe {
+            let generic_class = self.as_ref().data.generic_class;
+            let inst = generic_class
+                .as_ref()
+                .and_then(|generic_class| generic_class.context.class_inst.as_ref());
+            match inst {
+                Some(inst) if !inst.type_argv.is_null() => {
+                    slice::from_raw_parts(inst.type_argv as _, inst.type_argc as usize)
+                }
+                _ => &[],
+            }
+        }
+    }
+
+    /// Returns the element type of an array or pointer type
+    ///
+    /// # Returns
+    ///
+    /// Element type for [`TypeKind::SzArray`], [`TypeKind::Array`] and [`TypeKind::Ptr`],
+    /// otherwise `None`
+    pub fn element_type(self) -> Option<Self> {
+        let kind = self.raw_kind();
+        unsafe {
+            if kind == TypeKind::SzArray as u32 || kind == TypeKind::Ptr as u32 {
+                Self::from_ptr(self.as_ref().data.type_ as _)
+            } else if kind == TypeKind::Array as u32 {
+                let array = self.as_ref().data.array.as_ref()?;
+                Self::from_ptr(array.etype as _)
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns the rank of a multi-dimensional array type
+    ///
+    /// # Returns
+    ///
+    /// Rank for [`TypeKind::Array`], `1` for [`TypeKind::SzArray`], otherwise `None`
+    pub fn array_rank(self) -> Option<u8> {
+        let kind = self.raw_kind();
+        if kind == TypeKind::SzArray as u32 {
+            Some(1)
+        } else if kind == TypeKind::Array as u32 {
+            unsafe { self.as_ref().data.array.as_ref() }.map(|array| array.rank)
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq for Il2CppTypeHandle {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ptr() == other.as_ptr()
+            || unsafe { il2cpp_type_equals(self.as_ptr(), other.as_ptr()) }
+    }
+}
+
+impl Eq for Il2CppTypeHandle {}
+
+impl fmt::Display for Il2CppTypeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+impl fmt::Debug for Il2CppTypeHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Il2CppType")
+            .field("name", &self.name())
+            .field("kind", &TypeKind::from_raw(self.raw_kind()))
+            .field("byref", &self.is_byref())
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::mock;
+    use crate::{Il2CppImage, Il2CppSzArray, Il2CppTypeHandle, TypeKind};
+
+    #[test]
+    fn kinds() {
+        mock::install();
+        let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+        let type_ = int32.type_();
+        assert_eq!(type_.kind(), Some(TypeKind::I4));
+        assert_eq!(type_.name(), "System.Int32");
+        assert_eq!(type_.class().unwrap().as_ptr(), int32.as_ptr());
+
+        let array = Il2CppSzArray::<i32>::array_class_get(int32).unwrap().type_();
+        assert_eq!(array.kind(), Some(TypeKind::SzArray));
+        assert_eq!(array.element_type().unwrap(), type_);
+        assert_eq!(array.array_rank(), Some(1));
+    }
+
+    #[test]
+    fn unknown_kind() {
+        mock::install();
+        let int32 = Il2CppImage::corlib().find_class(c"System", c"Int32").unwrap();
+        let mut raw = *int32.type_().as_ref();
+        raw.set_type_(0x60);
+
+        let type_ = Il2CppTypeHandle::from_ptr(&mut raw).unwrap();
+        assert_eq!(type_.raw_kind(), 0x60);
+        assert_eq!(type_.kind(), None);
+    }
+}
@@ -0,0 +1,73 @@
+#![cfg(all(feature = "mock", feature = "serde"))]
+
+use bindings::game::{Player, Player_Inventory};
+use il2cpp_rs::bindgen::Generator;
+use il2cpp_rs::mock::{self, ClassBuilder};
+use il2cpp_rs::{Il2CppClass, Il2CppDomain, Il2CppImage, Il2CppObject, Il2CppString};
+use std::ffi::CStr;
+
+/// Bindings generated from the classes of [`player_class`], compiled against the crate
+#[allow(dead_code)]
+mod bindings {
+    include!("fixtures/bindings.rs");
+}
+
+fn corlib(name: &CStr) -> Il2CppClass {
+    Il2CppImage::corlib().find_class(c"System", name).unwrap()
+}
+
+/// `Game.Player` of the `BindgenTests` assembly, with a field of each kind and a nested class
+fn player_class() -> Il2CppClass {
+    let image = mock::image(c"BindgenTests.dll");
+    let player = ClassBuilder::new(image, c"Game", c"Player")
+        .field(c"health", corlib(c"Int32"))
+        .field(c"name", corlib(c"String"))
+        .static_field(c"count", corlib(c"Int32"))
+        .method(c"get_Health", &[], corlib(c"Int32"), |call| {
+            let health = call.this().unwrap_non_null().class().find_field(c"health").unwrap();
+            let health: i32 = unsafe { health.value(call.this().as_ptr() as _) };
+            Ok(Il2CppObject::box_value(corlib(c"Int32"), &health).unwrap().into())
+        })
+        .method(c"Scale", &[(c"factor", corlib(c"Int32"))], corlib(c"Int32"), |call| {
+            let factor = unsafe { call.arg::<i32>(0) };
+            Ok(Il2CppObject::box_value(corlib(c"Int32"), &(factor * 2)).unwrap().into())
+        })
+        .property(c"Health", Some(c"get_Health"), None)
+        .build();
+    ClassBuilder::new(image, c"", c"Inventory").nested_in(player).build();
+    player
+}
+
+#[test]
+fn generated_bindings() {
+    let player = player_class();
+
+    let mut domain = Il2CppDomain::get().snapshot();
+    domain.assemblies.retain(|assembly| assembly.name == "BindgenTests");
+    let generated = Generator::new(&domain).generate();
+    assert_eq!(
+        generated,
+        include_str!("fixtures/bindings.rs"),
+        "tests/fixtures/bindings.rs is outdated"
+    );
+
+    assert_eq!(Player::class().as_ptr(), player.as_ptr());
+    let inventory = Il2CppObject::new(Player_Inventory::class()).unwrap();
+    assert!(Player::from_object(inventory).is_none());
+
+    let object = Il2CppObject::new(player).unwrap();
+    let player = Player::from_object(object).unwrap();
+    assert_eq!(player.object().as_ptr(), object.as_ptr());
+
+    player.set_health(75);
+    assert_eq!(player.health(), 75);
+    assert_eq!(player.get_Health().unwrap(), 75);
+    assert_eq!(player.Scale(21).unwrap(), 42);
+
+    let name = Il2CppString::new(c"Alice").unwrap();
+    player.set_name(name.into());
+    assert_eq!(player.name().unwrap_non_null().to_utf8_lossy(), "Alice");
+
+    Player::set_count(3);
+    assert_eq!(Player::count(), 3);
+}
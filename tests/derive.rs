@@ -0,0 +1,14 @@
+#![cfg(feature = "derive")]
+
+#[test]
+fn derive_layout() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/pass.rs");
+    cases.pass("tests/ui/skip_and_name.rs");
+    cases.compile_fail("tests/ui/missing_class.rs");
+    cases.compile_fail("tests/ui/missing_repr_c.rs");
+    cases.compile_fail("tests/ui/generic_struct.rs");
+    cases.compile_fail("tests/ui/tuple_struct.rs");
+    cases.compile_fail("tests/ui/invalid_attribute.rs");
+    cases.compile_fail("tests/ui/not_copy.rs");
+}
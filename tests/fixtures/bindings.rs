@@ -0,0 +1,163 @@
+// Generated by il2cpp-rs, do not edit
+
+pub mod game {
+    static PLAYER_CLASS: ::il2cpp_rs::LazyClass =
+        ::il2cpp_rs::LazyClass::new(c"BindgenTests", c"Game", c"Player");
+
+    /// `Game.Player`, from `BindgenTests`
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug)]
+    #[repr(transparent)]
+    pub struct Player(::il2cpp_rs::Il2CppObject);
+
+    #[allow(non_snake_case, clippy::too_many_arguments)]
+    impl Player {
+        /// Returns the `Game.Player` class
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class is not loaded
+        pub fn class() -> ::il2cpp_rs::Il2CppClass {
+            PLAYER_CLASS.get()
+        }
+
+        /// Wraps `object` if it is an instance of the class, or of a class derived from it
+        pub fn from_object(object: ::il2cpp_rs::Il2CppObject) -> Option<Self> {
+            let class = Self::class();
+            let object_class = object.class();
+            (object_class.as_ptr() == class.as_ptr() || object_class.is_subclass_of(class, false))
+                .then_some(Self(object))
+        }
+
+        /// Returns the wrapped object
+        pub fn object(self) -> ::il2cpp_rs::Il2CppObject {
+            self.0
+        }
+
+        /// Returns the `System.Int32 health` field
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class has no such field
+        pub fn health_field() -> ::il2cpp_rs::FieldInfo {
+            static FIELD: ::il2cpp_rs::LazyField = ::il2cpp_rs::LazyField::new(&PLAYER_CLASS, c"health");
+            FIELD.get()
+        }
+
+        /// Reads the `System.Int32 health` field
+        pub fn health(self) -> i32 {
+            unsafe { Self::health_field().value(self.0.as_ptr() as _) }
+        }
+
+        /// Writes the `System.Int32 health` field
+        pub fn set_health(self, value: i32) {
+            unsafe { Self::health_field().set_value(self.0.as_ptr() as _, value) }
+        }
+
+        /// Returns the `System.String name` field
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class has no such field
+        pub fn name_field() -> ::il2cpp_rs::FieldInfo {
+            static FIELD: ::il2cpp_rs::LazyField = ::il2cpp_rs::LazyField::new(&PLAYER_CLASS, c"name");
+            FIELD.get()
+        }
+
+        /// Reads the `System.String name` field
+        pub fn name(self) -> ::il2cpp_rs::Il2CppStringRef {
+            unsafe { Self::name_field().value(self.0.as_ptr() as _) }
+        }
+
+        /// Writes the `System.String name` field
+        pub fn set_name(self, value: ::il2cpp_rs::Il2CppStringRef) {
+            unsafe { Self::name_field().set_value(self.0.as_ptr() as _, value) }
+        }
+
+        /// Returns the `System.Int32 count` field
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class has no such field
+        pub fn count_field() -> ::il2cpp_rs::FieldInfo {
+            static FIELD: ::il2cpp_rs::LazyField = ::il2cpp_rs::LazyField::new(&PLAYER_CLASS, c"count");
+            FIELD.get()
+        }
+
+        /// Reads the `System.Int32 count` field
+        pub fn count() -> i32 {
+            unsafe { Self::count_field().static_value() }
+        }
+
+        /// Writes the `System.Int32 count` field
+        pub fn set_count(value: i32) {
+            unsafe { Self::count_field().set_static_value(value) }
+        }
+
+        /// Returns the `System.Int32 get_Health()` method
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class has no such method
+        pub fn get_Health_method() -> ::il2cpp_rs::MethodInfo {
+            static METHOD: ::il2cpp_rs::LazyMethod =
+                ::il2cpp_rs::LazyMethod::exact(&PLAYER_CLASS, c"get_Health", &[]);
+            METHOD.get()
+        }
+
+        /// Calls the `System.Int32 Health` property getter
+        pub fn get_Health(self) -> Result<i32, ::il2cpp_rs::InvokeError> {
+            Self::get_Health_method().call(self.0.into(), ())
+        }
+
+        /// Returns the `System.Int32 Scale(System.Int32 factor)` method
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class has no such method
+        pub fn Scale_method() -> ::il2cpp_rs::MethodInfo {
+            static METHOD: ::il2cpp_rs::LazyMethod =
+                ::il2cpp_rs::LazyMethod::exact(&PLAYER_CLASS, c"Scale", &["System.Int32"]);
+            METHOD.get()
+        }
+
+        /// Calls `System.Int32 Scale(System.Int32 factor)`
+        pub fn Scale(self, factor: i32) -> Result<i32, ::il2cpp_rs::InvokeError> {
+            Self::Scale_method().call(self.0.into(), (factor,))
+        }
+    }
+
+    static PLAYER_INVENTORY_CLASS: ::il2cpp_rs::LazyClass =
+        ::il2cpp_rs::LazyClass::new(c"BindgenTests", c"Game", c"Player/Inventory");
+
+    /// `Game.Player+Inventory`, from `BindgenTests`
+    #[allow(non_camel_case_types)]
+    #[derive(Clone, Copy, Debug)]
+    #[repr(transparent)]
+    pub struct Player_Inventory(::il2cpp_rs::Il2CppObject);
+
+    #[allow(non_snake_case, clippy::too_many_arguments)]
+    impl Player_Inventory {
+        /// Returns the `Game.Player+Inventory` class
+        ///
+        /// # Panics
+        ///
+        /// Panics if the class is not loaded
+        pub fn class() -> ::il2cpp_rs::Il2CppClass {
+            PLAYER_INVENTORY_CLASS.get()
+        }
+
+        /// Wraps `object` if it is an instance of the class, or of a class derived from it
+        pub fn from_object(object: ::il2cpp_rs::Il2CppObject) -> Option<Self> {
+            let class = Self::class();
+            let object_class = object.class();
+            (object_class.as_ptr() == class.as_ptr() || object_class.is_subclass_of(class, false))
+                .then_some(Self(object))
+        }
+
+        /// Returns the wrapped object
+        pub fn object(self) -> ::il2cpp_rs::Il2CppObject {
+            self.0
+        }
+    }
+}
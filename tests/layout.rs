@@ -0,0 +1,135 @@
+#![cfg(all(feature = "derive", feature = "mock"))]
+
+use il2cpp_rs::mock::{self, ClassBuilder};
+use il2cpp_rs::{
+    FieldMismatch, Il2CppClass, Il2CppImage, Il2CppLayout, Il2CppObject, Il2CppString,
+    Il2CppStringRef, LayoutError,
+};
+use std::ffi::CStr;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Layout.Player", assembly = "LayoutTests")]
+#[repr(C)]
+struct Player {
+    #[il2cpp(skip)]
+    header: il2cpp_rs::sys::Il2CppObject,
+    health: i32,
+    #[il2cpp(name = "m_Name")]
+    name: Il2CppStringRef,
+    flag: u8,
+}
+
+/// Mirrors `Layout.Player` with a wrong size, a missing field and a static field
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Layout.Player")]
+#[repr(C)]
+struct Outdated {
+    #[il2cpp(skip)]
+    header: il2cpp_rs::sys::Il2CppObject,
+    health: i64,
+    armor: i32,
+    count: i32,
+}
+
+#[derive(Il2CppLayout, Clone, Copy)]
+#[il2cpp(class = "Layout.Vector2")]
+#[repr(C)]
+struct Vector2 {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Layout.Player+Inventory")]
+#[repr(C)]
+struct Inventory {
+    #[il2cpp(skip)]
+    header: il2cpp_rs::sys::Il2CppObject,
+}
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Layout.Missing")]
+#[repr(C)]
+struct Missing {
+    value: i32,
+}
+
+fn corlib(name: &CStr) -> Il2CppClass {
+    Il2CppImage::corlib().find_class(c"System", name).unwrap()
+}
+
+/// Declares the mirrored classes once, tests run in parallel
+fn player_class() -> Il2CppClass {
+    static PLAYER: std::sync::OnceLock<usize> = std::sync::OnceLock::new();
+    let player = *PLAYER.get_or_init(|| {
+        let image = mock::image(c"LayoutTests.dll");
+        let player = ClassBuilder::new(image, c"Layout", c"Player")
+            .field(c"health", corlib(c"Int32"))
+            .field(c"m_Name", corlib(c"String"))
+            .field(c"flag", corlib(c"Byte"))
+            .static_field(c"count", corlib(c"Int32"))
+            .build();
+        ClassBuilder::new(image, c"", c"Inventory").nested_in(player).build();
+        ClassBuilder::new(image, c"Layout", c"Vector2")
+            .value_type()
+            .field(c"x", corlib(c"Single"))
+            .field(c"y", corlib(c"Single"))
+            .build();
+        player.as_ptr() as usize
+    });
+    Il2CppClass::from_ptr(player as _).unwrap()
+}
+
+#[test]
+fn validate() {
+    let player = player_class();
+
+    assert_eq!(Player::validate().unwrap().as_ptr(), player.as_ptr());
+    assert!(Inventory::validate().is_ok());
+    Vector2::validate().unwrap_or_else(|err| panic!("{err}"));
+    assert_eq!(
+        Missing::validate().unwrap_err(),
+        LayoutError::ClassNotFound("Layout.Missing")
+    );
+}
+
+#[test]
+fn mismatches() {
+    player_class();
+
+    let err = Outdated::validate().unwrap_err();
+    let LayoutError::Mismatch { class, mismatches } = &err else {
+        panic!("expected a mismatch, found {err}");
+    };
+    assert_eq!(*class, "Layout.Player");
+    assert_eq!(mismatches.len(), 3);
+    assert!(matches!(mismatches[0], FieldMismatch::Size { runtime: 4, .. }));
+    assert!(matches!(mismatches[1], FieldMismatch::Missing(_)));
+    assert!(matches!(mismatches[2], FieldMismatch::Static(_)));
+    assert_eq!(
+        err.to_string(),
+        "layout of `Layout.Player` does not match the runtime:\n  \
+         `health` is 8 bytes wide, expected 4\n  \
+         `armor` is not a field of the class\n  \
+         `count` is a static field"
+    );
+}
+
+#[test]
+fn accessors() {
+    let object = Il2CppObject::new(player_class()).unwrap();
+    let player = unsafe { &mut *(object.as_ptr() as *mut Player) };
+
+    unsafe { player.set_health(12) };
+    assert_eq!(player.health(), 12);
+    let name = Il2CppString::new(c"Alice").unwrap();
+    unsafe { player.set_name(name.into()) };
+    assert_eq!(player.name().as_ptr(), name.as_ptr());
+    assert_eq!(player.flag(), 0);
+    let _ = &player.header;
+
+    // Value types are written in place, unboxed
+    let mut vector = Vector2 { x: 1.0, y: 2.0 };
+    unsafe { vector.set_y(5.0) };
+    assert_eq!((vector.x(), vector.y()), (1.0, 5.0));
+}
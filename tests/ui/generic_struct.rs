@@ -0,0 +1,10 @@
+use il2cpp_rs::Il2CppLayout;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Slot`1")]
+#[repr(C)]
+struct Slot<T> {
+    value: T,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use il2cpp_rs::Il2CppLayout;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Player")]
+#[repr(C)]
+struct Player {
+    #[il2cpp(rename = "m_Health")]
+    health: i32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use il2cpp_rs::Il2CppLayout;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Player")]
+struct Player {
+    health: i32,
+}
+
+fn main() {}
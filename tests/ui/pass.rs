@@ -0,0 +1,28 @@
+use il2cpp_rs::{Il2CppLayout, Il2CppStringRef};
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Player", assembly = "Assembly-CSharp")]
+#[repr(C)]
+pub struct Player {
+    pub health: i32,
+    speed: f32,
+    name: Il2CppStringRef,
+}
+
+fn main() {
+    assert_eq!(Player::CLASS, "Game.Player");
+    assert_eq!(Player::ASSEMBLY, Some(c"Assembly-CSharp"));
+    let names: Vec<_> = Player::FIELDS.iter().map(|field| field.name).collect();
+    assert_eq!(names, ["health", "speed", "name"]);
+    assert_eq!(Player::FIELDS[1].offset, 4);
+    assert_eq!(Player::FIELDS[2].size, size_of::<Il2CppStringRef>());
+
+    let player = Player {
+        health: 100,
+        speed: 2.5,
+        name: Il2CppStringRef::null(),
+    };
+    assert_eq!(player.health(), 100);
+    assert_eq!(player.speed(), 2.5);
+    assert!(player.name().is_null());
+}
@@ -0,0 +1,22 @@
+use il2cpp_rs::Il2CppLayout;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Player+Inventory")]
+#[repr(C)]
+struct Inventory {
+    #[il2cpp(skip)]
+    header: il2cpp_rs::sys::Il2CppObject,
+    #[il2cpp(name = "m_Capacity")]
+    capacity: i32,
+    #[il2cpp(skip)]
+    padding: [u8; 4],
+}
+
+fn main() {
+    assert_eq!(Inventory::ASSEMBLY, None);
+    assert_eq!(Inventory::FIELDS.len(), 1);
+    let field = Inventory::FIELDS[0];
+    assert_eq!(field.name, "capacity");
+    assert_eq!(field.managed_name, c"m_Capacity");
+    assert_eq!(field.offset, size_of::<il2cpp_rs::sys::Il2CppObject>());
+}
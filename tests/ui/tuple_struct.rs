@@ -0,0 +1,8 @@
+use il2cpp_rs::Il2CppLayout;
+
+#[derive(Il2CppLayout)]
+#[il2cpp(class = "Game.Vector2")]
+#[repr(C)]
+struct Vector2(f32, f32);
+
+fn main() {}